@@ -24,6 +24,7 @@ from calm.cli.counter import counter  # noqa: E402
 from calm.cli.gate import gate  # noqa: E402
 from calm.cli.init_cmd import init  # noqa: E402
 from calm.cli.install_cmd import install_cmd  # noqa: E402
+from calm.cli.reembed import reembed  # noqa: E402
 from calm.cli.review import review  # noqa: E402
 from calm.cli.server import server  # noqa: E402
 from calm.cli.session import session  # noqa: E402
@@ -38,6 +39,7 @@ cli.add_command(init)
 cli.add_command(install_cmd, name="install")
 cli.add_command(server)
 cli.add_command(status)
+cli.add_command(reembed)
 
 # Register orchestration commands
 cli.add_command(task)
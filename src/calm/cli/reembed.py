@@ -0,0 +1,125 @@
+"""CALM reembed command."""
+
+from __future__ import annotations
+
+import asyncio
+from typing import TYPE_CHECKING
+
+import click
+
+from calm.config import settings
+
+if TYPE_CHECKING:
+    from calm.embedding.migration import Reembedder, ReembedStats
+    from calm.storage.metadata import MetadataStore
+
+
+@click.command()
+@click.option(
+    "--collection",
+    "collections",
+    multiple=True,
+    help="Collection to re-embed (repeatable; default: all)",
+)
+@click.option(
+    "--force",
+    is_flag=True,
+    help="Re-embed even collections already embedded with the configured model",
+)
+def reembed(collections: tuple[str, ...], force: bool) -> None:
+    """Re-embed stored collections with the configured embedding models.
+
+    Run after changing embedding.code_model or embedding.semantic_model.
+    Each collection embedded with another model is re-embedded from its
+    stored payloads into a shadow collection, which then replaces it in
+    one step. The server does the same on start when
+    embedding.reembed_on_startup is true.
+    """
+    from calm.embedding.migration import REEMBED_COLLECTIONS
+    from calm.server.daemon import is_server_running
+
+    invalid = [c for c in collections if c not in REEMBED_COLLECTIONS]
+    if invalid:
+        raise click.BadParameter(
+            f"{', '.join(invalid)}. Valid options: {', '.join(REEMBED_COLLECTIONS)}",
+            param_hint="--collection",
+        )
+
+    if is_server_running():
+        raise click.ClickException(
+            "The CALM server is running. Stop it first (calm server stop)."
+        )
+
+    results = asyncio.run(_reembed(list(collections) or None, force))
+
+    if not results:
+        click.echo("All collections are up to date.")
+        return
+
+    failed = 0
+    for stats in results:
+        if stats.error:
+            failed += 1
+            click.echo(f"Failed to re-embed {stats.collection}: {stats.error}")
+        else:
+            click.echo(
+                f"Re-embedded {stats.collection} with {stats.model}: "
+                f"{stats.points_embedded} points in {stats.duration_ms / 1000:.1f}s"
+            )
+    if failed:
+        raise click.ClickException(f"{failed} collection(s) failed to re-embed")
+
+
+async def _reembed(collections: list[str] | None, force: bool) -> list[ReembedStats]:
+    """Re-embed stale (or all, with force) collections."""
+    reembedder, metadata_store = await _create_reembedder()
+    try:
+        return await reembedder.reembed_stale(collections, force=force)
+    finally:
+        await metadata_store.close()
+
+
+async def _create_reembedder() -> tuple[Reembedder, MetadataStore]:
+    """Open the configured stores and embedders."""
+    from calm.embedding.migration import Reembedder
+    from calm.embedding.registry import EmbeddingRegistry
+    from calm.search.searcher import KEYWORD_TEXT_FIELDS
+    from calm.storage.base import VectorStore
+    from calm.storage.keyword_index import KeywordIndex, KeywordIndexedStore
+    from calm.storage.metadata import MetadataStore
+
+    vector_store: VectorStore
+    if settings.vector_store == "sqlite":
+        from calm.storage.sqlite import SqliteVectorStore
+
+        vector_store = SqliteVectorStore(settings.vector_db_path)
+    else:
+        from calm.storage.qdrant import QdrantVectorStore
+
+        vector_store = QdrantVectorStore(url=settings.qdrant_url)
+
+    # Keeps the BM25 index in step with swapped collections
+    vector_store = KeywordIndexedStore(
+        vector_store,
+        KeywordIndex(settings.keyword_index_path, KEYWORD_TEXT_FIELDS),
+    )
+
+    registry = EmbeddingRegistry(
+        code_model=settings.code_model,
+        semantic_model=settings.semantic_model,
+        code_provider=settings.code_provider,
+        semantic_provider=settings.semantic_provider,
+        provider_options=settings.embedding_provider_options,
+    )
+    metadata_store = MetadataStore(settings.db_path)
+    await metadata_store.initialize()
+
+    reembedder = Reembedder(
+        vector_store,
+        metadata_store,
+        registry.get_code_embedder(),
+        registry.get_semantic_embedder(),
+        settings.code_model,
+        settings.semantic_model,
+    )
+    return reembedder, metadata_store
@@ -15,11 +15,15 @@ def server() -> None:
 @click.option(
     "--foreground", "-f", is_flag=True, help="Run in foreground (don't daemonize)"
 )
-def start(foreground: bool) -> None:
+@click.option(
+    "--watch", "-w", is_flag=True, help="Re-index indexed projects as files change"
+)
+def start(foreground: bool, watch: bool) -> None:
     """Start the CALM server daemon.
 
     By default, starts as a background daemon. Use --foreground to run
-    in the foreground (useful for debugging).
+    in the foreground (useful for debugging). Use --watch to keep projects
+    indexed with index_codebase up to date as their files change.
     """
     from calm.server.daemon import is_server_running, run_foreground, start_daemon
 
@@ -30,10 +34,10 @@ def start(foreground: bool) -> None:
     host, port = settings.server_host, settings.server_port
     if foreground:
         click.echo(f"Starting CALM server on {host}:{port}...")
-        run_foreground()
+        run_foreground(watch=watch)
     else:
         click.echo(f"Starting CALM server daemon on {host}:{port}...")
-        start_daemon()
+        start_daemon(watch=watch)
 
 
 @server.command("stop")
@@ -64,7 +68,10 @@ def status() -> None:
 
 
 @server.command("restart")
-def restart() -> None:
+@click.option(
+    "--watch", "-w", is_flag=True, help="Re-index indexed projects as files change"
+)
+def restart(watch: bool) -> None:
     """Restart the CALM server daemon."""
     from calm.server.daemon import is_server_running, start_daemon, stop_server
 
@@ -74,4 +81,4 @@ def restart() -> None:
 
     host, port = settings.server_host, settings.server_port
     click.echo(f"Starting CALM server daemon on {host}:{port}...")
-    start_daemon()
+    start_daemon(watch=watch)
@@ -74,7 +74,10 @@ def status(ctx: click.Context) -> None:
     # Configuration
     click.echo("Configuration:")
     click.echo(f"  Home: {settings.home}")
-    click.echo(f"  Qdrant: {settings.qdrant_url}")
+    if settings.vector_store == "sqlite":
+        click.echo(f"  Vector store: sqlite ({settings.vector_db_path})")
+    else:
+        click.echo(f"  Qdrant: {settings.qdrant_url}")
 
 
 @status.command()
@@ -2,12 +2,21 @@
 
 Provides centralized configuration for all CALM components.
 All settings support environment variable overrides with CALM_ prefix.
+Values in ~/.calm/config.yaml apply below environment variables.
 """
 
+import os
 from pathlib import Path
+from typing import Any, Literal
 
+import yaml
 from pydantic import BaseModel, Field
-from pydantic_settings import BaseSettings, SettingsConfigDict
+from pydantic.fields import FieldInfo
+from pydantic_settings import (
+    BaseSettings,
+    PydanticBaseSettingsSource,
+    SettingsConfigDict,
+)
 
 
 class IndexerSettings(BaseModel):
@@ -17,6 +26,61 @@ class IndexerSettings(BaseModel):
         default=100,
         description="Number of embeddings to generate per batch",
     )
+    parse_workers: int = Field(
+        default=0,
+        description="Processes for parallel parsing (0 = CPU count, up to 8)",
+    )
+    watch_debounce_seconds: float = Field(
+        default=1.0,
+        description="Quiet period before watch mode re-indexes changed files",
+    )
+    max_payload_code_chars: int = Field(
+        default=8000,
+        description="Maximum characters of unit source stored in the search payload",
+    )
+    chunk_overlap_tokens: int = Field(
+        default=32,
+        description="Tokens of overlap between chunks of units too long to embed",
+    )
+
+
+class SearchSettings(BaseModel):
+    """Settings for hybrid search."""
+
+    fusion: Literal["rrf", "weighted"] = Field(
+        default="rrf",
+        description="How hybrid search fuses semantic and keyword rankings",
+    )
+    rrf_k: int = Field(
+        default=60,
+        description="Reciprocal Rank Fusion rank constant",
+    )
+    semantic_weight: float = Field(
+        default=0.5,
+        description="Weight of semantic results in fusion",
+    )
+    keyword_weight: float = Field(
+        default=0.5,
+        description="Weight of keyword results in fusion",
+    )
+    candidate_multiplier: int = Field(
+        default=3,
+        description="Candidates fetched per list, as a multiple of the limit",
+    )
+
+
+class RerankSettings(BaseModel):
+    """Settings for the cross-encoder rerank stage of search."""
+
+    model: str = Field(
+        default="cross-encoder/ms-marco-MiniLM-L-6-v2",
+        description="Cross-encoder model used to rerank search candidates",
+    )
+    collections: dict[str, int] = Field(
+        default_factory=dict,
+        description="Collections to rerank, mapped to the top-N candidates "
+        "rescored per query (empty disables reranking)",
+    )
 
 
 class ContextSettings(BaseModel):
@@ -58,6 +122,11 @@ class ToolSettings(BaseModel):
 CALM_HOME = Path.home() / ".calm"
 CALM_DB = CALM_HOME / "metadata.db"
 CALM_CONFIG = CALM_HOME / "config.yaml"
+CALM_VECTOR_DB = CALM_HOME / "vectors.db"
+CALM_KEYWORD_DB = CALM_HOME / "keyword.db"
+
+# Environment variable naming the config.yaml to read instead
+CONFIG_FILE_ENV = "CALM_CONFIG_FILE"
 
 # Default config.yaml content
 DEFAULT_CONFIG = """\
@@ -70,16 +139,123 @@ server:
   port: 6335
   log_level: info
 
-# Embedding settings
+# Embedding settings (after changing a model, run `calm reembed`, or set
+# reembed_on_startup to re-embed stale collections when the server starts)
 embedding:
   code_model: sentence-transformers/all-MiniLM-L6-v2
   semantic_model: nomic-ai/nomic-embed-text-v1.5
+  # Provider per purpose: sentence-transformers, nomic, http (an
+  # OpenAI-compatible /v1/embeddings server) or one installed under the
+  # calm.embedding_providers entry point group
+  code_provider: sentence-transformers
+  semantic_provider: nomic
+  reembed_on_startup: false
+  # Keyword arguments passed to the provider of each purpose (code or
+  # semantic), or to every use of a provider when keyed by its name. The
+  # http provider needs the model's dimension.
+  # provider_options:
+  #   code:
+  #     url: http://localhost:8080/v1/embeddings
+  #     dimension: 384
+  #     max_tokens: 512
+  #   semantic:
+  #     url: http://localhost:8081/v1/embeddings
+  #     dimension: 768
 
 # Qdrant settings
 qdrant:
   url: http://localhost:6333
+
+# Vector store backend: qdrant (server, see `calm install`) or sqlite
+# (embedded, no Docker needed; stored at ~/.calm/vectors.db)
+vector_store:
+  backend: qdrant
+
+# Hybrid search: fuse semantic and keyword rankings with rrf (reciprocal
+# rank fusion) or weighted (min-max normalized scores)
+search:
+  fusion: rrf
+  semantic_weight: 0.5
+  keyword_weight: 0.5
+
+# Cross-encoder reranking of the top-N search candidates, per collection
+# (loads an extra ~90 MB model on first use)
+# rerank:
+#   model: cross-encoder/ms-marco-MiniLM-L-6-v2
+#   collections:
+#     code_units: 50
+#     memories: 30
 """
 
+# config.yaml (section, key) -> CalmSettings field
+_CONFIG_FILE_KEYS = {
+    ("server", "host"): "server_host",
+    ("server", "port"): "server_port",
+    ("server", "log_level"): "log_level",
+    ("embedding", "code_model"): "code_model",
+    ("embedding", "semantic_model"): "semantic_model",
+    ("embedding", "code_provider"): "code_provider",
+    ("embedding", "semantic_provider"): "semantic_provider",
+    ("embedding", "provider_options"): "embedding_provider_options",
+    ("embedding", "reembed_on_startup"): "reembed_on_startup",
+    ("qdrant", "url"): "qdrant_url",
+    ("vector_store", "backend"): "vector_store",
+    ("vector_store", "path"): "vector_db_path",
+}
+
+# config.yaml sections read whole into nested CalmSettings models
+_CONFIG_FILE_SECTIONS = ("search", "rerank")
+
+
+def load_config_file(path: Path) -> dict[str, Any]:
+    """Read settings from a config.yaml file.
+
+    Returns:
+        CalmSettings field values; unknown keys are ignored and a missing
+        file gives an empty dict.
+    """
+    try:
+        data = yaml.safe_load(path.read_text())
+    except OSError:
+        return {}
+    if not isinstance(data, dict):
+        return {}
+
+    values: dict[str, Any] = {}
+    for (section, key), field_name in _CONFIG_FILE_KEYS.items():
+        section_data = data.get(section)
+        if isinstance(section_data, dict) and key in section_data:
+            values[field_name] = section_data[key]
+    for section in _CONFIG_FILE_SECTIONS:
+        if isinstance(data.get(section), dict):
+            values[section] = data[section]
+    return values
+
+
+def config_file_path() -> Path:
+    """Locate config.yaml.
+
+    ``CALM_CONFIG_FILE`` names the file directly; otherwise it is
+    ``config.yaml`` under ``CALM_HOME`` (default ``~/.calm``).
+    """
+    if path := os.environ.get(CONFIG_FILE_ENV):
+        return Path(path).expanduser()
+    if home := os.environ.get("CALM_HOME"):
+        return Path(home).expanduser() / "config.yaml"
+    return CALM_CONFIG
+
+
+class _ConfigFileSource(PydanticBaseSettingsSource):
+    """Settings source for config.yaml (see ``config_file_path``)."""
+
+    def get_field_value(
+        self, field: FieldInfo, field_name: str
+    ) -> tuple[Any, str, bool]:
+        return None, field_name, False
+
+    def __call__(self) -> dict[str, Any]:
+        return load_config_file(config_file_path())
+
 
 class CalmSettings(BaseSettings):
     """CALM server configuration.
@@ -127,6 +303,20 @@ class CalmSettings(BaseSettings):
         description="Path to server log file",
     )
 
+    # Vector store settings
+    vector_store: Literal["qdrant", "sqlite"] = Field(
+        default="qdrant",
+        description="Vector store backend (qdrant server or embedded sqlite)",
+    )
+    vector_db_path: Path = Field(
+        default=CALM_VECTOR_DB,
+        description="Path to the SQLite vector database (sqlite backend)",
+    )
+    keyword_index_path: Path = Field(
+        default=CALM_KEYWORD_DB,
+        description="Path to the BM25 keyword index database",
+    )
+
     # Qdrant settings
     qdrant_url: str = Field(
         default="http://localhost:6333",
@@ -142,6 +332,22 @@ class CalmSettings(BaseSettings):
         default="nomic-ai/nomic-embed-text-v1.5",
         description="Model for semantic embeddings",
     )
+    code_provider: str = Field(
+        default="sentence-transformers",
+        description="Embedding provider for the code model",
+    )
+    semantic_provider: str = Field(
+        default="nomic",
+        description="Embedding provider for the semantic model",
+    )
+    embedding_provider_options: dict[str, dict[str, Any]] = Field(
+        default_factory=dict,
+        description="Keyword arguments per embedding purpose or provider name",
+    )
+    reembed_on_startup: bool = Field(
+        default=False,
+        description="Re-embed collections written with another model on start",
+    )
 
     @property
     def workflows_dir(self) -> Path:
@@ -193,9 +399,29 @@ class CalmSettings(BaseSettings):
 
     # Nested settings
     indexer: IndexerSettings = Field(default_factory=IndexerSettings)
+    search: SearchSettings = Field(default_factory=SearchSettings)
+    rerank: RerankSettings = Field(default_factory=RerankSettings)
     context: ContextSettings = Field(default_factory=ContextSettings)
     tool: ToolSettings = Field(default_factory=ToolSettings)
 
+    @classmethod
+    def settings_customise_sources(
+        cls,
+        settings_cls: type[BaseSettings],
+        init_settings: PydanticBaseSettingsSource,
+        env_settings: PydanticBaseSettingsSource,
+        dotenv_settings: PydanticBaseSettingsSource,
+        file_secret_settings: PydanticBaseSettingsSource,
+    ) -> tuple[PydanticBaseSettingsSource, ...]:
+        """Read config.yaml after init arguments and environment variables."""
+        return (
+            init_settings,
+            env_settings,
+            dotenv_settings,
+            _ConfigFileSource(settings_cls),
+            file_secret_settings,
+        )
+
 
 # Module-level singleton
 settings = CalmSettings()
@@ -1,10 +1,11 @@
 """Embedding services for CALM."""
 
-from .base import EmbeddingModelError, EmbeddingService, Vector
+from .base import EmbeddingModelError, EmbeddingService, Reranker, Vector
+from .http import HttpEmbedding
 from .minilm import MiniLMEmbedding
-from .mock import MockEmbeddingService
+from .mock import MockEmbeddingService, MockReranker
 from .nomic import NomicEmbedding
-from .registry import get_code_embedder, get_semantic_embedder
+from .registry import get_code_embedder, get_reranker, get_semantic_embedder
 
 __all__ = [
     "EmbeddingService",
@@ -12,7 +13,11 @@ __all__ = [
     "Vector",
     "NomicEmbedding",
     "MiniLMEmbedding",
+    "HttpEmbedding",
     "MockEmbeddingService",
+    "Reranker",
+    "MockReranker",
     "get_semantic_embedder",
     "get_code_embedder",
+    "get_reranker",
 ]
@@ -61,3 +61,40 @@ class EmbeddingService(ABC):
             int: Number of dimensions in output vectors
         """
         ...
+
+    @property
+    def max_tokens(self) -> int | None:
+        """Return the maximum input length in tokens, if known.
+
+        Longer inputs are truncated by the model, so indexers size the text
+        they embed by this.
+
+        Returns:
+            Maximum number of input tokens, or None if unknown
+        """
+        return None
+
+
+class Reranker(ABC):
+    """Abstract base class for second-stage rerankers.
+
+    A reranker scores (query, document) pairs jointly, which is slower but
+    more accurate than comparing independently embedded vectors, so it is
+    only applied to the top candidates of a first-stage search.
+    """
+
+    @abstractmethod
+    async def score(self, query: str, documents: list[str]) -> list[float]:
+        """Score each document's relevance to the query.
+
+        Args:
+            query: Search query
+            documents: Candidate document texts
+
+        Returns:
+            One relevance score per document (higher is more relevant)
+
+        Raises:
+            EmbeddingModelError: If scoring fails
+        """
+        ...
@@ -0,0 +1,83 @@
+"""Cross-encoder reranker using sentence-transformers."""
+
+import asyncio
+from functools import partial
+from typing import Any
+
+import torch
+from sentence_transformers import CrossEncoder  # type: ignore[import-untyped]
+
+from .base import EmbeddingModelError, Reranker
+
+
+class CrossEncoderReranker(Reranker):
+    """Reranker backed by a local cross-encoder model.
+
+    The default model (ms-marco MiniLM) is small enough to rescore a few
+    dozen candidates per query on CPU.
+
+    Attributes:
+        model: The loaded CrossEncoder model
+    """
+
+    def __init__(
+        self,
+        model_name: str = "cross-encoder/ms-marco-MiniLM-L-6-v2",
+        cache_dir: str | None = None,
+    ) -> None:
+        """Initialize the cross-encoder reranker.
+
+        Args:
+            model_name: Name of the model to load
+            cache_dir: Optional directory for caching model files
+
+        Raises:
+            EmbeddingModelError: If model loading fails
+        """
+        self._model_name = model_name
+        try:
+            self.model = CrossEncoder(model_name, cache_folder=cache_dir)
+            # Force CPU to avoid MPS memory leak (same as the embedders)
+            if torch.backends.mps.is_available():
+                self.model.model = self.model.model.to(torch.device("cpu"))
+        except OSError as e:
+            raise EmbeddingModelError(
+                f"Failed to download/load model '{model_name}': {e}. "
+                f"If this is the first run, check your network connection. "
+                f"Models are downloaded from HuggingFace Hub on first use."
+            ) from e
+        except Exception as e:
+            raise EmbeddingModelError(
+                f"Failed to load model '{model_name}': {e}"
+            ) from e
+
+    async def score(self, query: str, documents: list[str]) -> list[float]:
+        """Score each document's relevance to the query.
+
+        Args:
+            query: Search query
+            documents: Candidate document texts
+
+        Returns:
+            One relevance score per document (higher is more relevant)
+
+        Raises:
+            EmbeddingModelError: If scoring fails
+        """
+        if not documents:
+            return []
+
+        try:
+            # Run CPU-bound scoring in executor to avoid blocking event loop
+            loop = asyncio.get_event_loop()
+            scores: Any = await loop.run_in_executor(
+                None,
+                partial(
+                    self.model.predict,
+                    [(query, document) for document in documents],
+                    show_progress_bar=False,
+                ),
+            )
+            return [float(s) for s in scores]
+        except Exception as e:
+            raise EmbeddingModelError(f"Failed to score documents: {e}") from e
@@ -0,0 +1,124 @@
+"""Embedding service backed by a local HTTP embedding server."""
+
+from typing import Any
+
+import httpx
+import numpy as np
+
+from .base import EmbeddingModelError, EmbeddingService, Vector
+
+
+class HttpEmbedding(EmbeddingService):
+    """Embedding service for an OpenAI-compatible embeddings endpoint.
+
+    Works with llama.cpp's server, Ollama, text-embeddings-inference, vLLM
+    and anything else serving ``POST /v1/embeddings``. The dimension must
+    be configured: collections are created from it before the first
+    request, and probing the server for it would block the event loop.
+    """
+
+    def __init__(
+        self,
+        model_name: str,
+        dimension: int,
+        url: str = "http://localhost:8080/v1/embeddings",
+        max_tokens: int | None = None,
+        api_key: str | None = None,
+        timeout: float = 60.0,
+    ) -> None:
+        """Initialize the HTTP embedding service.
+
+        Args:
+            model_name: Model name sent with each request
+            dimension: Embedding dimension of the model
+            url: Embeddings endpoint
+            max_tokens: Maximum input length of the model, if known
+            api_key: Optional bearer token
+            timeout: Request timeout in seconds
+        """
+        self._model_name = model_name
+        self._url = url
+        self._dimension = dimension
+        self._max_tokens = max_tokens
+        self._headers = {"Authorization": f"Bearer {api_key}"} if api_key else {}
+        self._timeout = timeout
+
+    async def embed(self, text: str) -> Vector:
+        """Generate embedding for a single text.
+
+        Args:
+            text: Input text to embed
+
+        Returns:
+            Float32 numpy array
+
+        Raises:
+            EmbeddingModelError: If the request fails
+        """
+        return (await self.embed_batch([text]))[0]
+
+    async def embed_batch(self, texts: list[str]) -> list[Vector]:
+        """Generate embeddings for multiple texts in one request.
+
+        Args:
+            texts: List of input texts to embed
+
+        Returns:
+            List of float32 numpy arrays
+
+        Raises:
+            EmbeddingModelError: If the request fails or returns vectors of
+                another dimension
+        """
+        if not texts:
+            return []
+
+        try:
+            async with httpx.AsyncClient(timeout=self._timeout) as client:
+                response = await client.post(
+                    self._url, json=self._request(texts), headers=self._headers
+                )
+                response.raise_for_status()
+                vectors = self._parse(response.json(), len(texts))
+        except EmbeddingModelError:
+            raise
+        except Exception as e:
+            raise EmbeddingModelError(
+                f"Embedding request to {self._url} failed: {e}"
+            ) from e
+
+        if len(vectors[0]) != self._dimension:
+            raise EmbeddingModelError(
+                f"{self._url} returned {len(vectors[0])}-dimensional embeddings, "
+                f"but the configured dimension is {self._dimension}"
+            )
+        return vectors
+
+    @property
+    def dimension(self) -> int:
+        """Return the configured embedding dimension."""
+        return self._dimension
+
+    @property
+    def max_tokens(self) -> int | None:
+        """Return the configured maximum input length, if any."""
+        return self._max_tokens
+
+    def _request(self, texts: list[str]) -> dict[str, Any]:
+        """Build the request body."""
+        return {"model": self._model_name, "input": texts}
+
+    def _parse(self, body: Any, count: int) -> list[Vector]:
+        """Extract vectors, in input order, from a response body."""
+        try:
+            data = sorted(body["data"], key=lambda item: item.get("index", 0))
+            vectors = [np.asarray(item["embedding"], dtype=np.float32) for item in data]
+        except (KeyError, TypeError) as e:
+            raise EmbeddingModelError(
+                f"Unexpected response from {self._url}: missing {e}"
+            ) from e
+        if len(vectors) != count:
+            raise EmbeddingModelError(
+                f"Expected {count} embeddings from {self._url}, got {len(vectors)}"
+            )
+        return vectors
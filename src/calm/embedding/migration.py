@@ -0,0 +1,600 @@
+"""Re-embedding of stored collections after an embedding model change.
+
+Points are re-embedded from their payloads: the text that was originally
+embedded is rebuilt from stored fields (``content`` for memories, signature,
+docstring and code for code units and their chunks, the axis template for
+experiences, and so on). Each collection is re-embedded into a shadow
+collection that is swapped in with ``VectorStore.swap_collection`` once
+complete. The model and dimension used are recorded with
+``MetadataStore.set_collection_embedding``.
+
+Re-embedding a collection holds its lock (``collection_lock``), so the
+background job and the code indexer never re-embed it at the same time.
+
+While the server re-embeds a stale collection, its stored vectors come
+from the old model but queries and writes are embedded with the new one.
+The server's ``ReembedGuard`` therefore rejects vector searches on it and
+queues its writes, which are applied to the new collection right after the
+swap (or to the old one if re-embedding fails, which leaves it stale).
+Points written to the old collection by other processes are picked up by a
+catch-up pass just before the swap.
+"""
+
+import asyncio
+import hashlib
+import json
+import time
+import uuid
+from collections.abc import AsyncIterator
+from dataclasses import dataclass, field
+from typing import Any
+
+import structlog
+
+from calm.config import settings
+from calm.ghap.persister import axis_embedding_text
+from calm.git.analyzer import commit_embedding_text
+from calm.indexers.utils import max_embedding_chars, unit_embedding_text
+from calm.search.collections import CollectionName
+from calm.storage.base import CollectionInfo, SearchResult, Vector, VectorStore
+from calm.storage.metadata import MetadataStore
+
+from .base import EmbeddingService
+
+logger = structlog.get_logger(__name__)
+
+# Re-embeddable collection -> embedder purpose ("code" or "semantic")
+REEMBED_COLLECTIONS: dict[str, str] = {
+    CollectionName.MEMORIES: "semantic",
+    CollectionName.CODE_UNITS: "code",
+    CollectionName.EXPERIENCES_FULL: "semantic",
+    CollectionName.EXPERIENCES_STRATEGY: "semantic",
+    CollectionName.EXPERIENCES_SURPRISE: "semantic",
+    CollectionName.EXPERIENCES_ROOT_CAUSE: "semantic",
+    CollectionName.VALUES: "semantic",
+    CollectionName.COMMITS: "semantic",
+}
+
+_EXPERIENCE_AXES = {
+    collection: axis for axis, collection in CollectionName.EXPERIENCE_AXES.items()
+}
+
+# Strong references to running background jobs (the loop keeps weak ones)
+_jobs: set[asyncio.Task[Any]] = set()
+
+# Per-collection locks held while a collection is re-embedded
+_locks: dict[str, asyncio.Lock] = {}
+
+
+def collection_lock(collection: str) -> asyncio.Lock:
+    """Get the lock serializing re-embedding of a collection."""
+    return _locks.setdefault(collection, asyncio.Lock())
+
+
+class CollectionReembeddingError(Exception):
+    """Raised when vector-searching a collection that is being re-embedded."""
+
+
+@dataclass
+class ReembedStats:
+    """Outcome of re-embedding one collection."""
+
+    collection: str
+    model: str
+    points_embedded: int = 0
+    duration_ms: int = 0
+    error: str | None = None
+
+
+def embedding_text(
+    collection: str, payload: dict[str, Any], max_tokens: int | None = None
+) -> str:
+    """Rebuild the text a point was embedded from.
+
+    Args:
+        collection: Collection the point belongs to
+        payload: The point's payload
+        max_tokens: Maximum input length of the model, used to size code
+
+    Raises:
+        ValueError: If the collection isn't re-embeddable
+    """
+    if collection == CollectionName.MEMORIES:
+        return str(payload.get("content") or "")
+    if collection == CollectionName.CODE_UNITS:
+        code = payload.get("code") or ""
+        if payload.get("chunk_index") == 0 and payload.get("chunk_count", 1) > 1:
+            # The first chunk of a long unit covers only its first lines
+            lines = payload["chunk_end_line"] - payload["start_line"] + 1
+            code = "\n".join(code.split("\n")[:lines])
+        return unit_embedding_text(
+            payload.get("signature") or "",
+            payload.get("docstring"),
+            code,
+            max_embedding_chars(max_tokens),
+        )
+    if collection in _EXPERIENCE_AXES:
+        return axis_embedding_text(_EXPERIENCE_AXES[collection], payload)
+    if collection == CollectionName.VALUES:
+        return str(payload.get("text") or "")
+    if collection == CollectionName.COMMITS:
+        return commit_embedding_text(
+            payload.get("message") or "",
+            payload.get("files_changed") or [],
+            payload.get("author") or "",
+        )
+    raise ValueError(f"Collection '{collection}' cannot be re-embedded")
+
+
+async def reembed_collection(
+    vector_store: VectorStore,
+    embedding_service: EmbeddingService,
+    collection: str,
+    model: str,
+    metadata_store: MetadataStore,
+    batch_size: int | None = None,
+    only_if_stale: bool = False,
+) -> ReembedStats:
+    """Re-embed a collection into a shadow collection and swap it in.
+
+    Waits for any re-embedding of the same collection already running.
+
+    Args:
+        vector_store: Store holding the collection
+        embedding_service: Embedder for the new model
+        collection: Collection to re-embed
+        model: Name of the new model (recorded for the collection)
+        metadata_store: Store recording each collection's model
+        batch_size: Points embedded per batch (default from config)
+        only_if_stale: Skip the collection if it is already recorded as
+            embedded with ``model`` (e.g. by a run this one waited for)
+
+    Returns:
+        Re-embedding statistics
+
+    Raises:
+        Exception: If embedding or writing fails; the old collection is
+            left untouched and the shadow collection is deleted
+    """
+    async with collection_lock(collection):
+        dimension = embedding_service.dimension
+        if only_if_stale:
+            recorded = await metadata_store.get_collection_embedding(collection)
+            if recorded and (recorded.model, recorded.dimension) == (model, dimension):
+                return ReembedStats(collection=collection, model=model)
+        return await _reembed(
+            vector_store,
+            embedding_service,
+            collection,
+            model,
+            metadata_store,
+            batch_size or settings.indexer.embedding_batch_size,
+        )
+
+
+async def _reembed(
+    vector_store: VectorStore,
+    embedding_service: EmbeddingService,
+    collection: str,
+    model: str,
+    metadata_store: MetadataStore,
+    batch_size: int,
+) -> ReembedStats:
+    """Re-embed a collection while holding its lock."""
+    start_time = time.time()
+    dimension = embedding_service.dimension
+    stats = ReembedStats(collection=collection, model=model)
+
+    if await vector_store.get_collection_info(collection) is None:
+        # Nothing stored yet; the collection is created with the new model
+        await metadata_store.set_collection_embedding(collection, model, dimension)
+        return stats
+
+    shadow = f"{collection}__reembed_{uuid.uuid4().hex[:12]}"
+    await vector_store.create_collection(shadow, dimension=dimension)
+    logger.info(
+        "reembed.started", collection=collection, shadow=shadow, model=model
+    )
+
+    try:
+        copied: dict[str, str] = {}
+        stats.points_embedded = await _copy_points(
+            vector_store, embedding_service, collection, shadow, copied, batch_size
+        )
+
+        # Catch up with points written, changed or deleted while copying
+        current = dict(copied)
+        stats.points_embedded += await _copy_points(
+            vector_store, embedding_service, collection, shadow, current, batch_size
+        )
+        for id in copied.keys() - current.keys():
+            await vector_store.delete(shadow, id)
+
+        await vector_store.swap_collection(collection, shadow)
+    except BaseException:
+        await _discard_shadow(vector_store, collection, shadow)
+        raise
+
+    await metadata_store.set_collection_embedding(collection, model, dimension)
+    stats.duration_ms = int((time.time() - start_time) * 1000)
+    logger.info(
+        "reembed.completed",
+        collection=collection,
+        model=model,
+        points=stats.points_embedded,
+        duration_ms=stats.duration_ms,
+    )
+    return stats
+
+
+async def _discard_shadow(
+    vector_store: VectorStore, collection: str, shadow: str
+) -> None:
+    """Delete the shadow collection of a failed re-embedding.
+
+    The shadow is kept if the original collection is gone (a swap that
+    failed halfway), since it then holds the only copy of the points.
+    """
+    try:
+        if await vector_store.get_collection_info(collection) is None:
+            logger.error(
+                "reembed.swap_incomplete",
+                collection=collection,
+                shadow=shadow,
+                action="kept_shadow",
+            )
+            return
+        await vector_store.delete_collection(shadow)
+    except Exception as e:
+        logger.warning("reembed.cleanup_failed", shadow=shadow, error=str(e))
+
+
+async def _copy_points(
+    vector_store: VectorStore,
+    embedding_service: EmbeddingService,
+    source: str,
+    target: str,
+    copied: dict[str, str],
+    batch_size: int,
+) -> int:
+    """Embed the points of ``source`` that aren't in ``copied`` yet.
+
+    ``source`` is read one batch at a time. ``copied`` maps point IDs to a
+    digest of the payload they were embedded with; it is updated in place
+    to reflect ``source`` as it is now (points whose payload changed are
+    embedded again, deleted points are dropped).
+
+    Returns:
+        Number of points embedded
+    """
+    embedded = 0
+    seen: set[str] = set()
+    async for points in vector_store.scroll_batches(source, batch_size):
+        seen.update(point.id for point in points)
+        digests = {point.id: _payload_digest(point.payload) for point in points}
+        pending = [
+            point for point in points if copied.get(point.id) != digests[point.id]
+        ]
+        if not pending:
+            continue
+
+        vectors = await embedding_service.embed_batch(
+            [
+                embedding_text(source, point.payload, embedding_service.max_tokens)
+                for point in pending
+            ]
+        )
+        await vector_store.upsert_batch(
+            target,
+            [point.id for point in pending],
+            vectors,
+            [point.payload for point in pending],
+        )
+        for point in pending:
+            copied[point.id] = digests[point.id]
+        embedded += len(pending)
+
+    for id in copied.keys() - seen:
+        del copied[id]
+    return embedded
+
+
+def _payload_digest(payload: dict[str, Any]) -> str:
+    """Digest identifying a payload's content."""
+    encoded = json.dumps(payload, sort_keys=True, default=str).encode()
+    return hashlib.sha256(encoded).hexdigest()
+
+
+@dataclass
+class _HeldWrite:
+    """A write to a held collection, applied after its swap."""
+
+    ids: list[str]
+    vectors: list[Vector] | None = None  # None for a delete
+    payloads: list[dict[str, Any]] = field(default_factory=list)
+
+
+class ReembedGuard(VectorStore):
+    """Vector store wrapper guarding collections while they are re-embedded.
+
+    A held collection still stores vectors of the old model, while queries
+    and writes are embedded with the new one. Vector searches on it raise
+    ``CollectionReembeddingError``, and its writes are queued and applied
+    when it is released, which ``swap_collection`` does right after
+    swapping in the re-embedded points. Reads pass through to the old
+    collection, so they don't see queued writes.
+
+    Only collections that are known to be stale should be held, since
+    vector search on them is unavailable until they are released.
+    """
+
+    def __init__(self, store: VectorStore) -> None:
+        self.store = store
+        self._held: dict[str, list[_HeldWrite]] = {}
+
+    def hold(self, collection: str) -> None:
+        """Start holding back writes to a collection."""
+        self._held.setdefault(collection, [])
+
+    def is_held(self, collection: str) -> bool:
+        """Check whether a collection's writes are being held back."""
+        return collection in self._held
+
+    async def release(self, collection: str) -> None:
+        """Stop holding a collection, applying its queued writes.
+
+        After a failed re-embedding the writes land in the old collection.
+        It is still recorded as embedded with the old model, so the next
+        re-embedding replaces their new-model vectors along with the rest;
+        until then nothing the indexer recorded as written goes missing.
+        """
+        writes = self._held.pop(collection, [])
+        for write in writes:
+            try:
+                if write.vectors is None:
+                    for id in write.ids:
+                        await self.store.delete(collection, id)
+                else:
+                    await self.store.upsert_batch(
+                        collection, write.ids, write.vectors, write.payloads
+                    )
+            except Exception as e:
+                logger.error(
+                    "reembed.held_write_failed", collection=collection, error=str(e)
+                )
+
+    async def create_collection(
+        self, name: str, dimension: int, distance: str = "cosine"
+    ) -> None:
+        await self.store.create_collection(name, dimension, distance)
+
+    async def delete_collection(self, name: str) -> None:
+        await self.store.delete_collection(name)
+        # Queued writes would recreate points of the deleted collection
+        self._held.pop(name, None)
+
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        await self.store.swap_collection(name, replacement)
+        await self.release(name)
+
+    async def upsert(
+        self,
+        collection: str,
+        id: str,
+        vector: Vector,
+        payload: dict[str, Any],
+    ) -> None:
+        await self.upsert_batch(collection, [id], [vector], [payload])
+
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        if collection in self._held:
+            self._held[collection].append(_HeldWrite(ids, vectors, payloads))
+            return
+        await self.store.upsert_batch(collection, ids, vectors, payloads)
+
+    async def search(
+        self,
+        collection: str,
+        query: Vector,
+        limit: int = 10,
+        filters: dict[str, Any] | None = None,
+    ) -> list[SearchResult]:
+        if collection in self._held:
+            raise CollectionReembeddingError(
+                f"Collection '{collection}' is being re-embedded with a new "
+                "model; semantic search is available again once it finishes"
+            )
+        return await self.store.search(collection, query, limit, filters)
+
+    async def delete(self, collection: str, id: str) -> None:
+        if collection in self._held:
+            self._held[collection].append(_HeldWrite([id]))
+            return
+        await self.store.delete(collection, id)
+
+    async def scroll(
+        self,
+        collection: str,
+        limit: int = 100,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> list[SearchResult]:
+        return await self.store.scroll(collection, limit, filters, with_vectors)
+
+    async def scroll_batches(
+        self,
+        collection: str,
+        batch_size: int = 256,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> AsyncIterator[list[SearchResult]]:
+        async for results in self.store.scroll_batches(
+            collection, batch_size, filters, with_vectors
+        ):
+            yield results
+
+    async def count(
+        self, collection: str, filters: dict[str, Any] | None = None
+    ) -> int:
+        return await self.store.count(collection, filters)
+
+    async def get(
+        self, collection: str, id: str, with_vector: bool = False
+    ) -> SearchResult | None:
+        return await self.store.get(collection, id, with_vector)
+
+    async def get_collection_info(self, name: str) -> CollectionInfo | None:
+        return await self.store.get_collection_info(name)
+
+
+class Reembedder:
+    """Re-embeds stored collections with the configured models."""
+
+    def __init__(
+        self,
+        vector_store: VectorStore,
+        metadata_store: MetadataStore,
+        code_embedder: EmbeddingService,
+        semantic_embedder: EmbeddingService,
+        code_model: str,
+        semantic_model: str,
+        guard: ReembedGuard | None = None,
+    ) -> None:
+        """Initialize the re-embedder.
+
+        Args:
+            vector_store: Store holding the collections
+            metadata_store: Store recording each collection's model
+            code_embedder: Embedder for code units
+            semantic_embedder: Embedder for all other collections
+            code_model: Name of the code embedding model
+            semantic_model: Name of the semantic embedding model
+            guard: Guard of the server's store, which holds each stale
+                collection while it is re-embedded
+        """
+        self.vector_store = vector_store
+        self.metadata_store = metadata_store
+        self.guard = guard
+        self._embedders = {
+            "code": (code_embedder, code_model),
+            "semantic": (semantic_embedder, semantic_model),
+        }
+
+    def embedder_for(self, collection: str) -> tuple[EmbeddingService, str]:
+        """Get the embedder and model name used for a collection."""
+        return self._embedders[REEMBED_COLLECTIONS[collection]]
+
+    async def is_stale(self, collection: str) -> bool:
+        """Check whether a collection was embedded with another model.
+
+        A collection is stale if its recorded model differs from the
+        configured one. Collections with no recorded model (written before
+        models were recorded) are stale only if their dimension differs
+        from the model's, which loads the model.
+        """
+        info = await self.vector_store.get_collection_info(collection)
+        if info is None:
+            return False
+
+        embedder, model = self.embedder_for(collection)
+        recorded = await self.metadata_store.get_collection_embedding(collection)
+        if recorded is not None:
+            return recorded.model != model
+        return info.dimension != embedder.dimension
+
+    async def reembed(
+        self, collection: str, only_if_stale: bool = False
+    ) -> ReembedStats:
+        """Re-embed one collection with its configured model."""
+        embedder, model = self.embedder_for(collection)
+        return await reembed_collection(
+            self.vector_store,
+            embedder,
+            collection,
+            model,
+            self.metadata_store,
+            only_if_stale=only_if_stale,
+        )
+
+    async def reembed_stale(
+        self, collections: list[str] | None = None, force: bool = False
+    ) -> list[ReembedStats]:
+        """Re-embed every stale collection.
+
+        Every collection is checked first. Collections that are current but
+        have no recorded model get the configured model recorded; stale
+        ones are held by the guard, if any, until they are re-embedded. A
+        failure is logged and reported in the collection's stats without
+        stopping the other collections.
+
+        Args:
+            collections: Collections to check (default all re-embeddable)
+            force: Re-embed even collections that look current
+
+        Returns:
+            Statistics for each collection that was re-embedded or failed
+        """
+        results: list[ReembedStats] = []
+        stale: list[str] = []
+        for collection in collections or list(REEMBED_COLLECTIONS):
+            try:
+                if force or await self.is_stale(collection):
+                    stale.append(collection)
+                else:
+                    await self._record_current(collection, self.model_for(collection))
+            except Exception as e:
+                results.append(self._failed(collection, e))
+
+        if self.guard:
+            for collection in stale:
+                self.guard.hold(collection)
+        for collection in stale:
+            try:
+                results.append(await self.reembed(collection, only_if_stale=not force))
+            except Exception as e:
+                results.append(self._failed(collection, e))
+            finally:
+                if self.guard:
+                    await self.guard.release(collection)
+        return results
+
+    def model_for(self, collection: str) -> str:
+        """Get the configured model name of a collection."""
+        return self.embedder_for(collection)[1]
+
+    def _failed(self, collection: str, error: Exception) -> ReembedStats:
+        """Log a collection's failed check or re-embedding."""
+        logger.error(
+            "reembed.failed", collection=collection, error=str(error), exc_info=True
+        )
+        return ReembedStats(
+            collection=collection, model=self.model_for(collection), error=str(error)
+        )
+
+    async def _record_current(self, collection: str, model: str) -> None:
+        """Record the configured model for an existing, unrecorded collection."""
+        if await self.metadata_store.get_collection_embedding(collection):
+            return
+        info = await self.vector_store.get_collection_info(collection)
+        if info is not None:
+            await self.metadata_store.set_collection_embedding(
+                collection, model, info.dimension
+            )
+
+
+def start_reembed_job(reembedder: Reembedder) -> asyncio.Task[list[ReembedStats]]:
+    """Re-embed stale collections in a background task.
+
+    Only the collections found stale are held by the re-embedder's guard,
+    so the others stay fully searchable while the job runs.
+    """
+    task = asyncio.create_task(reembedder.reembed_stale())
+    _jobs.add(task)
+    task.add_done_callback(_jobs.discard)
+    return task
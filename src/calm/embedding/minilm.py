@@ -73,6 +73,16 @@ class MiniLMEmbedding(EmbeddingService):
             raise EmbeddingModelError("Model did not return embedding dimension")
         return int(dim)
 
+    @property
+    def max_tokens(self) -> int | None:
+        """Get the model's maximum sequence length.
+
+        Returns:
+            Maximum number of input tokens, or None if the model has no limit
+        """
+        length = self.model.max_seq_length
+        return int(length) if length else None
+
     async def embed(self, text: str) -> Vector:
         """Generate embedding for a single text.
 
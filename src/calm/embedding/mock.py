@@ -1,10 +1,10 @@
-"""Mock embedding implementation for testing."""
+"""Mock embedding and reranker implementations for testing."""
 
 import hashlib
 
 import numpy as np
 
-from .base import EmbeddingService, Vector
+from .base import EmbeddingService, Reranker, Vector
 
 
 class MockEmbeddingService(EmbeddingService):
@@ -16,13 +16,15 @@ class MockEmbeddingService(EmbeddingService):
     Produces configurable dimensional vectors (default 768 to match Nomic).
     """
 
-    def __init__(self, dimension: int = 768) -> None:
+    def __init__(self, dimension: int = 768, max_tokens: int | None = None) -> None:
         """Initialize the mock embedding service.
 
         Args:
             dimension: Dimensionality of the embeddings to produce
+            max_tokens: Maximum input length to report
         """
         self._dimension = dimension
+        self._max_tokens = max_tokens
 
     async def embed(self, text: str) -> Vector:
         """Generate deterministic embedding for a single text.
@@ -55,6 +57,11 @@ class MockEmbeddingService(EmbeddingService):
         """
         return self._dimension
 
+    @property
+    def max_tokens(self) -> int | None:
+        """Return the configured maximum input length."""
+        return self._max_tokens
+
     def _hash_to_vector(self, text: str) -> Vector:
         """Convert text to deterministic vector using hash.
 
@@ -78,3 +85,30 @@ class MockEmbeddingService(EmbeddingService):
             vector = vector / norm
 
         return vector
+
+
+class MockReranker(Reranker):
+    """Mock reranker scoring documents by query term overlap.
+
+    A document's score is the fraction of distinct query terms it contains
+    (case-insensitive), so tests can predict the reranked order without
+    loading a cross-encoder model.
+    """
+
+    async def score(self, query: str, documents: list[str]) -> list[float]:
+        """Score each document by the fraction of query terms it contains.
+
+        Args:
+            query: Search query
+            documents: Candidate document texts
+
+        Returns:
+            One score in [0.0, 1.0] per document
+        """
+        terms = set(query.lower().split())
+        if not terms:
+            return [0.0] * len(documents)
+        return [
+            sum(1 for term in terms if term in document.lower()) / len(terms)
+            for document in documents
+        ]
@@ -133,3 +133,13 @@ class NomicEmbedding(EmbeddingService):
         if dim is None:
             raise EmbeddingModelError("Model did not return embedding dimension")
         return int(dim)
+
+    @property
+    def max_tokens(self) -> int | None:
+        """Get the model's maximum sequence length.
+
+        Returns:
+            Maximum number of input tokens, or None if the model has no limit
+        """
+        length = self.model.max_seq_length
+        return int(length) if length else None
@@ -0,0 +1,99 @@
+"""Embedding provider discovery.
+
+A provider is a factory called as ``provider(model_name, **options)`` that
+returns an ``EmbeddingService``, usually the service class itself. CALM
+ships three:
+
+- ``sentence-transformers``: any sentence-transformers model
+- ``nomic``: sentence-transformers with Nomic's remote code
+- ``http``: a local server with an OpenAI-compatible ``/v1/embeddings``
+  endpoint (llama.cpp, Ollama, text-embeddings-inference, vLLM, ...)
+
+Other packages add providers (ONNX runtime, GGUF, ...) under the
+``calm.embedding_providers`` entry point group::
+
+    [project.entry-points."calm.embedding_providers"]
+    onnx = "calm_onnx:OnnxEmbedding"
+
+Providers are selected per purpose with ``embedding.code_provider`` and
+``embedding.semantic_provider`` in config.yaml; options come from
+``embedding.provider_options.code`` (or ``.semantic``), falling back to
+``embedding.provider_options.<provider>``.
+
+Fork Safety: providers are imported only when an embedder is created.
+"""
+
+from collections.abc import Callable
+from importlib.metadata import EntryPoint, entry_points
+from typing import Any
+
+from .base import EmbeddingModelError, EmbeddingService
+
+ENTRY_POINT_GROUP = "calm.embedding_providers"
+
+# Built-in providers; these can't be replaced by entry points
+BUILTIN_PROVIDERS: dict[str, str] = {
+    "sentence-transformers": "calm.embedding.minilm:MiniLMEmbedding",
+    "nomic": "calm.embedding.nomic:NomicEmbedding",
+    "http": "calm.embedding.http:HttpEmbedding",
+}
+
+EmbeddingProvider = Callable[..., EmbeddingService]
+
+
+def _entry_points() -> dict[str, EntryPoint]:
+    """Get all providers by name, built-ins last so they take precedence."""
+    found = {ep.name: ep for ep in entry_points(group=ENTRY_POINT_GROUP)}
+    for name, value in BUILTIN_PROVIDERS.items():
+        found[name] = EntryPoint(name=name, value=value, group=ENTRY_POINT_GROUP)
+    return found
+
+
+def available_providers() -> list[str]:
+    """List the names of all installed embedding providers."""
+    return sorted(_entry_points())
+
+
+def load_provider(name: str) -> EmbeddingProvider:
+    """Load an embedding provider by name.
+
+    Raises:
+        EmbeddingModelError: If no such provider is installed or it fails to
+            import
+    """
+    entry_point = _entry_points().get(name)
+    if entry_point is None:
+        available = ", ".join(available_providers())
+        raise EmbeddingModelError(
+            f"Unknown embedding provider '{name}'. Available: {available}"
+        )
+    try:
+        provider: EmbeddingProvider = entry_point.load()
+    except Exception as e:
+        raise EmbeddingModelError(
+            f"Failed to load embedding provider '{name}': {e}"
+        ) from e
+    return provider
+
+
+def create_embedder(
+    provider: str, model_name: str, options: dict[str, Any] | None = None
+) -> EmbeddingService:
+    """Create an embedding service with a provider.
+
+    Args:
+        provider: Provider name
+        model_name: Model passed to the provider
+        options: Extra keyword arguments for the provider
+
+    Raises:
+        EmbeddingModelError: If the provider can't be loaded or doesn't
+            return an ``EmbeddingService``
+    """
+    service = load_provider(provider)(model_name, **(options or {}))
+    if not isinstance(service, EmbeddingService):
+        raise EmbeddingModelError(
+            f"Embedding provider '{provider}' returned "
+            f"{type(service).__name__}, not an EmbeddingService"
+        )
+    return service
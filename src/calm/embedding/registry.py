@@ -1,15 +1,20 @@
-"""Embedding service registry for dual embedding models.
+"""Embedding service registry for dual embedding models and the reranker.
 
-Fork Safety: This module defers importing embedding implementations (MiniLM,
-Nomic) until actually needed. PyTorch must not be loaded before fork() completes
-in daemon mode.
+Embedders are created by the provider configured for each purpose (see
+``calm.embedding.providers``).
+
+Fork Safety: This module defers importing embedding providers and the
+cross-encoder reranker until actually needed. PyTorch must not be loaded
+before fork() completes in daemon mode.
 """
 
 from __future__ import annotations
 
+from typing import Any
+
 import structlog
 
-from .base import EmbeddingService
+from .base import EmbeddingService, Reranker
 
 logger = structlog.get_logger()
 
@@ -20,23 +25,42 @@ class EmbeddingRegistry:
     Provides lazy-loaded embedders by purpose:
     - Code embedder: Fast model (configurable) for code indexing/search
     - Semantic embedder: Quality model (configurable) for memories/GHAP/clustering
+    - Reranker: Cross-encoder (configurable) rescoring top search candidates
 
     Models are loaded on first use and cached for the server's lifetime.
     Thread safety is not required since the MCP server is single-threaded asyncio.
     Model names and dimensions determined by settings.
     """
 
-    def __init__(self, code_model: str, semantic_model: str) -> None:
+    def __init__(
+        self,
+        code_model: str,
+        semantic_model: str,
+        rerank_model: str = "cross-encoder/ms-marco-MiniLM-L-6-v2",
+        code_provider: str = "sentence-transformers",
+        semantic_provider: str = "nomic",
+        provider_options: dict[str, dict[str, Any]] | None = None,
+    ) -> None:
         """Initialize the registry with model names.
 
         Args:
             code_model: Model name for code embeddings
             semantic_model: Model name for semantic embeddings
+            rerank_model: Model name for the cross-encoder reranker
+            code_provider: Embedding provider for the code model
+            semantic_provider: Embedding provider for the semantic model
+            provider_options: Extra keyword arguments per purpose ("code" or
+                "semantic"), or per provider name for purposes without any
         """
         self._code_embedder: EmbeddingService | None = None
         self._semantic_embedder: EmbeddingService | None = None
+        self._reranker: Reranker | None = None
         self._code_model = code_model
         self._semantic_model = semantic_model
+        self._rerank_model = rerank_model
+        self._code_provider = code_provider
+        self._semantic_provider = semantic_provider
+        self._provider_options = provider_options or {}
 
     def get_code_embedder(self) -> EmbeddingService:
         """Get or create the code embedder.
@@ -45,19 +69,8 @@ class EmbeddingRegistry:
             EmbeddingService: Code embedder instance (MiniLM by default)
         """
         if self._code_embedder is None:
-            # Lazy import: PyTorch must not be loaded before fork()
-            from .minilm import MiniLMEmbedding
-
-            logger.info(
-                "code_embedder.loading",
-                model=self._code_model,
-                hint="first load may download the model (~90 MB)",
-            )
-            self._code_embedder = MiniLMEmbedding(model_name=self._code_model)
-            logger.info(
-                "code_embedder.ready",
-                model=self._code_model,
-                dimension=self._code_embedder.dimension,
+            self._code_embedder = self._create_embedder(
+                "code", self._code_provider, self._code_model
             )
         return self._code_embedder
 
@@ -68,28 +81,69 @@ class EmbeddingRegistry:
             EmbeddingService: Semantic embedder instance (Nomic by default)
         """
         if self._semantic_embedder is None:
+            self._semantic_embedder = self._create_embedder(
+                "semantic", self._semantic_provider, self._semantic_model
+            )
+        return self._semantic_embedder
+
+    def _create_embedder(
+        self, purpose: str, provider: str, model: str
+    ) -> EmbeddingService:
+        """Create an embedder with its configured provider and options."""
+        # Lazy import: PyTorch must not be loaded before fork()
+        from .providers import create_embedder
+
+        options = self._provider_options.get(purpose)
+        if options is None:
+            options = self._provider_options.get(provider)
+        logger.info(
+            f"{purpose}_embedder.loading",
+            provider=provider,
+            model=model,
+            hint="first load may download the model",
+        )
+        embedder = create_embedder(provider, model, options)
+        logger.info(
+            f"{purpose}_embedder.ready",
+            provider=provider,
+            model=model,
+            dimension=embedder.dimension,
+            max_tokens=embedder.max_tokens,
+        )
+        return embedder
+
+    def get_reranker(self) -> Reranker:
+        """Get or create the cross-encoder reranker.
+
+        Returns:
+            Reranker: Reranker instance (ms-marco MiniLM by default)
+        """
+        if self._reranker is None:
             # Lazy import: PyTorch must not be loaded before fork()
-            from .nomic import NomicEmbedding
+            from .cross_encoder import CrossEncoderReranker
 
             logger.info(
-                "semantic_embedder.loading",
-                model=self._semantic_model,
-                hint="first load may download the model (~275 MB)",
-            )
-            self._semantic_embedder = NomicEmbedding(model_name=self._semantic_model)
-            logger.info(
-                "semantic_embedder.ready",
-                model=self._semantic_model,
-                dimension=self._semantic_embedder.dimension,
+                "reranker.loading",
+                model=self._rerank_model,
+                hint="first load may download the model (~90 MB)",
             )
-        return self._semantic_embedder
+            self._reranker = CrossEncoderReranker(model_name=self._rerank_model)
+            logger.info("reranker.ready", model=self._rerank_model)
+        return self._reranker
 
 
 # Module-level singleton (initialized in main.py)
 _registry: EmbeddingRegistry | None = None
 
 
-def initialize_registry(code_model: str, semantic_model: str) -> None:
+def initialize_registry(
+    code_model: str,
+    semantic_model: str,
+    rerank_model: str = "cross-encoder/ms-marco-MiniLM-L-6-v2",
+    code_provider: str = "sentence-transformers",
+    semantic_provider: str = "nomic",
+    provider_options: dict[str, dict[str, Any]] | None = None,
+) -> None:
     """Initialize the global registry with model names.
 
     Must be called from main.py before any tool uses embedders.
@@ -98,9 +152,20 @@ def initialize_registry(code_model: str, semantic_model: str) -> None:
     Args:
         code_model: Model name for code embeddings
         semantic_model: Model name for semantic embeddings
+        rerank_model: Model name for the cross-encoder reranker
+        code_provider: Embedding provider for the code model
+        semantic_provider: Embedding provider for the semantic model
+        provider_options: Extra keyword arguments per purpose or provider name
     """
     global _registry
-    _registry = EmbeddingRegistry(code_model, semantic_model)
+    _registry = EmbeddingRegistry(
+        code_model,
+        semantic_model,
+        rerank_model,
+        code_provider,
+        semantic_provider,
+        provider_options,
+    )
 
 
 def get_code_embedder() -> EmbeddingService:
@@ -131,3 +196,18 @@ def get_semantic_embedder() -> EmbeddingService:
         msg = "Registry not initialized. Call initialize_registry() first."
         raise RuntimeError(msg)
     return _registry.get_semantic_embedder()
+
+
+def get_reranker() -> Reranker:
+    """Get the cross-encoder reranker from the global registry.
+
+    Returns:
+        Reranker: Reranker instance
+
+    Raises:
+        RuntimeError: If registry not initialized
+    """
+    if _registry is None:
+        msg = "Registry not initialized. Call initialize_registry() first."
+        raise RuntimeError(msg)
+    return _registry.get_reranker()
@@ -35,6 +35,112 @@ Context: {domain} - {strategy}
 Original hypothesis: {hypothesis}"""
 
 
+AXIS_TEMPLATES = {
+    "full": TEMPLATE_FULL,
+    "strategy": TEMPLATE_STRATEGY,
+    "surprise": TEMPLATE_SURPRISE,
+    "root_cause": TEMPLATE_ROOT_CAUSE,
+}
+
+
+def render_template(template: str, fields: dict[str, str]) -> str:
+    """Render a template with optional fields.
+
+    Templates contain:
+    - {field_name} for required fields
+    - [text with {field_name}] for optional fields
+
+    Optional sections are removed if the field is None or empty.
+
+    Limitations:
+    - Does not support nested brackets (e.g., [[inner]]).
+    - Nested brackets will fail gracefully by not matching the pattern.
+    - Current templates don't require nesting, so this is acceptable.
+
+    Args:
+        template: Template string with {field} and [optional {field}] syntax
+        fields: Field values by name
+
+    Returns:
+        Rendered text string
+
+    Raises:
+        ValueError: If a required field is missing
+    """
+    # Step 1: Extract optional sections (marked with [brackets])
+    # Pattern: [any text with {field_name} placeholders]
+    optional_pattern = re.compile(r"\[([^\[\]]+)\]")
+
+    def process_optional(match: re.Match[str]) -> str:
+        section = match.group(1)
+
+        # Extract field names from this section using {field_name} pattern
+        field_pattern = re.compile(r"\{(\w+)\}")
+        field_names = field_pattern.findall(section)
+
+        # Check if all fields exist and are non-None/non-empty
+        for field_name in field_names:
+            if field_name not in fields or not fields[field_name]:
+                # Field is missing or empty, remove entire section
+                return ""
+
+        # All fields present, render the section (without brackets)
+        return section
+
+    # Step 2: Process all optional sections
+    rendered = optional_pattern.sub(process_optional, template)
+
+    # Step 3: Render remaining template with all required fields
+    # Use str.format() to replace {field_name} placeholders
+    try:
+        return rendered.format(**fields)
+    except KeyError as e:
+        # Missing required field
+        raise ValueError(f"Template rendering failed: missing field {e}")
+
+
+def payload_fields(payload: dict[str, Any]) -> dict[str, str]:
+    """Rebuild template fields from a stored axis payload.
+
+    The inverse of ``_extract_fields`` + ``_build_axis_metadata``, used to
+    re-embed experiences without their original entries.
+    """
+    fields = {
+        name: str(payload[name])
+        for name in (
+            "goal",
+            "hypothesis",
+            "action",
+            "prediction",
+            "domain",
+            "strategy",
+            "iteration_count",
+            "outcome_status",
+            "outcome_result",
+        )
+        if payload.get(name) is not None
+    }
+
+    if payload.get("surprise"):
+        fields["surprise"] = payload["surprise"]
+    lesson = payload.get("lesson") or {}
+    if lesson.get("what_worked"):
+        fields["lesson_what_worked"] = lesson["what_worked"]
+    if lesson.get("takeaway"):
+        fields["lesson_takeaway"] = lesson["takeaway"]
+    root_cause = payload.get("root_cause")
+    if root_cause:
+        fields["root_cause_category"] = root_cause["category"]
+        fields["root_cause_description"] = root_cause["description"]
+
+    return fields
+
+
+def axis_embedding_text(axis: str, payload: dict[str, Any]) -> str:
+    """Render the embedding text of an axis from its stored payload."""
+    return render_template(AXIS_TEMPLATES[axis], payload_fields(payload))
+
+
 class ObservationPersister:
     """Persists resolved GHAP entries to vector store with multi-axis embeddings."""
 
@@ -166,18 +272,7 @@ class ObservationPersister:
                     raise
 
     def _render_template(self, template: str, entry: GHAPEntry) -> str:
-        """Render a template with optional fields.
-
-        Templates contain:
-        - {field_name} for required fields
-        - [text with {field_name}] for optional fields
-
-        Optional sections are removed if the field is None or empty.
-
-        Limitations:
-        - Does not support nested brackets (e.g., [[inner]]).
-        - Nested brackets will fail gracefully by not matching the pattern.
-        - Current templates don't require nesting, so this is acceptable.
+        """Render a template with the fields of an entry.
 
         Args:
             template: Template string with {field} and [optional {field}] syntax
@@ -189,39 +284,7 @@ class ObservationPersister:
         Raises:
             ValueError: If a required field is missing
         """
-        # Extract all fields for this entry
-        fields = self._extract_fields(entry)
-
-        # Step 1: Extract optional sections (marked with [brackets])
-        # Pattern: [any text with {field_name} placeholders]
-        optional_pattern = re.compile(r"\[([^\[\]]+)\]")
-
-        def process_optional(match: re.Match[str]) -> str:
-            section = match.group(1)
-
-            # Extract field names from this section using {field_name} pattern
-            field_pattern = re.compile(r"\{(\w+)\}")
-            field_names = field_pattern.findall(section)
-
-            # Check if all fields exist and are non-None/non-empty
-            for field_name in field_names:
-                if field_name not in fields or not fields[field_name]:
-                    # Field is missing or empty, remove entire section
-                    return ""
-
-            # All fields present, render the section (without brackets)
-            return section
-
-        # Step 2: Process all optional sections
-        rendered = optional_pattern.sub(process_optional, template)
-
-        # Step 3: Render remaining template with all required fields
-        # Use str.format() to replace {field_name} placeholders
-        try:
-            return rendered.format(**fields)
-        except KeyError as e:
-            # Missing required field
-            raise ValueError(f"Template rendering failed: missing field {e}")
+        return render_template(template, self._extract_fields(entry))
 
     def _extract_fields(self, entry: GHAPEntry) -> dict[str, str]:
         """Extract all fields from entry for template rendering.
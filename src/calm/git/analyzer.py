@@ -31,6 +31,17 @@ from .base import (
 logger = structlog.get_logger(__name__)
 
 
+def commit_embedding_text(
+    message: str, files_changed: list[str], author: str
+) -> str:
+    """Format a commit's message, files and author for embedding."""
+    files_str = ", ".join(files_changed)
+    if len(files_str) > 500:
+        files_str = files_str[:500] + "..."
+
+    return f"{message}\n\nFiles: {files_str}\n\nAuthor: {author}"
+
+
 class GitAnalyzer:
     """Analyzes and indexes git history for semantic search and metrics."""
 
@@ -237,11 +248,9 @@ class GitAnalyzer:
         )
 
     def _build_embedding_text(self, commit: Commit) -> str:
-        files_str = ", ".join(commit.files_changed)
-        if len(files_str) > 500:
-            files_str = files_str[:500] + "..."
-
-        return f"{commit.message}\n\nFiles: {files_str}\n\nAuthor: {commit.author}"
+        return commit_embedding_text(
+            commit.message, commit.files_changed, commit.author
+        )
 
     async def search_commits(
         self,
@@ -3,12 +3,15 @@
 from .base import (
     CodeParser,
     IndexingError,
+    IndexingProgress,
     IndexingStats,
     ParseError,
     SemanticUnit,
     UnitType,
 )
+from .documents import DocumentParser
 from .indexer import CodeIndexer
+from .languages import LanguageSpec
 from .tree_sitter import TreeSitterParser
 from .utils import EXTENSION_MAP, compute_file_hash, generate_unit_id
 
@@ -16,9 +19,12 @@ __all__ = [
     "CodeParser",
     "CodeIndexer",
     "TreeSitterParser",
+    "DocumentParser",
+    "LanguageSpec",
     "SemanticUnit",
     "UnitType",
     "IndexingError",
+    "IndexingProgress",
     "IndexingStats",
     "ParseError",
     "EXTENSION_MAP",
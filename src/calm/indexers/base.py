@@ -4,6 +4,8 @@ from abc import ABC, abstractmethod
 from dataclasses import dataclass, field
 from enum import Enum
 
+from .cargo import CargoWorkspace
+
 
 class UnitType(Enum):
     """Type of semantic unit extracted from code."""
@@ -13,6 +15,8 @@ class UnitType(Enum):
     METHOD = "method"
     MODULE = "module"
     CONSTANT = "constant"  # Module-level named constants
+    SECTION = "section"  # Markdown heading sections
+    CONFIG = "config"  # TOML tables and YAML top-level keys
 
 
 @dataclass
@@ -30,6 +34,9 @@ class SemanticUnit:
     language: str
     docstring: str | None = None
     complexity: int | None = None  # Cyclomatic complexity
+    attributes: list[str] = field(default_factory=list)  # e.g. ["derive(Debug)"]
+    visibility: str | None = None  # e.g. "pub", "pub(crate)", "private"
+    calls: list[str] = field(default_factory=list)  # Callee names as written
 
 
 @dataclass
@@ -48,8 +55,21 @@ class IndexingStats:
     files_indexed: int
     units_indexed: int
     files_skipped: int
+    files_removed: int = 0  # Previously indexed files no longer on disk
+    units_unchanged: int = 0  # Units of changed files reused without re-embedding
     errors: list[IndexingError] = field(default_factory=list)
     duration_ms: int = 0
+    crates: list[str] = field(default_factory=list)  # Cargo crates indexed
+    workspace: CargoWorkspace | None = None  # Cargo workspace at the root
+
+
+@dataclass
+class IndexingProgress:
+    """Progress of a directory indexing run."""
+
+    files_total: int
+    files_done: int = 0
+    units_indexed: int = 0
 
 
 class ParseError(Exception):
@@ -61,6 +81,10 @@ class ParseError(Exception):
         self.file_path = file_path
         super().__init__(f"{error_type}: {message}")
 
+    def __reduce__(self) -> tuple[type["ParseError"], tuple[str, str, str]]:
+        # Keep all fields when raised in a parser worker process
+        return (ParseError, (self.error_type, self.message, self.file_path))
+
 
 class CodeParser(ABC):
     """Abstract interface for parsing code into semantic units."""
@@ -0,0 +1,106 @@
+"""Best-effort resolution of call sites to indexed qualified names."""
+
+import re
+from collections.abc import Callable
+
+from calm.storage.metadata import CallSite, CodeSymbol
+
+# Separators used in qualified names and callee expressions
+_SEPARATOR_RE = re.compile(r"::|\.|->")
+
+# Trait impl paths, e.g. "<Point as Display>" (the type owns the method)
+_TRAIT_IMPL_RE = re.compile(r"<\s*([\w:]+)(?:<[^<>]*>)?\s+as\s+[^<>]*(?:<[^<>]*>)?[^<>]*>")
+
+# Generic arguments and turbofish, e.g. "Vec::<u8>::new" or "<T as Trait>"
+_GENERIC_RE = re.compile(r"<[^<>]*>")
+
+# Receivers that refer to the caller's own type
+_SELF_RECEIVERS = {"self", "this", "Self", "cls"}
+
+# Unit types a call can land on (classes cover constructor calls)
+_CALLABLE_TYPES = {"function", "method", "class"}
+
+
+def split_name(name: str) -> list[str]:
+    """Split a qualified name or callee expression into segments."""
+    name = _TRAIT_IMPL_RE.sub(r"\1", name)
+    previous = None
+    while previous != name:
+        previous, name = name, _GENERIC_RE.sub("", name)
+    return [s for s in _SEPARATOR_RE.split(name) if s]
+
+
+def resolve_calls(
+    symbols: list[CodeSymbol], call_sites: list[CallSite]
+) -> list[tuple[str, str, str, str]]:
+    """Resolve call sites against a project's symbols.
+
+    Matching is by the callee's final name segment, narrowed by (in order):
+    the written qualifier (``Point::new``, ``Calculator.add``), the caller's
+    own type for ``self``/``this`` receivers, and the caller's file. Calls
+    that remain ambiguous, or that match nothing (builtins, external
+    libraries), are dropped.
+
+    Returns:
+        (caller_qualified_name, callee_qualified_name, caller_file,
+        callee_file) tuples.
+    """
+    by_name: dict[str, list[CodeSymbol]] = {}
+    for symbol in symbols:
+        if symbol.unit_type in _CALLABLE_TYPES:
+            by_name.setdefault(symbol.name, []).append(symbol)
+
+    edges: list[tuple[str, str, str, str]] = []
+    seen: set[tuple[str, str]] = set()
+    for site in call_sites:
+        target = _resolve(site, by_name)
+        if target is None or target.qualified_name == site.caller_qualified_name:
+            continue
+        key = (site.caller_qualified_name, target.qualified_name)
+        if key in seen:
+            continue
+        seen.add(key)
+        edges.append(
+            (
+                site.caller_qualified_name,
+                target.qualified_name,
+                site.caller_file,
+                target.file_path,
+            )
+        )
+    return edges
+
+
+def _resolve(
+    site: CallSite, by_name: dict[str, list[CodeSymbol]]
+) -> CodeSymbol | None:
+    """Pick the single best symbol for a call site, if any."""
+    segments = split_name(site.callee_name)
+    if not segments:
+        return None
+    *qualifier, name = segments
+    candidates = by_name.get(name, [])
+    if not candidates:
+        return None
+
+    if qualifier and qualifier[-1] in _SELF_RECEIVERS:
+        caller_parent = split_name(site.caller_qualified_name)[:-1]
+        candidates = _narrow(
+            candidates, lambda s: split_name(s.qualified_name)[:-1] == caller_parent
+        )
+    elif qualifier:
+        owner = qualifier[-1]
+        candidates = _narrow(
+            candidates, lambda s: split_name(s.qualified_name)[-2:-1] == [owner]
+        )
+
+    candidates = _narrow(candidates, lambda s: s.file_path == site.caller_file)
+    return candidates[0] if len(candidates) == 1 else None
+
+
+def _narrow(
+    candidates: list[CodeSymbol], predicate: Callable[[CodeSymbol], bool]
+) -> list[CodeSymbol]:
+    """Filter candidates, keeping the original list if nothing matches."""
+    narrowed = [c for c in candidates if predicate(c)]
+    return narrowed or candidates
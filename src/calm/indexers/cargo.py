@@ -0,0 +1,244 @@
+"""Cargo manifest discovery and Rust module path resolution."""
+
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+
+import structlog
+
+logger = structlog.get_logger(__name__)
+
+MANIFEST_NAME = "Cargo.toml"
+
+# Files that form the root of their module (their path maps to the parent dir)
+_MODULE_ROOT_FILES = {"lib.rs", "main.rs", "mod.rs"}
+
+
+@dataclass
+class CargoCrate:
+    """A Cargo package located on disk."""
+
+    name: str  # Normalized crate name (dashes replaced with underscores)
+    version: str | None
+    root: Path  # Directory containing Cargo.toml
+
+
+def read_manifest(path: Path) -> dict[str, object] | None:
+    """Read and parse a Cargo.toml file.
+
+    Returns:
+        Parsed TOML data, or None if the file is unreadable or invalid.
+    """
+    try:
+        with open(path, "rb") as f:
+            return tomllib.load(f)
+    except (OSError, tomllib.TOMLDecodeError) as e:
+        logger.debug("cargo_manifest_unreadable", path=str(path), error=str(e))
+        return None
+
+
+def load_crate(manifest_path: Path) -> CargoCrate | None:
+    """Load package metadata from a Cargo.toml.
+
+    Returns:
+        CargoCrate, or None for virtual (workspace-only) manifests.
+    """
+    data = read_manifest(manifest_path)
+    if not data:
+        return None
+
+    package = data.get("package")
+    if not isinstance(package, dict) or not isinstance(package.get("name"), str):
+        return None
+
+    version = package.get("version")
+    if isinstance(version, dict) and version.get("workspace") is True:
+        # version.workspace = true inherits [workspace.package].version
+        version = _workspace_package_version(manifest_path.parent)
+
+    return CargoCrate(
+        name=package["name"].replace("-", "_"),
+        version=version if isinstance(version, str) else None,
+        root=manifest_path.parent,
+    )
+
+
+def _workspace_package_version(crate_root: Path) -> str | None:
+    """Find the inherited version from the enclosing workspace manifest."""
+    for directory in (crate_root, *crate_root.parents):
+        manifest = directory / MANIFEST_NAME
+        data = read_manifest(manifest) if manifest.is_file() else None
+        workspace = data.get("workspace") if data else None
+        if isinstance(workspace, dict):
+            package = workspace.get("package")
+            if isinstance(package, dict):
+                version = package.get("version")
+                return version if isinstance(version, str) else None
+            return None
+        if (directory / ".git").exists():
+            break
+    return None
+
+
+@dataclass
+class CargoWorkspace:
+    """A Cargo workspace (or a single package treated as one)."""
+
+    root: Path
+    members: list[CargoCrate] = field(default_factory=list)
+    default_members: list[CargoCrate] = field(default_factory=list)
+    excluded: list[Path] = field(default_factory=list)  # Resolved ``exclude``
+
+    def crate_for(self, file_path: str | Path) -> CargoCrate | None:
+        """Return the member crate containing a file (innermost match)."""
+        path = Path(file_path).expanduser().resolve()
+        best: CargoCrate | None = None
+        best_depth = -1
+        for crate in self.members:
+            crate_root = crate.root.resolve()
+            depth = len(crate_root.parts)
+            if path.is_relative_to(crate_root) and depth > best_depth:
+                best, best_depth = crate, depth
+        return best
+
+    def is_excluded(self, file_path: str | Path) -> bool:
+        """Whether a file is under one of the workspace's ``exclude`` paths."""
+        path = Path(file_path).expanduser().resolve()
+        return any(path.is_relative_to(excluded) for excluded in self.excluded)
+
+
+def load_workspace(root: str | Path) -> CargoWorkspace | None:
+    """Load the Cargo workspace rooted at a directory.
+
+    Expands ``members`` globs, drops ``exclude`` paths, and resolves
+    ``default-members``. A root manifest with only a ``[package]`` is treated
+    as a single-member workspace.
+
+    Returns:
+        CargoWorkspace, or None if the directory has no usable Cargo.toml.
+    """
+    root_path = Path(root).expanduser().resolve()
+    manifest = root_path / MANIFEST_NAME
+    data = read_manifest(manifest) if manifest.is_file() else None
+    if not data:
+        return None
+
+    root_crate = load_crate(manifest)
+    workspace = data.get("workspace")
+    if not isinstance(workspace, dict):
+        if root_crate is None:
+            return None
+        return CargoWorkspace(
+            root=root_path, members=[root_crate], default_members=[root_crate]
+        )
+
+    excluded = [root_path / p for p in _string_list(workspace.get("exclude"))]
+    members: list[CargoCrate] = [root_crate] if root_crate else []
+    for member_dir in _expand_member_globs(
+        root_path, _string_list(workspace.get("members")), excluded
+    ):
+        crate = load_crate(member_dir / MANIFEST_NAME)
+        if crate and all(m.root != crate.root for m in members):
+            members.append(crate)
+
+    if "default-members" in workspace:
+        default_dirs = set(
+            _expand_member_globs(
+                root_path, _string_list(workspace.get("default-members")), excluded
+            )
+        )
+        default_members = [m for m in members if m.root in default_dirs]
+    elif root_crate:
+        # Cargo defaults to the root package when there is one
+        default_members = [root_crate]
+    else:
+        default_members = list(members)
+
+    return CargoWorkspace(
+        root=root_path,
+        members=members,
+        default_members=default_members,
+        excluded=excluded,
+    )
+
+
+def _expand_member_globs(
+    root: Path, patterns: list[str], excluded: list[Path]
+) -> list[Path]:
+    """Expand workspace member globs to crate directories."""
+    directories: list[Path] = []
+    for pattern in patterns:
+        matches = sorted(root.glob(pattern)) if pattern not in (".", "") else [root]
+        for match in matches:
+            if not (match / MANIFEST_NAME).is_file():
+                continue
+            if any(match == ex or match.is_relative_to(ex) for ex in excluded):
+                continue
+            if match not in directories:
+                directories.append(match)
+    return directories
+
+
+def _string_list(value: object) -> list[str]:
+    """Coerce a manifest value to a list of strings."""
+    if not isinstance(value, list):
+        return []
+    return [v for v in value if isinstance(v, str)]
+
+
+def find_crate(file_path: str | Path) -> CargoCrate | None:
+    """Find the Cargo package that owns a source file.
+
+    Walks up from the file's directory to the nearest Cargo.toml that
+    declares a ``[package]``. The walk stops at a repository root
+    (a directory containing ``.git``) to avoid picking up unrelated
+    manifests above the project.
+    """
+    current = Path(file_path).expanduser().resolve().parent
+    for directory in (current, *current.parents):
+        manifest = directory / MANIFEST_NAME
+        if manifest.is_file():
+            crate = load_crate(manifest)
+            if crate:
+                return crate
+        if (directory / ".git").exists():
+            break
+    return None
+
+
+def rust_module_path(file_path: str | Path, crate: CargoCrate) -> str:
+    """Map a Rust source file to its module path within a crate.
+
+    Examples (crate ``my_crate``):
+        src/lib.rs          -> my_crate
+        src/main.rs         -> my_crate
+        src/foo/mod.rs      -> my_crate::foo
+        src/foo/bar.rs      -> my_crate::foo::bar
+        src/bin/tool.rs     -> tool (binary targets are their own crates)
+        tests/integration.rs -> my_crate::tests::integration
+    """
+    path = Path(file_path).expanduser().resolve()
+    try:
+        parts = list(path.relative_to(crate.root.resolve()).parts)
+    except ValueError:
+        return path.stem
+
+    if parts and parts[0] == "src":
+        parts = parts[1:]
+        if len(parts) >= 2 and parts[0] == "bin":
+            # src/bin/tool.rs or src/bin/tool/main.rs
+            binary = Path(parts[1]).stem
+            return "::".join([binary, *_module_segments(parts[2:])])
+        return "::".join([crate.name, *_module_segments(parts)])
+
+    return "::".join([crate.name, *_module_segments(parts)])
+
+
+def _module_segments(parts: list[str]) -> list[str]:
+    """Convert relative path parts into module path segments."""
+    if not parts:
+        return []
+    *dirs, filename = parts
+    if filename in _MODULE_ROOT_FILES:
+        return dirs
+    return [*dirs, Path(filename).stem]
@@ -0,0 +1,100 @@
+"""Splitting long units into overlapping chunks for embedding.
+
+Embedding models only see their first ``max_tokens`` tokens, so a unit
+whose text is longer is embedded as several chunks of whole lines, each
+prefixed with the unit's signature and docstring and overlapping the
+previous chunk by a few lines. Token counts are estimated at
+``CHARS_PER_TOKEN`` characters per token.
+
+Each chunk is stored as its own point. The first uses the unit's ID and
+payload; later chunks get IDs from :func:`chunk_id` and a payload whose
+``code`` holds only the chunk's lines. All of them carry ``parent_id``,
+``chunk_index``, ``chunk_count``, ``chunk_start_line`` and
+``chunk_end_line``; search collapses them back into one result per unit.
+"""
+
+from dataclasses import dataclass
+
+from .base import SemanticUnit
+from .utils import generate_unit_id, unit_embedding_text
+
+# Payload filter matching one point per unit (chunk 0, or an unchunked
+# point written before chunking)
+UNIT_FILTER = {"$not": {"chunk_index": {"$gt": 0}}}
+
+
+@dataclass
+class Chunk:
+    """A run of a unit's lines, embedded on its own."""
+
+    index: int
+    content: str  # The unit's lines in this chunk
+    text: str  # Text to embed (signature, docstring and content)
+    start_line: int
+    end_line: int
+
+
+def chunk_id(project: str, file_path: str, qualified_name: str, index: int) -> str:
+    """Generate the point ID of a unit's chunk (chunk 0 uses the unit's ID)."""
+    if index == 0:
+        return generate_unit_id(project, file_path, qualified_name)
+    return generate_unit_id(project, file_path, f"{qualified_name}#chunk{index}")
+
+
+def chunk_unit(unit: SemanticUnit, max_chars: int, overlap_chars: int) -> list[Chunk]:
+    """Split a unit into chunks whose text fits ``max_chars``.
+
+    Chunks break at line boundaries and repeat up to ``overlap_chars`` of
+    the previous chunk's last lines. A unit that fits is a single chunk.
+    """
+    header = sum(len(part) + 2 for part in (unit.signature, unit.docstring) if part)
+    # Leave at least half the budget for code, even with a long docstring
+    budget = max(max_chars - header, max_chars // 2, 1)
+
+    if len(unit.content) <= budget:
+        return [
+            Chunk(
+                index=0,
+                content=unit.content,
+                text=unit_embedding_text(
+                    unit.signature, unit.docstring, unit.content, max_chars
+                ),
+                start_line=unit.start_line,
+                end_line=unit.end_line,
+            )
+        ]
+
+    lines = unit.content.split("\n")
+    chunks: list[Chunk] = []
+    start = 0
+    while start < len(lines):
+        end, size = start, 0
+        while end < len(lines) and (end == start or size + len(lines[end]) <= budget):
+            size += len(lines[end]) + 1
+            end += 1
+
+        content = "\n".join(lines[start:end])[:budget]
+        chunks.append(
+            Chunk(
+                index=len(chunks),
+                content=content,
+                text=unit_embedding_text(
+                    unit.signature, unit.docstring, content, max_chars
+                ),
+                start_line=unit.start_line + start,
+                end_line=unit.start_line + end - 1,
+            )
+        )
+        if end >= len(lines):
+            break
+
+        # Step back over the overlap, always moving forward at least a line
+        next_start, overlap = end, 0
+        while next_start - 1 > start and (
+            overlap + len(lines[next_start - 1]) + 1 <= overlap_chars
+        ):
+            next_start -= 1
+            overlap += len(lines[next_start]) + 1
+        start = next_start
+
+    return chunks
@@ -0,0 +1,286 @@
+"""Parsing of project documents and config files into units.
+
+READMEs, design docs and config files are indexed alongside code so they
+show up in code search and context assembly. There is no grammar to load;
+each format is split by lines:
+
+- Markdown is split at its headings. Each heading starts a ``SECTION``
+  unit running to the next heading, qualified by the headings above it
+  (e.g. ``README.Installation.From source``). Text before the first
+  heading is a section named after the file.
+- TOML is split at its tables (``[dependencies]``, ``[[bin]]``) and the
+  keys before the first table, each a ``CONFIG`` unit (e.g.
+  ``Cargo.dependencies``).
+- YAML is split at its top-level keys, each a ``CONFIG`` unit (e.g.
+  ``ci.jobs``).
+
+A config file without tables or top-level keys is a single unit. Comment
+lines directly above a table or key become its docstring. Names repeated
+in a file (such as ``[[bin]]`` tables) get a ``(2)``, ``(3)``... suffix so
+every unit keeps its own ID.
+"""
+
+import asyncio
+import re
+from dataclasses import dataclass
+from pathlib import Path
+
+import structlog
+
+from .base import CodeParser, SemanticUnit, UnitType
+from .languages import DOCUMENT_EXTENSIONS
+from .utils import read_source
+
+logger = structlog.get_logger(__name__)
+
+DOCUMENT_LANGUAGES = frozenset(DOCUMENT_EXTENSIONS.values())
+
+# ATX heading, e.g. "## Installation ##"
+_HEADING_RE = re.compile(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$")
+
+# Opening or closing line of a fenced code block
+_FENCE_RE = re.compile(r"^ {0,3}(`{3,}|~{3,})")
+
+# Bare or quoted TOML key
+_TOML_KEY = r"""(?:[A-Za-z0-9_-]+|"[^"]*"|'[^']*')"""
+
+# TOML table header, e.g. [tool.ruff] or [[bin]]
+_TOML_TABLE_RE = re.compile(r"^\[\[?\s*([^\[\]]+?)\s*\]\]?\s*(?:#.*)?$")
+
+# TOML key/value pair before the first table, e.g. name = "calm"
+_TOML_KEY_RE = re.compile(rf"^({_TOML_KEY}(?:\s*\.\s*{_TOML_KEY})*)\s*=")
+
+# Key of the top-level YAML mapping, e.g. jobs:
+_YAML_KEY_RE = re.compile(
+    r"""^("[^"]*"|'[^']*'|[^\s#\-?:,\[\]{}&*!|>'"%@`][^#]*?)\s*:(?:\s|$)"""
+)
+
+# Start or end of a YAML document
+_YAML_MARKER_RE = re.compile(r"^(?:---|\.\.\.)(?:\s|$)")
+
+
+@dataclass
+class _Block:
+    """A run of a file's lines that becomes one unit."""
+
+    name: str
+    path: tuple[str, ...]  # Qualified name parts after the file stem
+    start: int  # Index of the first line
+    end: int  # Index after the last line
+    doc: str | None = None
+
+
+class DocumentParser(CodeParser):
+    """Parser splitting Markdown, TOML and YAML files into units."""
+
+    async def parse_file(self, path: str) -> list[SemanticUnit]:
+        """Parse a document and split it into units.
+
+        Raises:
+            ParseError: If the file can't be read as UTF-8 text
+        """
+        loop = asyncio.get_event_loop()
+        return await loop.run_in_executor(None, self._parse_file_sync, path)
+
+    def supported_languages(self) -> list[str]:
+        """Return the document formats this parser splits."""
+        return sorted(DOCUMENT_LANGUAGES)
+
+    def detect_language(self, path: str) -> str | None:
+        """Detect the document format from the file extension."""
+        return DOCUMENT_EXTENSIONS.get(Path(path).suffix.lower())
+
+    def _parse_file_sync(self, path: str) -> list[SemanticUnit]:
+        """Synchronous document parsing (runs in executor)."""
+        language = self.detect_language(path)
+        if not language:
+            logger.debug("unsupported_language", path=path)
+            return []
+
+        source = read_source(path)
+        if source is None:
+            return []
+        return self.extract_units(source, path, language)
+
+    def extract_units(
+        self, source: str, file_path: str, language: str
+    ) -> list[SemanticUnit]:
+        """Split a document's text into units.
+
+        Args:
+            source: Document text
+            file_path: Path stored on the units
+            language: "markdown", "toml" or "yaml"
+        """
+        lines = source.split("\n")
+        stem = Path(file_path).stem
+
+        if language == "markdown":
+            unit_type = UnitType.SECTION
+            blocks = self._markdown_blocks(lines, stem)
+        else:
+            unit_type = UnitType.CONFIG
+            if language == "toml":
+                blocks = self._config_blocks(lines, self._toml_keys(lines), [])
+            else:
+                markers = [
+                    i for i, line in enumerate(lines) if _YAML_MARKER_RE.match(line)
+                ]
+                blocks = self._config_blocks(lines, self._yaml_keys(lines), markers)
+            if not blocks:
+                blocks = self._whole_file_block(lines, stem)
+
+        units: list[SemanticUnit] = []
+        seen: dict[str, int] = {}
+        for block in blocks:
+            qualified_name = ".".join((stem, *block.path))
+            seen[qualified_name] = seen.get(qualified_name, 0) + 1
+            if seen[qualified_name] > 1:
+                qualified_name = f"{qualified_name} ({seen[qualified_name]})"
+
+            units.append(
+                SemanticUnit(
+                    name=block.name,
+                    qualified_name=qualified_name,
+                    unit_type=unit_type,
+                    signature=lines[block.start].strip(),
+                    content="\n".join(lines[block.start : block.end]),
+                    file_path=file_path,
+                    start_line=block.start + 1,
+                    end_line=block.end,
+                    language=language,
+                    docstring=block.doc,
+                    complexity=None,
+                )
+            )
+        return units
+
+    def _markdown_blocks(self, lines: list[str], stem: str) -> list[_Block]:
+        """Split Markdown into the sections under each heading.
+
+        Headings inside fenced code blocks and YAML front matter don't
+        count, and headings with nothing under them before the next
+        heading are skipped.
+        """
+        headings: list[tuple[int, int, str]] = []  # (line, level, title)
+        fence: str | None = None
+        for i in range(self._front_matter_end(lines), len(lines)):
+            fence_match = _FENCE_RE.match(lines[i])
+            if fence:
+                marker = fence_match.group(1) if fence_match else ""
+                if marker.startswith(fence[0]) and len(marker) >= len(fence):
+                    fence = None
+            elif fence_match:
+                fence = fence_match.group(1)
+            elif heading := _HEADING_RE.match(lines[i]):
+                if heading.group(2):
+                    headings.append((i, len(heading.group(1)), heading.group(2)))
+
+        first_heading = headings[0][0] if headings else len(lines)
+        blocks = self._whole_file_block(lines[:first_heading], stem)
+
+        parents: list[tuple[int, str]] = []  # (level, title) of enclosing headings
+        for n, (start, level, title) in enumerate(headings):
+            while parents and parents[-1][0] >= level:
+                parents.pop()
+            path = (*(t for _, t in parents), title)
+            parents.append((level, title))
+
+            end = headings[n + 1][0] if n + 1 < len(headings) else len(lines)
+            end = self._trim(lines, start, end)
+            if end > start + 1:
+                blocks.append(_Block(title, path, start, end))
+
+        return blocks
+
+    def _front_matter_end(self, lines: list[str]) -> int:
+        """Get the index of the first line after Markdown front matter."""
+        if lines and lines[0].rstrip() == "---":
+            for i in range(1, len(lines)):
+                if lines[i].rstrip() in ("---", "..."):
+                    return i + 1
+        return 0
+
+    def _toml_keys(self, lines: list[str]) -> list[tuple[int, str]]:
+        """Find the (line, name) of each TOML table and of the keys before them.
+
+        Lines inside multi-line strings are skipped.
+        """
+        keys: list[tuple[int, str]] = []
+        in_table = False
+        open_string: str | None = None
+        for i, line in enumerate(lines):
+            if open_string:
+                if line.count(open_string) % 2 == 1:
+                    open_string = None
+                continue
+
+            if table := _TOML_TABLE_RE.match(line):
+                keys.append((i, re.sub(r"\s*\.\s*", ".", table.group(1))))
+                in_table = True
+            elif not in_table and (key := _TOML_KEY_RE.match(line)):
+                keys.append((i, re.sub(r"\s*\.\s*", ".", key.group(1))))
+
+            for delimiter in ('"""', "'''"):
+                if line.count(delimiter) % 2 == 1:
+                    open_string = delimiter
+        return keys
+
+    def _yaml_keys(self, lines: list[str]) -> list[tuple[int, str]]:
+        """Find the (line, name) of each key of the top-level YAML mapping."""
+        keys: list[tuple[int, str]] = []
+        for i, line in enumerate(lines):
+            if key := _YAML_KEY_RE.match(line):
+                keys.append((i, key.group(1).strip("\"'")))
+        return keys
+
+    def _config_blocks(
+        self, lines: list[str], keys: list[tuple[int, str]], boundaries: list[int]
+    ) -> list[_Block]:
+        """Split a config file into a block per table or key.
+
+        Each block runs until the comments above the next key, or an
+        earlier boundary line (such as a YAML document marker).
+        """
+        doc_starts = [self._comment_start(lines, start) for start, _ in keys]
+        blocks: list[_Block] = []
+        for n, (start, name) in enumerate(keys):
+            end = doc_starts[n + 1] if n + 1 < len(keys) else len(lines)
+            end = min([b for b in boundaries if start < b < end] or [end])
+            doc = "\n".join(
+                self._clean_comment(line) for line in lines[doc_starts[n] : start]
+            ).strip()
+            blocks.append(
+                _Block(
+                    name=name,
+                    path=(name,),
+                    start=start,
+                    end=self._trim(lines, start, end),
+                    doc=doc if doc else None,
+                )
+            )
+        return blocks
+
+    def _whole_file_block(self, lines: list[str], stem: str) -> list[_Block]:
+        """Make one block, named after the file, of all its non-blank lines."""
+        start = next((i for i, line in enumerate(lines) if line.strip()), None)
+        if start is None:
+            return []
+        return [_Block(stem, (), start, self._trim(lines, start, len(lines)))]
+
+    def _comment_start(self, lines: list[str], start: int) -> int:
+        """Get the first of the ``#`` comment lines directly above a line."""
+        while start > 0 and lines[start - 1].lstrip().startswith("#"):
+            start -= 1
+        return start
+
+    def _clean_comment(self, line: str) -> str:
+        """Strip the ``#`` marker and one following space from a comment."""
+        text = line.strip().lstrip("#")
+        return text[1:] if text.startswith(" ") else text
+
+    def _trim(self, lines: list[str], start: int, end: int) -> int:
+        """Move ``end`` back over blank lines, keeping at least ``start``."""
+        while end > start + 1 and not lines[end - 1].strip():
+            end -= 1
+        return end
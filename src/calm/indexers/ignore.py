@@ -0,0 +1,311 @@
+"""Gitignore-style exclusion rules for the code indexer.
+
+Implements the gitignore pattern semantics the indexer needs: comments and
+escapes, negation (``!``), anchored patterns (a leading or inner ``/``),
+directory-only rules (trailing ``/``), ``*``/``?``/``[...]`` globs and
+``**``. Rules are read from, in increasing precedence:
+
+1. the global git excludes file (``core.excludesFile``, or
+   ``$XDG_CONFIG_HOME/git/ignore``)
+2. ``.git/info/exclude`` of the enclosing repository
+3. ``.gitignore`` files, outer directories first
+4. ``.calmignore`` files (same syntax, applied after ``.gitignore`` in the
+   same directory)
+
+Patterns stored in the project's settings are applied last and win over
+everything read from disk.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import structlog
+
+logger = structlog.get_logger(__name__)
+
+GITIGNORE_NAME = ".gitignore"
+CALMIGNORE_NAME = ".calmignore"
+
+# core.excludesFile in a git config file
+_EXCLUDES_FILE_RE = re.compile(r"^\s*excludesfile\s*=\s*(.+?)\s*$", re.IGNORECASE)
+
+
+@dataclass
+class ExclusionSettings:
+    """Per-project exclusion settings, persisted in ``ProjectConfig.settings``."""
+
+    respect_gitignore: bool = True
+    respect_calmignore: bool = True
+    patterns: list[str] = field(default_factory=list)  # gitignore syntax
+
+    SETTINGS_KEY = "exclusions"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "respect_gitignore": self.respect_gitignore,
+            "respect_calmignore": self.respect_calmignore,
+            "patterns": list(self.patterns),
+        }
+
+    @classmethod
+    def from_settings(cls, settings: dict[str, Any] | None) -> "ExclusionSettings":
+        """Read exclusion settings from a project's settings dict."""
+        data = (settings or {}).get(cls.SETTINGS_KEY)
+        if not isinstance(data, dict):
+            return cls()
+        patterns = data.get("patterns", [])
+        return cls(
+            respect_gitignore=bool(data.get("respect_gitignore", True)),
+            respect_calmignore=bool(data.get("respect_calmignore", True)),
+            patterns=[p for p in patterns if isinstance(p, str)]
+            if isinstance(patterns, list)
+            else [],
+        )
+
+
+@dataclass
+class IgnoreRule:
+    """A single compiled gitignore pattern."""
+
+    pattern: str
+    base: Path  # Directory the pattern is relative to
+    regex: re.Pattern[str]
+    negate: bool = False
+    dir_only: bool = False
+
+    def matches(self, path: Path, is_dir: bool) -> bool:
+        if self.dir_only and not is_dir:
+            return False
+        try:
+            relative = path.relative_to(self.base).as_posix()
+        except ValueError:
+            return False
+        return relative != "." and self.regex.fullmatch(relative) is not None
+
+
+def parse_ignore_pattern(line: str, base: Path) -> IgnoreRule | None:
+    """Compile one line of an ignore file, or None for blanks and comments."""
+    line = line.rstrip("\n")
+    # Trailing spaces are ignored unless escaped
+    stripped = line.rstrip(" ")
+    if stripped.endswith("\\") and len(stripped) < len(line):
+        stripped += " "
+    line = stripped
+    if not line or line.startswith("#"):
+        return None
+
+    negate = False
+    if line.startswith("!"):
+        negate = True
+        line = line[1:]
+    elif line.startswith("\\!") or line.startswith("\\#"):
+        line = line[1:]
+
+    dir_only = line.endswith("/")
+    line = line.rstrip("/")
+    if not line:
+        return None
+
+    # A slash at the start or middle anchors the pattern to its base
+    anchored = "/" in line
+    line = line.lstrip("/")
+
+    body = _glob_to_regex(line)
+    regex = body if anchored else f"(?:.*/)?{body}"
+    return IgnoreRule(
+        pattern=line,
+        base=base,
+        regex=re.compile(regex),
+        negate=negate,
+        dir_only=dir_only,
+    )
+
+
+def _glob_to_regex(pattern: str) -> str:
+    """Translate a gitignore glob (without leading/trailing slash) to a regex."""
+    out: list[str] = []
+    i, n = 0, len(pattern)
+    while i < n:
+        if pattern.startswith("**/", i) and (i == 0 or pattern[i - 1] == "/"):
+            out.append("(?:.*/)?")
+            i += 3
+        elif pattern.startswith("**", i) and i + 2 == n and (
+            i == 0 or pattern[i - 1] == "/"
+        ):
+            out.append(".*")
+            i += 2
+        elif pattern[i] == "*":
+            out.append("[^/]*")
+            i += 1
+        elif pattern[i] == "?":
+            out.append("[^/]")
+            i += 1
+        elif pattern[i] == "[":
+            # A leading "!" (negation) or "]" is part of the class
+            j = i + 1
+            if j < n and pattern[j] == "!":
+                j += 1
+            if j < n and pattern[j] == "]":
+                j += 1
+            end = pattern.find("]", j)
+            if end == -1:
+                out.append(re.escape("["))
+                i += 1
+                continue
+            chars = pattern[i + 1 : end]
+            if chars.startswith("!"):
+                chars = "^" + chars[1:]
+            out.append(f"[{chars}]")
+            i = end + 1
+        elif pattern[i] == "\\" and i + 1 < n:
+            out.append(re.escape(pattern[i + 1]))
+            i += 2
+        else:
+            out.append(re.escape(pattern[i]))
+            i += 1
+    return "".join(out)
+
+
+def read_ignore_file(path: Path, base: Path | None = None) -> list[IgnoreRule]:
+    """Read rules from an ignore file (missing or unreadable files give none)."""
+    try:
+        lines = path.read_text(encoding="utf-8", errors="replace").splitlines()
+    except OSError:
+        return []
+    rule_base = base if base is not None else path.parent
+    rules = [parse_ignore_pattern(line, rule_base) for line in lines]
+    return [r for r in rules if r is not None]
+
+
+class IgnoreMatcher:
+    """Ordered gitignore rules where the last matching rule wins."""
+
+    def __init__(
+        self,
+        rules: list[IgnoreRule] | None = None,
+        overrides: list[IgnoreRule] | None = None,
+    ) -> None:
+        self.rules: list[IgnoreRule] = list(rules or [])
+        # Project-level patterns, checked after (and winning over) file rules
+        self.overrides: list[IgnoreRule] = list(overrides or [])
+        self._loaded_dirs: set[Path] = set()
+        self._ignore_files: list[str] = []
+
+    def load_directory(self, directory: Path) -> None:
+        """Add the ignore files found directly in a directory (once)."""
+        if directory in self._loaded_dirs:
+            return
+        self._loaded_dirs.add(directory)
+        for name in self._ignore_files:
+            self.rules.extend(read_ignore_file(directory / name))
+
+    def is_ignored(self, path: Path, is_dir: bool = False) -> bool:
+        result = _last_match(self.overrides, path, is_dir)
+        if result is None:
+            result = _last_match(self.rules, path, is_dir)
+        return bool(result)
+
+    def is_path_ignored(self, root: Path, path: Path) -> bool:
+        """Check a file and every directory between root and it.
+
+        Loads ignore files along the way, so this works without a prior walk
+        (e.g. for single files reported by the watcher).
+        """
+        try:
+            parts = path.relative_to(root).parts
+        except ValueError:
+            return False
+        current = root
+        self.load_directory(current)
+        for part in parts[:-1]:
+            current = current / part
+            if self.is_ignored(current, is_dir=True):
+                return True
+            self.load_directory(current)
+        return self.is_ignored(path, is_dir=False)
+
+    @classmethod
+    def for_root(
+        cls, root: str | Path, exclusions: ExclusionSettings | None = None
+    ) -> "IgnoreMatcher":
+        """Build a matcher for indexing a directory tree.
+
+        Loads global excludes, ``.git/info/exclude`` and the ``.gitignore``
+        files of directories between the repository root and ``root``.
+        Ignore files inside ``root`` are loaded lazily via
+        :meth:`load_directory` as the tree is walked.
+        """
+        exclusions = exclusions or ExclusionSettings()
+        root_path = Path(root).expanduser().absolute()
+        overrides = [parse_ignore_pattern(p, root_path) for p in exclusions.patterns]
+        matcher = cls(overrides=[r for r in overrides if r is not None])
+
+        if exclusions.respect_gitignore:
+            matcher._ignore_files.append(GITIGNORE_NAME)
+            repo_root = find_repo_root(root_path)
+            excludes_file = global_excludes_file()
+            if excludes_file:
+                base = repo_root or root_path
+                matcher.rules.extend(read_ignore_file(excludes_file, base))
+            if repo_root:
+                matcher.rules.extend(
+                    read_ignore_file(repo_root / ".git" / "info" / "exclude", repo_root)
+                )
+                # .gitignore files above the indexed directory still apply
+                current = repo_root
+                for part in (None, *root_path.relative_to(repo_root).parts):
+                    if part is not None:
+                        current = current / part
+                    if current == root_path:
+                        break
+                    matcher.rules.extend(read_ignore_file(current / GITIGNORE_NAME))
+        if exclusions.respect_calmignore:
+            matcher._ignore_files.append(CALMIGNORE_NAME)
+
+        return matcher
+
+
+def _last_match(rules: list[IgnoreRule], path: Path, is_dir: bool) -> bool | None:
+    """Return True/False for the last matching rule (ignored/negated), or None."""
+    for rule in reversed(rules):
+        if rule.matches(path, is_dir):
+            return not rule.negate
+    return None
+
+
+def find_repo_root(path: Path) -> Path | None:
+    """Find the enclosing git repository root (directory containing ``.git``)."""
+    for directory in (path, *path.parents):
+        if (directory / ".git").exists():
+            return directory
+    return None
+
+
+def global_excludes_file() -> Path | None:
+    """Locate the global git excludes file.
+
+    Honors ``core.excludesFile`` in the user's git config, falling back to
+    git's default of ``$XDG_CONFIG_HOME/git/ignore``.
+    """
+    xdg_config = Path(os.environ.get("XDG_CONFIG_HOME") or Path.home() / ".config")
+    for config in (Path.home() / ".gitconfig", xdg_config / "git" / "config"):
+        try:
+            lines = config.read_text(encoding="utf-8", errors="replace").splitlines()
+        except OSError:
+            continue
+        section = ""
+        for line in lines:
+            stripped = line.strip()
+            if stripped.startswith("["):
+                section = stripped.strip("[]").strip().lower()
+                continue
+            match = _EXCLUDES_FILE_RE.match(stripped)
+            if section == "core" and match:
+                value = match.group(1).strip('"')
+                return Path(value).expanduser()
+
+    default = xdg_config / "git" / "ignore"
+    return default if default.is_file() else None
@@ -1,7 +1,13 @@
 """Code indexer implementation for semantic code search."""
 
+import asyncio
+import multiprocessing
 import os
+import re
 import time
+from collections import Counter
+from collections.abc import AsyncIterator, Callable
+from concurrent.futures import ProcessPoolExecutor
 from datetime import datetime
 from fnmatch import fnmatch
 from pathlib import Path
@@ -12,14 +18,63 @@ import structlog
 from calm.config import settings
 from calm.embedding.base import EmbeddingModelError, EmbeddingService
 from calm.search.collections import CollectionName
-from calm.storage.base import Vector, VectorStore
-from calm.storage.metadata import MetadataStore
-
-from .base import CodeParser, IndexingError, IndexingStats, ParseError, SemanticUnit
-from .utils import EXTENSION_MAP, compute_file_hash, generate_unit_id
+from calm.storage.base import SearchResult, Vector, VectorStore
+from calm.storage.metadata import CallSite, CodeSymbol, MetadataStore
+
+from .base import (
+    CodeParser,
+    IndexingError,
+    IndexingProgress,
+    IndexingStats,
+    ParseError,
+    SemanticUnit,
+)
+from .calls import resolve_calls
+from .cargo import CargoCrate, CargoWorkspace, find_crate, load_workspace
+from .chunking import UNIT_FILTER, Chunk, chunk_id, chunk_unit
+from .ignore import ExclusionSettings, IgnoreMatcher
+from .tree_sitter import TreeSitterParser, parse_file_in_worker
+from .utils import (
+    CHARS_PER_TOKEN,
+    EXTENSION_MAP,
+    compute_file_hash,
+    compute_unit_hash,
+    max_embedding_chars,
+)
 
 logger = structlog.get_logger(__name__)
 
+# Below this many files, parsing in-process beats process pool startup
+_PARALLEL_PARSE_MIN_FILES = 32
+
+ProgressCallback = Callable[[IndexingProgress], None]
+
+# Leading path of an attribute, e.g. "derive" in "derive(Debug)"
+_ATTRIBUTE_NAME_RE = re.compile(r"^[\w:]+")
+
+
+def _attribute_name(attribute: str) -> str:
+    """Return the name of an attribute such as ``cfg(test)`` -> ``cfg``."""
+    match = _ATTRIBUTE_NAME_RE.match(attribute)
+    return match.group(0) if match else attribute
+
+
+def _unit_keys(units: list[SemanticUnit]) -> dict[int, str]:
+    """Name each unit's point IDs are derived from, by ``id(unit)``.
+
+    Overloads share a qualified name, so the second and later units with a
+    name in a file get ``#2``, ``#3``, ... and keep points of their own.
+    """
+    seen: Counter[str] = Counter()
+    keys: dict[int, str] = {}
+    for unit in units:
+        seen[unit.qualified_name] += 1
+        count = seen[unit.qualified_name]
+        keys[id(unit)] = (
+            unit.qualified_name if count == 1 else f"{unit.qualified_name}#{count}"
+        )
+    return keys
+
 
 class CodeIndexer:
     """Index parsed code units for semantic search."""
@@ -48,11 +103,30 @@ class CodeIndexer:
         ".worktrees",
     }
 
+    @staticmethod
+    def normalize_path(path: str | Path) -> str:
+        """Absolute form of a path as the index records it.
+
+        Symlinks are kept, so files of a project indexed through a symlinked
+        root are recorded (and watched) under that root.
+        """
+        return str(Path(path).expanduser().absolute())
+
     @property
     def embedding_batch_size(self) -> int:
         """Get embedding batch size from configuration."""
         return settings.indexer.embedding_batch_size
 
+    @property
+    def max_payload_code_chars(self) -> int:
+        """Get the payload source size bound from configuration."""
+        return settings.indexer.max_payload_code_chars
+
+    @property
+    def chunk_overlap_tokens(self) -> int:
+        """Get the overlap between chunks of long units from configuration."""
+        return settings.indexer.chunk_overlap_tokens
+
     def __init__(
         self,
         parser: CodeParser,
@@ -65,27 +139,43 @@ class CodeIndexer:
         self.vector_store = vector_store
         self.metadata_store = metadata_store
         self._collection_ensured = False
+        # Cargo workspace of the directory being indexed, if any
+        self._workspace: CargoWorkspace | None = None
+        # Nearest-manifest crate lookups, keyed by directory
+        self._crate_cache: dict[str, CargoCrate | None] = {}
 
     async def _ensure_collection(self) -> None:
-        """Create collection if it doesn't exist, recreate if dimension mismatches."""
+        """Create collection if it doesn't exist, re-embed if dimension mismatches."""
         if self._collection_ensured:
             return
 
         try:
-            try:
-                info = await self.vector_store.get_collection_info(self.COLLECTION_NAME)
-                if info and info.dimension != self.embedding_service.dimension:
-                    logger.warning(
-                        "dimension_mismatch",
-                        collection=self.COLLECTION_NAME,
-                        expected=self.embedding_service.dimension,
-                        actual=info.dimension,
-                        action="recreating_collection",
-                    )
-                    await self.vector_store.delete_collection(self.COLLECTION_NAME)
-            except Exception:
-                pass
+            info = await self.vector_store.get_collection_info(self.COLLECTION_NAME)
+        except Exception:
+            info = None
+        if info and info.dimension != self.embedding_service.dimension:
+            logger.warning(
+                "dimension_mismatch",
+                collection=self.COLLECTION_NAME,
+                expected=self.embedding_service.dimension,
+                actual=info.dimension,
+                action="reembedding_collection",
+            )
+            # Lazy import: the migration module imports this package.
+            # Waits for (and then skips after) a re-embedding already running.
+            from calm.embedding.migration import reembed_collection
+
+            await reembed_collection(
+                self.vector_store,
+                self.embedding_service,
+                self.COLLECTION_NAME,
+                settings.code_model,
+                self.metadata_store,
+                self.embedding_batch_size,
+                only_if_stale=True,
+            )
 
+        try:
             await self.vector_store.create_collection(
                 name=self.COLLECTION_NAME,
                 dimension=self.embedding_service.dimension,
@@ -104,8 +194,18 @@ class CodeIndexer:
 
         self._collection_ensured = True
 
-    async def index_file(self, path: str, project: str) -> IndexingStats:
-        """Index a single file."""
+    async def index_file(
+        self, path: str, project: str, update_calls: bool = True
+    ) -> IndexingStats:
+        """Index a single file.
+
+        Args:
+            path: File to index
+            project: Project identifier
+            update_calls: Re-resolve the project's call graph afterwards;
+                callers indexing several files can pass False and call
+                ``update_call_graph`` once at the end
+        """
         stats = IndexingStats(files_indexed=0, units_indexed=0, files_skipped=0)
 
         await self._ensure_collection()
@@ -126,58 +226,158 @@ class CodeIndexer:
             stats.files_skipped = 1
             return stats
 
-        await self._delete_file_units(path, project)
-
-        successful_units, embeddings = await self._embed_units(units, stats.errors)
+        await self._index_parsed_files([(path, units)], project, stats)
+        if update_calls:
+            await self.update_call_graph(project)
+        return stats
 
-        if not successful_units:
-            return stats
+    async def _index_parsed_files(
+        self,
+        parsed: list[tuple[str, list[SemanticUnit]]],
+        project: str,
+        stats: IndexingStats,
+    ) -> None:
+        """Embed and store the units of several parsed files together.
+
+        Units whose content hash matches their stored version keep their
+        vectors but have their payload rewritten, since fields outside the
+        hash (line numbers, crate, module path, ``indexed_at``) may have
+        changed; the rest share embedding batches. Both go into a single
+        bulk upsert. Stored units that were removed or changed are deleted.
+        Per-file metadata is recorded for files with at least one stored
+        unit.
+        """
+        owners = {id(unit): path for path, units in parsed for unit in units}
+        keys: dict[int, str] = {}
+        for _, units in parsed:
+            keys.update(_unit_keys(units))
+        changed: list[SemanticUnit] = []
+        reused: list[tuple[SemanticUnit, list[Chunk], list[Vector]]] = []
+        unchanged: set[int] = set()
+        stale: set[str] = set()
+        for path, units in parsed:
+            stored = await self._stored_units(path, project)
+            for unit in units:
+                unit_id = chunk_id(project, path, keys[id(unit)], 0)
+                points = stored.pop(unit_id, [])
+                chunks = self._chunk_unit(unit)
+                if not self._is_unchanged(unit, chunks, points):
+                    changed.append(unit)
+                    stale.update(point.id for point in points)
+                    continue
+                unchanged.add(id(unit))
+                vectors = [p.vector for p in points if p.vector is not None]
+                reused.append((unit, chunks, vectors))
+            stale.update(point.id for points in stored.values() for point in points)
+
+        embedded = await self._embed_units(changed, stats.errors)
+
+        ids: list[str] = []
+        vectors: list[Vector] = []
+        payloads: list[dict[str, Any]] = []
+        for unit, chunks, chunk_vectors in [*embedded, *reused]:
+            path = owners[id(unit)]
+            payload = self._build_payload(unit, project, self._crate_for(path))
+            parent_id = chunk_id(project, path, keys[id(unit)], 0)
+            for chunk, vector in zip(chunks, chunk_vectors):
+                ids.append(chunk_id(project, path, keys[id(unit)], chunk.index))
+                vectors.append(vector)
+                payloads.append(
+                    self._chunk_payload(payload, parent_id, chunk, len(chunks))
+                )
 
-        unit_ids = [
-            generate_unit_id(project, path, u.qualified_name)
-            for u in successful_units
-        ]
-        for unit, unit_id, embedding in zip(successful_units, unit_ids, embeddings):
-            payload = self._build_payload(unit, project)
-            await self.vector_store.upsert(
+        if ids:
+            await self.vector_store.upsert_batch(
                 collection=self.COLLECTION_NAME,
-                id=unit_id,
-                vector=embedding,
-                payload=payload,
+                ids=ids,
+                vectors=vectors,
+                payloads=payloads,
             )
-
-        file_hash = compute_file_hash(path)
-        mtime = Path(path).stat().st_mtime
-        language = self.parser.detect_language(path)
-        await self.metadata_store.add_indexed_file(
-            file_path=path,
-            project=project,
-            language=language or "unknown",
-            file_hash=file_hash,
-            unit_count=len(successful_units),
-            last_modified=datetime.fromtimestamp(mtime),
+        for point_id in stale - set(ids):
+            await self.vector_store.delete(collection=self.COLLECTION_NAME, id=point_id)
+
+        stored_units = unchanged | {id(unit) for unit, _, _ in embedded}
+        for path, units in parsed:
+            kept = [u for u in units if id(u) in stored_units]
+            if not kept:
+                continue
+            file_hash = compute_file_hash(path)
+            mtime = Path(path).stat().st_mtime
+            # The parser's language, which for a .h header depends on its content
+            language = kept[0].language or self.parser.detect_language(path)
+            await self.metadata_store.add_indexed_file(
+                file_path=path,
+                project=project,
+                language=language or "unknown",
+                file_hash=file_hash,
+                unit_count=len(kept),
+                last_modified=datetime.fromtimestamp(mtime),
+            )
+            await self._store_symbols(path, project, kept)
+            stats.files_indexed += 1
+            stats.units_indexed += len(kept)
+            stats.units_unchanged += sum(id(u) in unchanged for u in kept)
+
+    async def _stored_units(
+        self, path: str, project: str
+    ) -> dict[str, list[SearchResult]]:
+        """Get a file's stored points with vectors, by unit ID in chunk order."""
+        filters = {"file_path": path, "project": project}
+        count = await self.vector_store.count(self.COLLECTION_NAME, filters=filters)
+        if not count:
+            return {}
+        results = await self.vector_store.scroll(
+            collection=self.COLLECTION_NAME,
+            limit=count,
+            filters=filters,
+            with_vectors=True,
         )
 
-        stats.files_indexed = 1
-        stats.units_indexed = len(successful_units)
-        return stats
+        results.sort(key=lambda r: r.payload.get("chunk_index", 0))
+        units: dict[str, list[SearchResult]] = {}
+        for result in results:
+            parent_id = result.payload.get("parent_id", result.id)
+            units.setdefault(parent_id, []).append(result)
+        return units
+
+    @staticmethod
+    def _is_unchanged(
+        unit: SemanticUnit, chunks: list[Chunk], points: list[SearchResult]
+    ) -> bool:
+        """Check whether a unit's stored points can be kept as they are.
+
+        Needs a matching content hash and one stored vector per chunk;
+        points written before content hashing never match.
+        """
+        return (
+            len(points) == len(chunks)
+            and points[0].payload.get("content_hash") == compute_unit_hash(unit)
+            and all(point.vector is not None for point in points)
+        )
 
     async def _embed_units(
         self, units: list[SemanticUnit], errors: list[IndexingError]
-    ) -> tuple[list[SemanticUnit], list[Vector]]:
-        """Embed units in batches."""
-        successful_units: list[SemanticUnit] = []
-        embeddings: list[Vector] = []
+    ) -> list[tuple[SemanticUnit, list[Chunk], list[Vector]]]:
+        """Embed the chunks of units in batches.
+
+        Returns:
+            Each successfully embedded unit with its chunks and their
+            vectors; a unit with any chunk in a failed batch is left out
+        """
+        chunks = {id(unit): self._chunk_unit(unit) for unit in units}
+        pending = [(unit, chunk) for unit in units for chunk in chunks[id(unit)]]
+        vectors: dict[int, list[Vector]] = {id(unit): [] for unit in units}
+        failed: set[int] = set()
         batch_size = self.embedding_batch_size
 
-        for i in range(0, len(units), batch_size):
-            batch = units[i : i + batch_size]
-            texts = [self._prepare_embedding_text(u) for u in batch]
+        for i in range(0, len(pending), batch_size):
+            batch = pending[i : i + batch_size]
+            texts = [chunk.text for _, chunk in batch]
 
             try:
                 batch_embeddings = await self.embedding_service.embed_batch(texts)
-                embeddings.extend(batch_embeddings)
-                successful_units.extend(batch)
+                for (unit, _), embedding in zip(batch, batch_embeddings):
+                    vectors[id(unit)].append(embedding)
             except EmbeddingModelError as e:
                 logger.warning(
                     "embedding_batch_failed",
@@ -185,7 +385,10 @@ class CodeIndexer:
                     batch_start=i,
                     error=str(e),
                 )
-                for unit in batch:
+                for unit, _ in batch:
+                    if id(unit) in failed:
+                        continue
+                    failed.add(id(unit))
                     errors.append(
                         IndexingError(
                             file_path=unit.file_path,
@@ -194,42 +397,140 @@ class CodeIndexer:
                         )
                     )
 
-        return successful_units, embeddings
+        return [
+            (unit, chunks[id(unit)], vectors[id(unit)])
+            for unit in units
+            if id(unit) not in failed
+        ]
 
-    def _prepare_embedding_text(self, unit: SemanticUnit) -> str:
-        """Format unit for embedding."""
-        parts = [unit.signature]
+    async def _store_symbols(
+        self, path: str, project: str, units: list[SemanticUnit]
+    ) -> None:
+        """Persist a file's definitions and raw call sites for call graph resolution."""
+        symbols = [
+            CodeSymbol(
+                qualified_name=u.qualified_name,
+                name=u.name,
+                unit_type=u.unit_type.value,
+                file_path=path,
+            )
+            for u in units
+        ]
+        call_sites = [
+            CallSite(
+                caller_qualified_name=u.qualified_name,
+                callee_name=callee,
+                caller_file=path,
+            )
+            for u in units
+            for callee in u.calls
+        ]
+        await self.metadata_store.replace_file_symbols(
+            path, project, symbols, call_sites
+        )
 
-        if unit.docstring:
-            parts.append(unit.docstring)
+    async def update_call_graph(self, project: str) -> int:
+        """Re-resolve all call sites in a project and rewrite its call graph.
 
-        content = unit.content
-        if len(content) > 4000:
-            content = content[:4000]
-        parts.append(content)
+        Returns:
+            Number of call edges stored
+        """
+        symbols = await self.metadata_store.list_symbols(project)
+        call_sites = await self.metadata_store.list_call_sites(project)
+        edges = resolve_calls(symbols, call_sites)
+        await self.metadata_store.replace_call_graph(project, edges)
+        logger.info(
+            "call_graph_updated",
+            project=project,
+            call_sites=len(call_sites),
+            edges=len(edges),
+        )
+        return len(edges)
+
+    def _chunk_unit(self, unit: SemanticUnit) -> list[Chunk]:
+        """Split a unit into chunks sized to the model's input length."""
+        return chunk_unit(
+            unit,
+            max_embedding_chars(self.embedding_service.max_tokens),
+            self.chunk_overlap_tokens * CHARS_PER_TOKEN,
+        )
+
+    def _crate_for(self, path: str) -> CargoCrate | None:
+        """Find the Cargo crate a file belongs to.
 
-        return "\n\n".join(parts)
+        Prefers members of the workspace being indexed, then falls back to
+        the nearest Cargo.toml (e.g. for single-file index runs). Files under
+        the workspace's ``exclude`` paths belong to no crate.
+        """
+        if self._workspace:
+            crate = self._workspace.crate_for(path)
+            if crate:
+                return crate
+            if self._workspace.is_excluded(path):
+                return None
+
+        directory = str(Path(path).parent)
+        if directory not in self._crate_cache:
+            self._crate_cache[directory] = find_crate(path)
+        return self._crate_cache[directory]
 
     def _build_payload(
-        self, unit: SemanticUnit, project: str
+        self, unit: SemanticUnit, project: str, crate: CargoCrate | None = None
     ) -> dict[str, Any]:
         """Build vector store payload from SemanticUnit."""
+        code = unit.content
+        code_truncated = len(code) > self.max_payload_code_chars
+        if code_truncated:
+            # Cut at a line boundary so snippets never show half a line
+            code = code[: self.max_payload_code_chars].rsplit("\n", 1)[0]
+
         return {
             "project": project,
+            "crate": crate.name if crate else None,
+            "crate_version": crate.version if crate else None,
             "file_path": unit.file_path,
             "name": unit.name,
             "qualified_name": unit.qualified_name,
             "unit_type": unit.unit_type.value,
             "signature": unit.signature,
+            "code": code,
+            "code_truncated": code_truncated,
+            "docstring": unit.docstring,
             "language": unit.language,
             "start_line": unit.start_line,
             "end_line": unit.end_line,
             "line_count": unit.end_line - unit.start_line + 1,
             "complexity": unit.complexity,
             "has_docstring": unit.docstring is not None,
+            "visibility": unit.visibility,
+            "attributes": unit.attributes,
+            "attribute_names": [_attribute_name(a) for a in unit.attributes],
+            "content_hash": compute_unit_hash(unit),
             "indexed_at": datetime.now().isoformat(),
         }
 
+    @staticmethod
+    def _chunk_payload(
+        payload: dict[str, Any], parent_id: str, chunk: Chunk, chunk_count: int
+    ) -> dict[str, Any]:
+        """Build the payload of one chunk of a unit.
+
+        Chunk 0 keeps the unit's payload; later chunks store only their
+        own lines as ``code``.
+        """
+        chunk_payload = {
+            **payload,
+            "parent_id": parent_id,
+            "chunk_index": chunk.index,
+            "chunk_count": chunk_count,
+            "chunk_start_line": chunk.start_line,
+            "chunk_end_line": chunk.end_line,
+        }
+        if chunk.index > 0:
+            chunk_payload["code"] = chunk.content
+            chunk_payload["code_truncated"] = False
+        return chunk_payload
+
     async def needs_reindex(self, path: str, project: str) -> bool:
         """Check if file needs reindexing."""
         indexed_file = await self.metadata_store.get_indexed_file(path, project)
@@ -249,47 +550,270 @@ class CodeIndexer:
         project: str,
         recursive: bool = True,
         exclude_patterns: list[str] | None = None,
+        exclusions: ExclusionSettings | None = None,
+        progress: ProgressCallback | None = None,
     ) -> IndexingStats:
-        """Index all supported files in directory."""
+        """Index all supported files in directory.
+
+        Changed files are parsed concurrently (in a process pool for large
+        runs), and their units are embedded and upserted in cross-file
+        batches of ``embedding_batch_size``.
+
+        Args:
+            exclusions: Ignore-file and pattern settings; defaults to the
+                project's stored settings. Saved to the project when given.
+            progress: Optional callback invoked after each batch is stored
+        """
         await self._ensure_collection()
 
         start_time = time.time()
         stats = IndexingStats(files_indexed=0, units_indexed=0, files_skipped=0)
 
-        files = self._find_files(path, recursive, exclude_patterns)
+        path = self.normalize_path(path)
+        if exclusions is None:
+            exclusions = await self.get_exclusions(project)
+        files = self._find_files(path, recursive, exclude_patterns, exclusions)
 
-        for file_path in files:
-            file_stats = await self.index_file(file_path, project)
-            stats.files_indexed += file_stats.files_indexed
-            stats.units_indexed += file_stats.units_indexed
-            stats.files_skipped += file_stats.files_skipped
-            stats.errors.extend(file_stats.errors)
+        workspace = load_workspace(path)
+        self._workspace = workspace
+        self._crate_cache.clear()
+        crates: set[str] = set()
+        try:
+            changed: list[str] = []
+            for file_path in files:
+                crate = self._crate_for(file_path)
+                if crate:
+                    crates.add(crate.name)
+                if await self.needs_reindex(file_path, project):
+                    changed.append(file_path)
+                else:
+                    stats.files_skipped += 1
+
+            await self._index_changed_files(changed, project, stats, progress)
+        finally:
+            self._workspace = None
+
+        stats.files_removed = await self._purge_missing_files(
+            path, project, files, recursive
+        )
+        await self.update_call_graph(project)
+        await self._register_project(project, path, exclusions)
 
+        stats.crates = sorted(crates)
+        stats.workspace = workspace
         stats.duration_ms = int((time.time() - start_time) * 1000)
         return stats
 
+    async def _index_changed_files(
+        self,
+        paths: list[str],
+        project: str,
+        stats: IndexingStats,
+        progress: ProgressCallback | None,
+    ) -> None:
+        """Parse files concurrently and store them in cross-file batches."""
+        report = IndexingProgress(files_total=len(paths))
+        batch: list[tuple[str, list[SemanticUnit]]] = []
+        batch_units = 0
+
+        async def flush() -> None:
+            nonlocal batch, batch_units
+            if batch:
+                await self._index_parsed_files(batch, project, stats)
+                report.files_done += len(batch)
+                batch, batch_units = [], 0
+            report.units_indexed = stats.units_indexed
+            logger.info(
+                "indexing_progress",
+                project=project,
+                files_done=report.files_done,
+                files_total=report.files_total,
+                units_indexed=report.units_indexed,
+            )
+            if progress:
+                progress(report)
+
+        async for path, result in self._parse_files(paths):
+            if isinstance(result, ParseError):
+                logger.warning("parse_failed", path=path, error=result.message)
+                stats.errors.append(
+                    IndexingError(path, result.error_type, result.message)
+                )
+                report.files_done += 1
+                continue
+            if not result:
+                stats.files_skipped += 1
+                report.files_done += 1
+                continue
+
+            batch.append((path, result))
+            batch_units += len(result)
+            if batch_units >= self.embedding_batch_size:
+                await flush()
+
+        await flush()
+
+    async def _parse_files(
+        self, paths: list[str]
+    ) -> AsyncIterator[tuple[str, list[SemanticUnit] | ParseError]]:
+        """Parse files, yielding (path, units or error) as each completes."""
+        workers = self._parse_workers(len(paths))
+        if workers <= 1:
+            for path in paths:
+                try:
+                    yield path, await self.parser.parse_file(path)
+                except ParseError as e:
+                    yield path, e
+            return
+
+        loop = asyncio.get_running_loop()
+        # spawn, not fork: the server may already have loaded PyTorch
+        context = multiprocessing.get_context("spawn")
+        with ProcessPoolExecutor(max_workers=workers, mp_context=context) as pool:
+
+            async def parse(path: str) -> tuple[str, list[SemanticUnit] | ParseError]:
+                try:
+                    units = await loop.run_in_executor(
+                        pool, parse_file_in_worker, path
+                    )
+                    return path, units
+                except ParseError as e:
+                    return path, e
+
+            for next_result in asyncio.as_completed([parse(p) for p in paths]):
+                yield await next_result
+
+    def _parse_workers(self, file_count: int) -> int:
+        """Number of parse processes to use for a run."""
+        if type(self.parser) is not TreeSitterParser:
+            # Custom parsers may not be importable in worker processes
+            return 1
+        if file_count < _PARALLEL_PARSE_MIN_FILES:
+            return 1
+        configured = settings.indexer.parse_workers
+        return configured if configured > 0 else min(os.cpu_count() or 1, 8)
+
+    async def _purge_missing_files(
+        self, root: str, project: str, found: list[str], recursive: bool
+    ) -> int:
+        """Remove indexed files under root that this run did not find.
+
+        Covers files that were deleted, moved, or are now excluded. Only
+        files within the scanned scope are considered, so indexing a
+        subdirectory (or non-recursively) leaves the rest of the project alone.
+
+        Returns:
+            Number of files removed
+        """
+        root_path = Path(root).expanduser()
+        found_set = set(found)
+        removed = 0
+        for indexed in await self.metadata_store.list_indexed_files(project):
+            file_path = Path(indexed.file_path)
+            if indexed.file_path in found_set:
+                continue
+            if not file_path.is_relative_to(root_path):
+                continue
+            if not recursive and file_path.parent != root_path:
+                continue
+            await self.remove_file(indexed.file_path, project)
+            removed += 1
+        return removed
+
+    async def get_exclusions(self, project: str) -> ExclusionSettings:
+        """Get a project's stored exclusion settings (defaults if unset)."""
+        existing = await self.metadata_store.get_project(project)
+        return ExclusionSettings.from_settings(existing.settings if existing else None)
+
+    async def _register_project(
+        self, project: str, root: str, exclusions: ExclusionSettings
+    ) -> None:
+        """Record the project root and exclusions so watch mode can follow it.
+
+        Indexing a subdirectory of the recorded root keeps the root, and
+        indexing a parent of it widens the root to that parent.
+        """
+        existing = await self.metadata_store.get_project(project)
+        project_settings = dict(existing.settings) if existing else {}
+        project_settings[ExclusionSettings.SETTINGS_KEY] = exclusions.to_dict()
+        root_path = self.normalize_path(root)
+        if existing:
+            # Compared resolved, so a symlinked and a real path to the same
+            # directory are one root
+            recorded = Path(existing.root_path).expanduser().resolve()
+            if Path(root_path).resolve().is_relative_to(recorded):
+                root_path = existing.root_path
+        await self.metadata_store.add_project(
+            name=project,
+            root_path=root_path,
+            settings=project_settings,
+        )
+        await self.metadata_store.update_project_last_indexed(project)
+
+    def should_index(
+        self,
+        path: str,
+        root: str,
+        exclude_patterns: list[str] | None = None,
+        exclusions: ExclusionSettings | None = None,
+    ) -> bool:
+        """Check whether a single file under root would be picked up by indexing.
+
+        Applies the same rules as directory indexing (supported extension,
+        excluded directories, exclusion patterns, ignore files) without
+        walking the tree.
+        """
+        file_path = Path(path)
+        if file_path.suffix not in EXTENSION_MAP or file_path.is_symlink():
+            return False
+        root_path = Path(root).expanduser()
+        try:
+            parents = file_path.relative_to(root_path).parts[:-1]
+        except ValueError:
+            return False
+        excluded_dirs = self._build_excluded_dirs(exclude_patterns)
+        if any(part in excluded_dirs for part in parents):
+            return False
+        if self._should_exclude(path, exclude_patterns):
+            return False
+        matcher = IgnoreMatcher.for_root(root_path, exclusions)
+        return not matcher.is_path_ignored(root_path.absolute(), file_path.absolute())
+
     def _find_files(
-        self, root: str, recursive: bool, exclude_patterns: list[str] | None
+        self,
+        root: str,
+        recursive: bool,
+        exclude_patterns: list[str] | None,
+        exclusions: ExclusionSettings | None = None,
     ) -> list[str]:
         """Find all supported files in directory.
 
         Uses os.walk with in-place directory pruning to avoid descending
         into excluded directories (e.g. .venv, node_modules). This prevents
         the performance cost of enumerating thousands of irrelevant files.
+        Directories and files matched by .gitignore/.calmignore rules are
+        skipped the same way.
         """
         supported_exts = set(EXTENSION_MAP.keys())
         files: list[str] = []
 
         root_path = Path(root).expanduser()
         excluded_dirs = self._build_excluded_dirs(exclude_patterns)
+        matcher = IgnoreMatcher.for_root(root_path, exclusions)
 
         try:
             for dirpath, dirnames, filenames in os.walk(
                 str(root_path), followlinks=False
             ):
+                current = Path(dirpath).absolute()
+                matcher.load_directory(current)
+
                 # Prune excluded directories in-place to prevent descent
                 dirnames[:] = [
-                    d for d in dirnames if d not in excluded_dirs
+                    d
+                    for d in dirnames
+                    if d not in excluded_dirs
+                    and not matcher.is_ignored(current / d, is_dir=True)
                 ]
 
                 for filename in filenames:
@@ -300,6 +824,8 @@ class CodeIndexer:
                         continue
                     if self._should_exclude(str(file_path), exclude_patterns):
                         continue
+                    if matcher.is_ignored(current / filename):
+                        continue
                     files.append(str(file_path))
 
                 if not recursive:
@@ -347,6 +873,7 @@ class CodeIndexer:
         count = await self._count_file_units(path, project)
         await self._delete_file_units(path, project)
         await self.metadata_store.delete_indexed_file(path, project)
+        await self.metadata_store.delete_file_symbols(path, project)
         logger.info("file_removed", path=path, project=project, units_removed=count)
         return count
 
@@ -383,14 +910,14 @@ class CodeIndexer:
         return file_info is not None
 
     async def _count_file_units(self, path: str, project: str) -> int:
-        """Count units for a file in the vector store."""
+        """Count units (not chunks) for a file in the vector store."""
         return await self.vector_store.count(
             collection=self.COLLECTION_NAME,
-            filters={"file_path": path, "project": project},
+            filters={"file_path": path, "project": project, **UNIT_FILTER},
         )
 
     async def _delete_file_units(self, path: str, project: str) -> None:
-        """Delete all vector store entries for a file."""
+        """Delete all vector store entries (units and chunks) for a file."""
         total_deleted = 0
         while True:
             results = await self.vector_store.scroll(
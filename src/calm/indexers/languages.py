@@ -0,0 +1,532 @@
+"""Registry of languages the code parser understands.
+
+Each language is described by a ``LanguageSpec``: its file extensions, the
+tree-sitter grammar to load, the node types counted for cyclomatic
+complexity and call graphs, and optionally a query that extracts its
+definitions. ``EXTENSION_MAP`` and the parser's language tables are derived
+from the registry; ``EXTENSION_MAP`` also covers the document formats in
+``DOCUMENT_EXTENSIONS``.
+
+Other packages add languages under the ``calm.languages`` entry point
+group, pointing at a ``LanguageSpec``::
+
+    [project.entry-points."calm.languages"]
+    elixir = "calm_elixir:ELIXIR"
+
+Definitions are extracted with the spec's ``query``; built-in languages
+keep theirs in ``queries/<name>.scm``. Queries use the tree-sitter tags
+conventions: ``@definition.<kind>`` on a definition and ``@name`` (plus
+optional ``@doc`` comments) in the same pattern, e.g.::
+
+    ((comment)* @doc
+     .
+     (function_definition name: (identifier) @name) @definition.function)
+
+Definitions are matched at any depth and qualified by the definitions and
+scopes enclosing them, so a query also captures:
+
+- ``@scope`` with ``@name``: a namespace or module that only adds to names
+- ``@scope.type``: a scope whose functions are methods, named by ``@name``
+  (e.g. a Rust ``impl``); with ``@trait`` it is named ``<Type as Trait>``
+- ``@receiver``: the type a Go-style method is declared on
+- ``@package``: the package name, used instead of the file stem
+
+Grammars are imported only when a file in the language is first parsed,
+and plugins only when the registry is first used.
+"""
+
+import importlib
+from collections.abc import Callable, Iterator, Mapping, MutableMapping
+from dataclasses import dataclass, field
+from functools import cache
+from importlib.metadata import entry_points
+from pathlib import Path
+
+import structlog
+
+from .base import UnitType
+
+logger = structlog.get_logger(__name__)
+
+ENTRY_POINT_GROUP = "calm.languages"
+
+# Definition kinds from tags queries, by the unit type they produce
+DEFINITION_KINDS: dict[str, UnitType] = {
+    "function": UnitType.FUNCTION,
+    "method": UnitType.METHOD,
+    "class": UnitType.CLASS,
+    "interface": UnitType.CLASS,
+    "struct": UnitType.CLASS,
+    "enum": UnitType.CLASS,
+    "trait": UnitType.CLASS,
+    "type": UnitType.CLASS,
+    "module": UnitType.MODULE,
+    "constant": UnitType.CONSTANT,
+}
+
+
+@dataclass(frozen=True)
+class LanguageSpec:
+    """How to recognize and parse one language.
+
+    Attributes:
+        name: Language identifier stored on units (e.g. "go")
+        extensions: File extensions, lowercase and including the dot
+        grammar: ``"package:function"`` returning the tree-sitter language
+        branch_types: Node types that add a branch to cyclomatic complexity
+        call_types: Call node types, mapped to the field holding the callee
+        receiver_field: Field holding a call's receiver, for grammars whose
+            callee field is only the method name
+        query: Tree-sitter query capturing definitions (see module docs)
+        separator: Joins the parts of qualified names (e.g. "::")
+        qualify_with_file: Start qualified names with the file stem when
+            the query captures no ``@package``
+        doc_comment: Marker of doc comments above a definition: a line
+            prefix such as "///", or "/**" for block comments
+    """
+
+    name: str
+    extensions: tuple[str, ...]
+    grammar: str
+    branch_types: frozenset[str] = frozenset()
+    call_types: Mapping[str, str] = field(default_factory=dict)
+    receiver_field: str | None = None
+    query: str | None = None
+    separator: str = "."
+    qualify_with_file: bool = True
+    doc_comment: str | None = None
+
+    @property
+    def package(self) -> str:
+        """Get the name of the grammar's Python package."""
+        return self.grammar.partition(":")[0]
+
+    def load_grammar(self) -> object:
+        """Import the grammar package and return its language pointer.
+
+        Raises:
+            ImportError: If the grammar package isn't installed
+        """
+        package, _, function = self.grammar.partition(":")
+        module = importlib.import_module(package)
+        return getattr(module, function or "language")()
+
+
+# Non-code files, split into sections and config blocks by
+# calm.indexers.documents rather than parsed with a grammar
+DOCUMENT_EXTENSIONS: dict[str, str] = {
+    ".md": "markdown",
+    ".markdown": "markdown",
+    ".toml": "toml",
+    ".yaml": "yaml",
+    ".yml": "yaml",
+}
+
+# Query files of the built-in languages
+QUERIES_DIR = Path(__file__).parent / "queries"
+
+
+def _builtin_query(name: str) -> str:
+    """Read a built-in language's definition query."""
+    return (QUERIES_DIR / f"{name}.scm").read_text(encoding="utf-8")
+
+
+# Shared by JavaScript and TypeScript
+_JS_BRANCH_TYPES = frozenset(
+    {
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "try_statement",
+        "catch_clause",
+        "switch_statement",
+        "switch_case",
+        "binary_expression",  # &&, ||
+        "ternary_expression",  # ?:
+    }
+)
+
+# Shared by C and C++
+_C_BRANCH_TYPES = frozenset(
+    {
+        "if_statement",
+        "else_clause",
+        "switch_statement",
+        "case_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "binary_expression",  # &&, ||
+        "conditional_expression",  # ?:
+    }
+)
+
+BUILTIN_LANGUAGES: tuple[LanguageSpec, ...] = (
+    LanguageSpec(
+        name="python",
+        extensions=(".py",),
+        grammar="tree_sitter_python:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "elif_clause",
+                "for_statement",
+                "while_statement",
+                "try_statement",
+                "except_clause",
+                "with_statement",
+                "boolean_operator",  # and, or
+                "match_statement",
+                "case_clause",
+            }
+        ),
+        call_types={"call": "function"},
+        query=_builtin_query("python"),
+    ),
+    LanguageSpec(
+        name="typescript",
+        extensions=(".ts", ".tsx"),
+        grammar="tree_sitter_typescript:language_typescript",
+        branch_types=_JS_BRANCH_TYPES,
+        call_types={"call_expression": "function", "new_expression": "constructor"},
+        query=_builtin_query("typescript"),
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="javascript",
+        extensions=(".js", ".jsx"),
+        grammar="tree_sitter_javascript:language",
+        branch_types=_JS_BRANCH_TYPES,
+        call_types={"call_expression": "function", "new_expression": "constructor"},
+        query=_builtin_query("javascript"),
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="rust",
+        extensions=(".rs",),
+        grammar="tree_sitter_rust:language",
+        branch_types=frozenset(
+            {
+                "if_expression",
+                "else_clause",
+                "match_expression",
+                "match_arm",
+                "for_expression",
+                "while_expression",
+                "loop_expression",
+                "binary_expression",  # &&, ||, ?
+            }
+        ),
+        call_types={"call_expression": "function"},
+        query=_builtin_query("rust"),
+        separator="::",
+    ),
+    LanguageSpec(
+        name="swift",
+        extensions=(".swift",),
+        grammar="tree_sitter_swift:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "else_clause",
+                "switch_statement",
+                "switch_case",
+                "for_statement",
+                "while_statement",
+                "guard_statement",
+                "binary_expression",  # &&, ||, ??
+                "ternary_expression",
+            }
+        ),
+        query=_builtin_query("swift"),
+        doc_comment="///",
+    ),
+    LanguageSpec(
+        name="java",
+        extensions=(".java",),
+        grammar="tree_sitter_java:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "else_clause",
+                "switch_statement",
+                "switch_case",
+                "for_statement",
+                "while_statement",
+                "do_statement",
+                "try_statement",
+                "catch_clause",
+                "binary_expression",  # &&, ||
+                "ternary_expression",  # ?:
+            }
+        ),
+        call_types={"method_invocation": "name", "object_creation_expression": "type"},
+        receiver_field="object",
+        query=_builtin_query("java"),
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="c",
+        extensions=(".c", ".h"),
+        grammar="tree_sitter_c:language",
+        branch_types=_C_BRANCH_TYPES,
+        call_types={"call_expression": "function"},
+        query=_builtin_query("c"),
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="cpp",
+        extensions=(".cpp", ".hpp", ".cc", ".cxx"),
+        grammar="tree_sitter_cpp:language",
+        branch_types=_C_BRANCH_TYPES,
+        call_types={"call_expression": "function"},
+        query=_builtin_query("cpp"),
+        separator="::",
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="sql",
+        extensions=(".sql",),
+        grammar="tree_sitter_sql:language",
+        query=_builtin_query("sql"),
+        doc_comment="--",
+    ),
+    LanguageSpec(
+        name="go",
+        extensions=(".go",),
+        grammar="tree_sitter_go:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "for_statement",
+                "expression_case",
+                "type_case",
+                "communication_case",
+                "binary_expression",  # &&, ||
+            }
+        ),
+        call_types={"call_expression": "function"},
+        query=_builtin_query("go"),
+        doc_comment="//",
+    ),
+    LanguageSpec(
+        name="kotlin",
+        extensions=(".kt", ".kts"),
+        grammar="tree_sitter_kotlin:language",
+        branch_types=frozenset(
+            {
+                "if_expression",
+                "when_entry",
+                "for_statement",
+                "while_statement",
+                "do_while_statement",
+                "catch_block",
+                "conjunction_expression",  # &&
+                "disjunction_expression",  # ||
+                "elvis_expression",  # ?:
+            }
+        ),
+        query=_builtin_query("kotlin"),
+        doc_comment="/**",
+    ),
+    LanguageSpec(
+        name="csharp",
+        extensions=(".cs",),
+        grammar="tree_sitter_c_sharp:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "switch_section",
+                "switch_expression_arm",
+                "for_statement",
+                "foreach_statement",
+                "while_statement",
+                "do_statement",
+                "catch_clause",
+                "binary_expression",  # &&, ||, ??
+                "conditional_expression",  # ?:
+            }
+        ),
+        call_types={
+            "invocation_expression": "function",
+            "object_creation_expression": "type",
+        },
+        query=_builtin_query("csharp"),
+        qualify_with_file=False,
+        doc_comment="///",
+    ),
+    LanguageSpec(
+        name="ruby",
+        extensions=(".rb",),
+        grammar="tree_sitter_ruby:language",
+        branch_types=frozenset(
+            {
+                "if",
+                "elsif",
+                "unless",
+                "if_modifier",
+                "unless_modifier",
+                "while",
+                "until",
+                "while_modifier",
+                "until_modifier",
+                "for",
+                "when",
+                "rescue",
+                "binary",  # and, or, &&, ||
+                "conditional",  # ?:
+            }
+        ),
+        call_types={"call": "method"},
+        receiver_field="receiver",
+        query=_builtin_query("ruby"),
+        separator="::",
+        qualify_with_file=False,
+        doc_comment="#",
+    ),
+    LanguageSpec(
+        name="lua",
+        extensions=(".lua",),
+        grammar="tree_sitter_lua:language",
+        branch_types=frozenset(
+            {
+                "if_statement",
+                "elseif_statement",
+                "for_statement",
+                "while_statement",
+                "repeat_statement",
+                "binary_expression",  # and, or
+            }
+        ),
+        call_types={"function_call": "name"},
+        query=_builtin_query("lua"),
+        doc_comment="--",
+    ),
+)
+
+
+def _plugin_languages() -> list[LanguageSpec]:
+    """Load the languages registered by installed packages.
+
+    A plugin that fails to load is logged and skipped.
+    """
+    specs: list[LanguageSpec] = []
+    for entry_point in entry_points(group=ENTRY_POINT_GROUP):
+        try:
+            spec = entry_point.load()
+        except Exception as e:
+            logger.warning(
+                "language_plugin_failed", plugin=entry_point.name, error=str(e)
+            )
+            continue
+        if not isinstance(spec, LanguageSpec):
+            logger.warning(
+                "language_plugin_invalid",
+                plugin=entry_point.name,
+                type=type(spec).__name__,
+            )
+            continue
+        specs.append(spec)
+    return specs
+
+
+@cache
+def get_languages() -> dict[str, LanguageSpec]:
+    """Get every registered language by name.
+
+    Plugins are loaded once per process. Built-in languages can't be
+    replaced by plugins.
+    """
+    languages = {spec.name: spec for spec in _plugin_languages()}
+    languages.update((spec.name, spec) for spec in BUILTIN_LANGUAGES)
+    return languages
+
+
+def extension_map() -> dict[str, str]:
+    """Map every registered file extension to its language.
+
+    Includes the document formats. Built-in languages and documents keep
+    their extensions if a plugin claims them too.
+    """
+    builtin = {spec.name for spec in BUILTIN_LANGUAGES}
+    plugins = [s for s in get_languages().values() if s.name not in builtin]
+    mapping = {ext: spec.name for spec in plugins for ext in spec.extensions}
+    mapping.update(DOCUMENT_EXTENSIONS)
+    mapping.update(
+        (ext, spec.name) for spec in BUILTIN_LANGUAGES for ext in spec.extensions
+    )
+    return mapping
+
+
+class LazyDict[V](MutableMapping[str, V]):
+    """Dict built on first access.
+
+    Lets module-level tables derived from the registry be defined without
+    loading language plugins at import time.
+    """
+
+    def __init__(self, build: Callable[[], dict[str, V]]) -> None:
+        self._build = build
+        self._data: dict[str, V] | None = None
+
+    @property
+    def _items(self) -> dict[str, V]:
+        if self._data is None:
+            self._data = self._build()
+        return self._data
+
+    def __getitem__(self, key: str) -> V:
+        return self._items[key]
+
+    def __setitem__(self, key: str, value: V) -> None:
+        self._items[key] = value
+
+    def __delitem__(self, key: str) -> None:
+        del self._items[key]
+
+    def __iter__(self) -> Iterator[str]:
+        return iter(self._items)
+
+    def __len__(self) -> int:
+        return len(self._items)
+
+
+def split_patterns(query: str) -> list[str]:
+    """Split a query into its top-level patterns.
+
+    Used to compile a query pattern by pattern when it fails as a whole,
+    e.g. because the installed grammar lacks one of its node types.
+    Predicates belong inside their pattern's parentheses.
+    """
+    patterns: list[str] = []
+    start: int | None = None
+    depth = 0
+    in_string = False
+    i = 0
+    while i < len(query):
+        char = query[i]
+        if in_string:
+            if char == "\\":
+                i += 1
+            elif char == '"':
+                in_string = False
+        elif char == '"':
+            in_string = True
+        elif char == ";":
+            # Comment to the end of the line
+            end = query.find("\n", i)
+            i = len(query) if end == -1 else end
+            continue
+        elif char in "([":
+            if depth == 0:
+                if start is not None:
+                    patterns.append(query[start:i].strip())
+                start = i
+            depth += 1
+        elif char in ")]":
+            depth -= 1
+        i += 1
+    if start is not None:
+        patterns.append(query[start:].strip())
+    return patterns
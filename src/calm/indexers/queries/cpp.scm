@@ -0,0 +1,30 @@
+; C++ definitions. Out-of-class definitions like Vector::norm are named
+; by their qualified declarator.
+
+(function_definition
+  declarator: (function_declarator
+    declarator: [
+      (identifier)
+      (field_identifier)
+      (qualified_identifier)
+      (destructor_name)
+      (operator_name)
+    ] @name)) @definition.function
+
+; Functions returning pointers or references
+(function_definition
+  declarator: (_
+    (function_declarator
+      declarator: [(identifier) (field_identifier) (qualified_identifier)] @name)))
+  @definition.function
+
+(class_specifier
+  name: (type_identifier) @name
+  body: (field_declaration_list)) @definition.class
+
+(struct_specifier
+  name: (type_identifier) @name
+  body: (field_declaration_list)) @definition.struct
+
+(namespace_definition
+  name: (_) @name) @scope
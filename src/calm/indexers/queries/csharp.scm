@@ -0,0 +1,31 @@
+; C# definitions, qualified by their namespace rather than the file
+
+(namespace_declaration
+  name: (_) @name) @scope
+
+(file_scoped_namespace_declaration
+  name: (_) @name) @scope
+
+(class_declaration
+  name: (identifier) @name) @definition.class
+
+(struct_declaration
+  name: (identifier) @name) @definition.struct
+
+(interface_declaration
+  name: (identifier) @name) @definition.interface
+
+(enum_declaration
+  name: (identifier) @name) @definition.enum
+
+(record_declaration
+  name: (identifier) @name) @definition.class
+
+(method_declaration
+  name: (identifier) @name) @definition.method
+
+(constructor_declaration
+  name: (identifier) @name) @definition.method
+
+(local_function_statement
+  name: (identifier) @name) @definition.function
@@ -0,0 +1,20 @@
+; Go definitions. Names start with the package, and methods are qualified
+; by their receiver type.
+
+(package_clause
+  (package_identifier) @package)
+
+(function_declaration
+  name: (identifier) @name) @definition.function
+
+(method_declaration
+  receiver: (parameter_list
+    (parameter_declaration
+      type: (_) @receiver))
+  name: (field_identifier) @name) @definition.method
+
+(type_spec
+  name: (type_identifier) @name) @definition.type
+
+(type_alias
+  name: (type_identifier) @name) @definition.type
@@ -0,0 +1,17 @@
+; Kotlin definitions. Names start with the package; interfaces are
+; class_declaration nodes too.
+
+(package_header
+  (identifier) @package)
+
+(class_declaration
+  (type_identifier) @name) @definition.class
+
+(object_declaration
+  (type_identifier) @name) @definition.class
+
+(function_declaration
+  (simple_identifier) @name) @definition.function
+
+; Companion object members belong to the enclosing class
+(companion_object) @scope.type
@@ -0,0 +1,8 @@
+; Lua definitions. Names like M.add are qualified by their table path.
+
+; function M:reset() takes self
+(function_declaration
+  name: (method_index_expression) @name) @definition.method
+
+(function_declaration
+  name: [(identifier) (dot_index_expression)] @name) @definition.function
@@ -0,0 +1,15 @@
+; Python definitions. Docstrings come from the body's first string.
+
+(class_definition
+  name: (identifier) @name) @definition.class
+
+(function_definition
+  name: (identifier) @name) @definition.function
+
+; Module-level UPPER_CASE constants
+(module
+  (expression_statement
+    (assignment
+      left: (identifier) @name) @definition.constant)
+  (#match? @name "^[A-Z0-9_]*[A-Z][A-Z0-9_]*$")
+  (#match? @name "_"))
@@ -0,0 +1,18 @@
+; Ruby definitions, qualified by their enclosing modules and classes
+; (e.g. Shapes::Circle). The parser joins methods to their owner with "."
+; (Shapes::Circle.area) and qualifies top-level methods with the file stem.
+
+(class
+  name: [(constant) (scope_resolution)] @name) @definition.class
+
+(module
+  name: [(constant) (scope_resolution)] @name) @definition.class
+
+(method
+  name: (_) @name) @definition.function
+
+(singleton_method
+  name: (_) @name) @definition.function
+
+; class << self
+(singleton_class) @scope.type
@@ -0,0 +1,35 @@
+; Rust definitions. Doc comments, attributes and visibility are read from
+; the item's siblings and modifiers.
+
+(function_item
+  name: (identifier) @name) @definition.function
+
+(struct_item
+  name: (type_identifier) @name) @definition.struct
+
+(enum_item
+  name: (type_identifier) @name) @definition.enum
+
+(trait_item
+  name: (type_identifier) @name) @definition.trait
+
+; Inline modules; `mod foo;` lives in its own file
+(mod_item
+  name: (identifier) @name
+  body: (declaration_list)) @scope
+
+; impl Type { ... }
+(impl_item
+  !trait
+  type: (_) @name
+  body: (declaration_list)) @scope.type
+
+; impl Trait for Type { ... }, named <Type as Trait>
+(impl_item
+  trait: [
+    (type_identifier) @trait
+    (scoped_type_identifier name: (type_identifier) @trait)
+    (generic_type) @trait
+  ]
+  type: (_) @name
+  body: (declaration_list)) @scope.type
@@ -0,0 +1,11 @@
+; Swift definitions. Classes, structs, enums and extensions are all
+; class_declaration nodes.
+
+(class_declaration
+  name: (_) @name) @definition.class
+
+(protocol_declaration
+  name: (_) @name) @definition.interface
+
+(function_declaration
+  name: (_) @name) @definition.function
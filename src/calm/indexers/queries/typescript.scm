@@ -0,0 +1,34 @@
+; TypeScript definitions
+
+(function_declaration
+  name: (identifier) @name) @definition.function
+
+(generator_function_declaration
+  name: (identifier) @name) @definition.function
+
+(class_declaration
+  name: (_) @name) @definition.class
+
+(abstract_class_declaration
+  name: (_) @name) @definition.class
+
+(interface_declaration
+  name: (_) @name) @definition.interface
+
+(method_definition
+  name: (_) @name) @definition.method
+
+; Functions assigned to a const, let or var
+(lexical_declaration
+  (variable_declarator
+    name: (identifier) @name
+    value: [(arrow_function) (function_expression)])) @definition.function
+
+(variable_declaration
+  (variable_declarator
+    name: (identifier) @name
+    value: [(arrow_function) (function_expression)])) @definition.function
+
+; namespace Foo { ... }
+(internal_module
+  name: (_) @name) @scope
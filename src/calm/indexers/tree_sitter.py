@@ -1,195 +1,214 @@
 """TreeSitter-based code parser implementation."""
 
 import asyncio
+import importlib.util
+import re
+from collections.abc import Callable
+from dataclasses import dataclass
 from pathlib import Path
 
 import structlog
-from tree_sitter import Language, Node, Parser
-
-from .base import CodeParser, ParseError, SemanticUnit, UnitType
-from .utils import EXTENSION_MAP
+from tree_sitter import Language, Node, Parser, Query, QueryCursor
+
+from .base import CodeParser, SemanticUnit, UnitType
+from .cargo import CargoCrate, find_crate, rust_module_path
+from .documents import DOCUMENT_LANGUAGES, DocumentParser
+from .languages import (
+    DEFINITION_KINDS,
+    LanguageSpec,
+    LazyDict,
+    get_languages,
+    split_patterns,
+)
+from .utils import EXTENSION_MAP, read_source
 
 logger = structlog.get_logger(__name__)
 
+# Parser reused across calls within a parse worker process
+_worker_parser: "TreeSitterParser | None" = None
+
 # Branch node types for cyclomatic complexity by language
-BRANCH_TYPES: dict[str, set[str]] = {
-    "python": {
-        "if_statement",
-        "elif_clause",
-        "for_statement",
-        "while_statement",
-        "try_statement",
-        "except_clause",
-        "with_statement",
-        "boolean_operator",  # and, or
-        "match_statement",
-        "case_clause",
-    },
-    "typescript": {
-        "if_statement",
-        "for_statement",
-        "while_statement",
-        "do_statement",
-        "try_statement",
-        "catch_clause",
-        "switch_statement",
-        "switch_case",
-        "binary_expression",  # &&, ||
-        "ternary_expression",  # ?:
-    },
-    "javascript": {
-        "if_statement",
-        "for_statement",
-        "while_statement",
-        "do_statement",
-        "try_statement",
-        "catch_clause",
-        "switch_statement",
-        "switch_case",
-        "binary_expression",  # &&, ||
-        "ternary_expression",  # ?:
-    },
-    "lua": {
-        "if_statement",
-        "elseif_clause",
-        "for_statement",
-        "while_statement",
-        "repeat_statement",
-        "binary_operator",  # and, or
-    },
-    "rust": {
-        "if_expression",
-        "else_clause",
-        "match_expression",
-        "match_arm",
-        "for_expression",
-        "while_expression",
-        "loop_expression",
-        "binary_expression",  # &&, ||, ?
-    },
-    "swift": {
-        "if_statement",
-        "else_clause",
-        "switch_statement",
-        "switch_case",
-        "for_statement",
-        "while_statement",
-        "guard_statement",
-        "binary_expression",  # &&, ||, ??
-        "ternary_expression",
-    },
-    "java": {
-        "if_statement",
-        "else_clause",
-        "switch_statement",
-        "switch_case",
-        "for_statement",
-        "while_statement",
-        "do_statement",
-        "try_statement",
-        "catch_clause",
-        "binary_expression",  # &&, ||
-        "ternary_expression",  # ?:
-    },
-    "c": {
-        "if_statement",
-        "else_clause",
-        "switch_statement",
-        "case_statement",
-        "for_statement",
-        "while_statement",
-        "do_statement",
-        "binary_expression",  # &&, ||
-        "conditional_expression",  # ?:
-    },
-    "cpp": {
-        "if_statement",
-        "else_clause",
-        "switch_statement",
-        "case_statement",
-        "for_statement",
-        "while_statement",
-        "do_statement",
-        "binary_expression",  # &&, ||
-        "conditional_expression",  # ?:
-    },
-}
+BRANCH_TYPES: LazyDict[frozenset[str]] = LazyDict(
+    lambda: {name: spec.branch_types for name, spec in get_languages().items()}
+)
+
+# Comment node types across grammars, for doc comment lookups
+COMMENT_TYPES = {"comment", "line_comment", "block_comment", "multiline_comment"}
+
+# XML tags in C# doc comments, e.g. <summary>, <param name="x">
+_XML_TAG_RE = re.compile(r"</?\w+[^>]*>")
+
+# Comment markers at the start or end of a line of a @doc capture
+_COMMENT_MARKER_RE = re.compile(r"^\s*(?:/\*+|\*+/|\*|//+!?|--+|#+|;+)\s?")
+
+# C++-only constructs that mark a .h header as C++ rather than C
+_CPP_HEADER_RE = re.compile(
+    r"^\s*(?:namespace\b|template\s*<|class\s+\w+[^;]*?[:{]|using\s+namespace\b"
+    r"|(?:public|private|protected)\s*:)|\bstd::",
+    re.MULTILINE,
+)
+
+# Separators inside a captured name written as a path (M.add, Vector::norm)
+_NAME_PATH_RE = re.compile(r"::|\.|:")
+
+
+def _strip_generic_args(name: str) -> str:
+    """Drop generic arguments from a type name, e.g. ``Foo<T>`` -> ``Foo``."""
+    kept: list[str] = []
+    depth = 0
+    for i, char in enumerate(name):
+        if char == "<":
+            depth += 1
+        elif char == ">" and depth and name[i - 1] != "-":
+            depth -= 1
+        elif not depth:
+            kept.append(char)
+    return "".join(kept).strip()
+
+
+# Per-language hooks for what a query can't express: the module path of a
+# file, a unit for the module itself, and extra unit details
+ModuleResolver = Callable[[str], str]
+ModuleExtractor = Callable[[Node, str, str, str], SemanticUnit | None]
+UnitFinisher = Callable[[SemanticUnit, Node, str], None]
+
+
+@dataclass
+class _Match:
+    """A definition or scope matched by a language's query."""
+
+    node: Node
+    kind: str  # Definition kind, "scope" or "scope.type"
+    name: str | None  # None for scopes that don't add to names
+    docs: list[Node]
+    receiver: str | None = None
+
+    @property
+    def is_scope(self) -> bool:
+        """Whether the match only qualifies the definitions inside it."""
+        return self.kind.startswith("scope")
+
+    @property
+    def is_type(self) -> bool:
+        """Whether functions directly inside the match are methods."""
+        return (
+            self.kind == "scope.type"
+            or DEFINITION_KINDS.get(self.kind) == UnitType.CLASS
+        )
 
 
 class TreeSitterParser(CodeParser):
     """Code parser using tree-sitter for multi-language support."""
 
     def __init__(self) -> None:
-        """Initialize parser with all supported language grammars.
+        """Initialize the parser.
 
-        Loading is done eagerly to avoid lazy-loading overhead during parsing.
-        Each language grammar is imported from its respective package.
+        Languages come from the language registry. Grammars are loaded on
+        first use of each language, so a missing optional grammar package
+        only affects files in that language.
         """
-        # Import languages dynamically
-        import tree_sitter_cpp
-        import tree_sitter_java
-        import tree_sitter_javascript
-        import tree_sitter_python
-        import tree_sitter_rust
-        import tree_sitter_sql
-        import tree_sitter_swift
-        import tree_sitter_typescript
-
-        # Create parsers for each language
-        self._parsers: dict[str, Parser] = {}
-        self._languages: dict[str, Language] = {}
-
-        # Python
-        self._languages["python"] = Language(tree_sitter_python.language())
-        self._parsers["python"] = Parser(self._languages["python"])
-
-        # TypeScript
-        ts_lang = Language(tree_sitter_typescript.language_typescript())
-        self._languages["typescript"] = ts_lang
-        self._parsers["typescript"] = Parser(ts_lang)
-
-        # JavaScript
-        js_lang = Language(tree_sitter_javascript.language())
-        self._languages["javascript"] = js_lang
-        self._parsers["javascript"] = Parser(js_lang)
-
-        # Rust
-        rust_lang = Language(tree_sitter_rust.language())
-        self._languages["rust"] = rust_lang
-        self._parsers["rust"] = Parser(rust_lang)
-
-        # Swift
-        swift_lang = Language(tree_sitter_swift.language())
-        self._languages["swift"] = swift_lang
-        self._parsers["swift"] = Parser(swift_lang)
-
-        # Java
-        java_lang = Language(tree_sitter_java.language())
-        self._languages["java"] = java_lang
-        self._parsers["java"] = Parser(java_lang)
-
-        # C++
-        cpp_lang = Language(tree_sitter_cpp.language())
-        self._languages["cpp"] = cpp_lang
-        self._parsers["cpp"] = Parser(cpp_lang)
-        # C uses same parser as C++
-        self._languages["c"] = cpp_lang
-        self._parsers["c"] = Parser(cpp_lang)
-
-        # SQL
-        sql_lang = Language(tree_sitter_sql.language())
-        self._languages["sql"] = sql_lang
-        self._parsers["sql"] = Parser(sql_lang)
+        self._specs: dict[str, LanguageSpec] = dict(get_languages())
+
+        # Grammar and parser per language (None when the grammar failed to
+        # load), and the definition query patterns that compiled
+        self._grammars: dict[str, Language | None] = {}
+        self._parsers: dict[str, Parser | None] = {}
+        self._queries: dict[str, list[Query]] = {}
+
+        # Language hooks used alongside the definition queries
+        self._module_resolvers: dict[str, ModuleResolver] = {
+            "rust": self._resolve_rust_module_path,
+        }
+        self._module_extractors: dict[str, ModuleExtractor] = {
+            "python": self._extract_python_module,
+            "rust": self._extract_rust_module,
+        }
+        self._finishers: dict[str, UnitFinisher] = {
+            "python": self._finish_python_unit,
+            "rust": self._finish_rust_unit,
+            "csharp": self._finish_csharp_unit,
+            "ruby": self._finish_ruby_unit,
+        }
+
+        # Cargo package lookups for Rust module paths, keyed by directory
+        self._cargo_crates: dict[str, CargoCrate | None] = {}
+
+        # Markdown and config files are split without a grammar
+        self._documents = DocumentParser()
 
     def supported_languages(self) -> list[str]:
-        """Return list of supported language identifiers."""
-        return list(self._parsers.keys())
+        """Return list of supported language identifiers.
+
+        Only languages whose grammar package is installed are listed,
+        followed by the document formats.
+        """
+        languages = [
+            name
+            for name, spec in self._specs.items()
+            if importlib.util.find_spec(spec.package) is not None
+        ]
+        return languages + self._documents.supported_languages()
 
     def detect_language(self, path: str) -> str | None:
         """Detect language from file extension."""
         ext = Path(path).suffix.lower()
         return EXTENSION_MAP.get(ext)
 
+    def _get_grammar(self, language: str) -> Language | None:
+        """Get a language's grammar, loading it on first use.
+
+        Returns None (logging a warning once) if the grammar package is
+        missing or incompatible with the installed tree-sitter.
+        """
+        if language not in self._grammars:
+            spec = self._specs[language]
+            try:
+                self._grammars[language] = Language(spec.load_grammar())
+            except Exception as e:
+                logger.warning(
+                    "grammar_unavailable",
+                    language=language,
+                    package=spec.package.replace("_", "-"),
+                    error=str(e),
+                )
+                self._grammars[language] = None
+        return self._grammars[language]
+
+    def _get_parser(self, language: str) -> Parser | None:
+        """Get the parser for a language, or None if its grammar is missing."""
+        if language not in self._parsers:
+            grammar = self._get_grammar(language)
+            self._parsers[language] = Parser(grammar) if grammar else None
+        return self._parsers[language]
+
+    def _get_queries(self, language: str) -> list[Query]:
+        """Get a language's compiled definition query.
+
+        A query that fails to compile (e.g. naming a node type the installed
+        grammar version lacks) is compiled pattern by pattern instead, so a
+        bad pattern only loses its own definitions.
+        """
+        if language not in self._queries:
+            source = self._specs[language].query
+            grammar = self._get_grammar(language)
+            queries: list[Query] = []
+            if source and grammar:
+                try:
+                    queries.append(Query(grammar, source))
+                except Exception:
+                    for pattern in split_patterns(source):
+                        try:
+                            queries.append(Query(grammar, pattern))
+                        except Exception as e:
+                            logger.warning(
+                                "query_pattern_invalid",
+                                language=language,
+                                pattern=pattern.split("\n")[0],
+                                error=str(e),
+                            )
+            self._queries[language] = queries
+        return self._queries[language]
+
     async def parse_file(self, path: str) -> list[SemanticUnit]:
         """Parse a file and extract semantic units.
 
@@ -209,212 +228,287 @@ class TreeSitterParser(CodeParser):
             logger.debug("unsupported_language", path=path)
             return []
 
-        # Read file with binary detection
-        try:
-            # Detect binary files (null bytes in first 8KB)
-            with open(path, "rb") as f:
-                sample = f.read(8192)
-                if b"\x00" in sample:
-                    return []  # Skip silently
-
-            # Read as UTF-8
-            with open(path, encoding="utf-8") as f:
-                source = f.read()
-        except UnicodeDecodeError:
-            raise ParseError("encoding_error", "Not valid UTF-8", path)
-        except OSError as e:
-            raise ParseError("io_error", str(e), path)
+        source = read_source(path)
+        if source is None:
+            return []  # Skip binary files silently
 
-        # Parse with tree-sitter
-        parser = self._parsers[language]
-        tree = parser.parse(bytes(source, "utf8"))
+        if language in DOCUMENT_LANGUAGES:
+            return self._documents.extract_units(source, path, language)
 
-        # Extract units based on language
-        units: list[SemanticUnit] = []
-        if language == "python":
-            units = self._extract_python_units(tree.root_node, source, path)
-        elif language in ("typescript", "javascript"):
-            units = self._extract_ts_js_units(tree.root_node, source, path, language)
-        elif language == "lua":
-            units = self._extract_lua_units(tree.root_node, source, path)
-        elif language == "rust":
-            units = self._extract_rust_units(tree.root_node, source, path)
-        elif language == "swift":
-            units = self._extract_swift_units(tree.root_node, source, path)
-        elif language == "java":
-            units = self._extract_java_units(tree.root_node, source, path)
-        elif language in ("c", "cpp"):
-            units = self._extract_c_cpp_units(tree.root_node, source, path, language)
-        elif language == "sql":
-            units = self._extract_sql_units(tree.root_node, source, path)
+        # .h is shared by C and C++; parse C++ headers with the C++ grammar
+        if language == "c" and Path(path).suffix.lower() == ".h":
+            if _CPP_HEADER_RE.search(source):
+                language = "cpp"
 
-        return units
+        # Parse with tree-sitter
+        parser = self._get_parser(language)
+        if parser is None:
+            return []
+        tree = parser.parse(bytes(source, "utf8"))
 
-    def _extract_python_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract Python functions, classes, methods, module docstrings, constants."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        # Extract module docstring
-        if root.child_count > 0:
-            first_child = root.children[0]
-            if first_child.type == "expression_statement":
-                expr = first_child.children[0] if first_child.child_count > 0 else None
-                if expr and expr.type == "string":
-                    text = self._extract_text(expr, source)
-                    docstring = text.strip('"""').strip("'''").strip()
-                    units.append(
-                        SemanticUnit(
-                            name=module_name,
-                            qualified_name=module_name,
-                            unit_type=UnitType.MODULE,
-                            signature=f"# Module: {module_name}",
-                            content=docstring,
-                            file_path=file_path,
-                            start_line=expr.start_point[0] + 1,
-                            end_line=expr.end_point[0] + 1,
-                            language="python",
-                            docstring=docstring,
-                            complexity=None,
-                        )
-                    )
+        units = self._extract_query_units(tree.root_node, source, path, language)
 
-        # Extract classes and functions
-        for node in root.children:
-            if node.type == "class_definition":
-                units.extend(
-                    self._extract_python_class(node, source, file_path, module_name)
-                )
-            elif node.type == "function_definition":
-                unit = self._extract_python_function(
-                    node, source, file_path, module_name, None
-                )
-                if unit:
-                    units.append(unit)
-            elif node.type == "assignment":
-                # Extract module-level constants (UPPER_CASE)
-                target = node.child_by_field_name("left")
-                if target and target.type == "identifier":
-                    name = self._extract_text(target, source)
-                    if name.isupper() and "_" in name:
-                        text = self._extract_text(node, source)
-                        units.append(
-                            SemanticUnit(
-                                name=name,
-                                qualified_name=f"{module_name}.{name}",
-                                unit_type=UnitType.CONSTANT,
-                                signature=text.split("\n")[0],
-                                content=text,
-                                file_path=file_path,
-                                start_line=node.start_point[0] + 1,
-                                end_line=node.end_point[0] + 1,
-                                language="python",
-                                docstring=None,
-                                complexity=None,
-                            )
-                        )
+        if self._specs[language].call_types:
+            self._attach_calls(units, tree.root_node, source, language)
 
         return units
 
-    def _extract_python_class(
-        self, node: Node, source: str, file_path: str, module_name: str
+    def _attach_calls(
+        self, units: list[SemanticUnit], root: Node, source: str, language: str
+    ) -> None:
+        """Record call sites on every enclosing function or method.
+
+        A function's calls include those of the functions nested in it, so
+        its callees are the same whether or not they are split into units.
+        Callee names are kept as written (e.g. ``self.save``, ``Point::new``);
+        resolving them to qualified names happens at index time.
+        """
+        callables = [
+            u
+            for u in units
+            if u.unit_type in (UnitType.FUNCTION, UnitType.METHOD)
+        ]
+        if not callables:
+            return
+
+        for line, callee in self._find_call_sites(root, source, language):
+            for owner in callables:
+                if owner.start_line <= line <= owner.end_line:
+                    if callee not in owner.calls:
+                        owner.calls.append(callee)
+
+    def _find_call_sites(
+        self, root: Node, source: str, language: str
+    ) -> list[tuple[int, str]]:
+        """Find (line, callee text) for every call expression under root."""
+        spec = self._specs[language]
+        call_types = spec.call_types
+        receiver_field = spec.receiver_field
+        sites: list[tuple[int, str]] = []
+        stack = [root]
+        while stack:
+            node = stack.pop()
+            field = call_types.get(node.type)
+            if field:
+                callee_node = node.child_by_field_name(field)
+                if callee_node:
+                    callee = self._extract_text(callee_node, source)
+                    if receiver_field and field in ("name", "method"):
+                        # Qualify method calls with their receiver
+                        receiver = node.child_by_field_name(receiver_field)
+                        if receiver:
+                            receiver_text = self._extract_text(receiver, source)
+                            callee = f"{receiver_text}.{callee}"
+                    sites.append((node.start_point[0] + 1, "".join(callee.split())))
+            stack.extend(reversed(node.children))
+        return sites
+
+    def _extract_query_units(
+        self, root: Node, source: str, file_path: str, language: str
     ) -> list[SemanticUnit]:
-        """Extract a Python class and its methods."""
-        units: list[SemanticUnit] = []
+        """Extract units with a language's definition query.
 
-        # Get class name
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return units
+        Definitions are matched at any depth. Each is qualified by the
+        module prefix and the names of the definitions and scopes enclosing
+        it; a function whose nearest enclosing definition is a class-like
+        type or ``@scope.type`` becomes a method.
+        """
+        queries = self._get_queries(language)
+        if not queries:
+            return []
 
-        class_name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{class_name}"
+        # Best match per node; optional @doc captures can make the same
+        # definition match more than once
+        package: str | None = None
+        matches: dict[tuple[int, int, bool], _Match] = {}
+        for query in queries:
+            for _, captures in QueryCursor(query).matches(root):
+                if package is None and captures.get("package"):
+                    package_text = self._extract_text(captures["package"][0], source)
+                    package = "".join(package_text.split())
+                match = self._read_match(captures, source)
+                if match is None:
+                    continue
+                key = (match.node.start_byte, match.node.end_byte, match.is_scope)
+                best = matches.get(key)
+                if best is None or len(match.docs) > len(best.docs):
+                    matches[key] = match
 
-        # Extract class docstring
-        docstring = self._extract_python_docstring(node, source)
+        prefix = self._module_prefix(language, file_path, package)
+        units: list[SemanticUnit] = []
+        module_extractor = self._module_extractors.get(language)
+        if module_extractor and prefix:
+            unit = module_extractor(root, source, file_path, prefix)
+            if unit:
+                units.append(unit)
+
+        # Outermost first, so each match's enclosing matches are on the stack
+        enclosing: list[_Match] = []
+        for match in sorted(
+            matches.values(), key=lambda m: (m.node.start_byte, -m.node.end_byte)
+        ):
+            while enclosing and enclosing[-1].node.end_byte < match.node.end_byte:
+                enclosing.pop()
+            if not match.is_scope:
+                units.append(
+                    self._match_unit(
+                        match, enclosing, prefix, source, file_path, language
+                    )
+                )
+            enclosing.append(match)
 
-        # Get class signature (first line)
-        signature = self._extract_text(node, source).split("\n")[0]
+        return units
 
-        # Get class body
-        body = node.child_by_field_name("body")
+    def _read_match(
+        self, captures: dict[str, list[Node]], source: str
+    ) -> _Match | None:
+        """Read a definition or scope from a query match's captures."""
+        kind = next(
+            (c for c in captures if c.startswith(("definition.", "scope"))), None
+        )
+        if kind is None:
+            return None
 
-        units.append(
-            SemanticUnit(
-                name=class_name,
-                qualified_name=qualified_name,
-                unit_type=UnitType.CLASS,
-                signature=signature,
-                content=self._extract_text(node, source),
-                file_path=file_path,
-                start_line=node.start_point[0] + 1,
-                end_line=node.end_point[0] + 1,
-                language="python",
-                docstring=docstring if docstring else None,
-                complexity=None,
-            )
+        node = captures[kind][0]
+        name_nodes = captures.get("name")
+        name = self._extract_text(name_nodes[0], source) if name_nodes else None
+        if kind.startswith("definition."):
+            kind = kind.removeprefix("definition.")
+            if kind not in DEFINITION_KINDS or not name:
+                return None
+        elif name:
+            # impl<T> Foo<T> scopes its methods as Foo, not Foo<T>
+            name = _strip_generic_args(name)
+            if captures.get("trait"):
+                trait = self._extract_text(captures["trait"][0], source)
+                name = f"<{name} as {_strip_generic_args(trait)}>"
+
+        receiver = None
+        if captures.get("receiver"):
+            # Strip pointers and type parameters, e.g. *Stack[T] -> Stack
+            receiver_text = self._extract_text(captures["receiver"][0], source)
+            receiver = receiver_text.lstrip("*&").split("[")[0].strip()
+
+        return _Match(
+            node=node,
+            kind=kind,
+            name=name,
+            docs=captures.get("doc", []),
+            receiver=receiver or None,
         )
 
-        # Extract methods
-        if body:
-            for child in body.children:
-                if child.type == "function_definition":
-                    method = self._extract_python_function(
-                        child, source, file_path, module_name, class_name
-                    )
-                    if method:
-                        units.append(method)
+    def _module_prefix(
+        self, language: str, file_path: str, package: str | None
+    ) -> str | None:
+        """Get the start of a file's qualified names.
 
-        return units
+        Uses the language's module path resolver if it has one, then the
+        captured ``@package``, then the file stem.
+        """
+        resolver = self._module_resolvers.get(language)
+        if resolver:
+            return resolver(file_path)
+        if package:
+            return package
+        if self._specs[language].qualify_with_file:
+            return Path(file_path).stem
+        return None
 
-    def _extract_python_function(
+    def _match_unit(
         self,
-        node: Node,
+        match: _Match,
+        enclosing: list[_Match],
+        prefix: str | None,
         source: str,
         file_path: str,
-        module_name: str,
-        class_name: str | None,
-    ) -> SemanticUnit | None:
-        """Extract a Python function or method."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-
-        # Build qualified name
-        if class_name:
-            qualified_name = f"{module_name}.{class_name}.{name}"
+        language: str,
+    ) -> SemanticUnit:
+        """Build the unit for a definition inside ``enclosing`` matches."""
+        spec = self._specs[language]
+        node = match.node
+
+        # Names written as paths (M.add, Vector::norm) add their own parts
+        name_parts = [p.strip() for p in _NAME_PATH_RE.split(match.name or "")]
+        name_parts = [p for p in name_parts if p] or [match.name or ""]
+        parts = [prefix] if prefix else []
+        parts.extend(m.name for m in enclosing if m.name)
+        if match.receiver:
+            parts.append(match.receiver)
+        parts.extend(name_parts)
+
+        unit_type = DEFINITION_KINDS[match.kind]
+        if unit_type == UnitType.FUNCTION and (
+            match.receiver or (enclosing and enclosing[-1].is_type)
+        ):
             unit_type = UnitType.METHOD
-        else:
-            qualified_name = f"{module_name}.{name}"
-            unit_type = UnitType.FUNCTION
-
-        # Get signature (first line)
-        signature = self._extract_text(node, source).split("\n")[0]
-
-        # Extract docstring
-        docstring = self._extract_python_docstring(node, source)
 
-        # Compute complexity
-        complexity = self._compute_complexity(node, "python")
+        if match.docs:
+            docstring = "\n".join(
+                self._clean_comment(self._extract_text(doc, source))
+                for doc in match.docs
+            ).strip()
+        elif spec.doc_comment == "/**":
+            docstring = self._extract_block_doc(node, source)
+        elif spec.doc_comment:
+            docstring = self._extract_line_comment_doc(node, source, spec.doc_comment)
+        else:
+            docstring = None
+        callable_unit = unit_type in (UnitType.FUNCTION, UnitType.METHOD)
 
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
+        unit = SemanticUnit(
+            name=name_parts[-1],
+            qualified_name=spec.separator.join(parts),
             unit_type=unit_type,
-            signature=signature,
+            signature=self._extract_text(node, source).split("\n")[0],
             content=self._extract_text(node, source),
             file_path=file_path,
             start_line=node.start_point[0] + 1,
             end_line=node.end_point[0] + 1,
-            language="python",
+            language=language,
             docstring=docstring if docstring else None,
-            complexity=complexity,
+            complexity=(
+                self._compute_complexity(node, language) if callable_unit else None
+            ),
+        )
+
+        finisher = self._finishers.get(language)
+        if finisher:
+            finisher(unit, node, source)
+        return unit
+
+    def _extract_python_module(
+        self, root: Node, source: str, file_path: str, module_name: str
+    ) -> SemanticUnit | None:
+        """Extract a Python module docstring as a module unit."""
+        if root.child_count == 0:
+            return None
+        first_child = root.children[0]
+        if first_child.type != "expression_statement":
+            return None
+        expr = first_child.children[0] if first_child.child_count > 0 else None
+        if not expr or expr.type != "string":
+            return None
+
+        text = self._extract_text(expr, source)
+        docstring = text.strip('"""').strip("'''").strip()
+        return SemanticUnit(
+            name=module_name,
+            qualified_name=module_name,
+            unit_type=UnitType.MODULE,
+            signature=f"# Module: {module_name}",
+            content=docstring,
+            file_path=file_path,
+            start_line=expr.start_point[0] + 1,
+            end_line=expr.end_point[0] + 1,
+            language="python",
+            docstring=docstring,
+            complexity=None,
         )
 
+    def _finish_python_unit(self, unit: SemanticUnit, node: Node, source: str) -> None:
+        """Set a Python unit's docstring from its body."""
+        unit.docstring = self._extract_python_docstring(node, source) or None
+
     def _extract_python_docstring(self, node: Node, source: str) -> str | None:
         """Extract Python docstring from function/class body."""
         body = node.child_by_field_name("body")
@@ -438,684 +532,198 @@ class TreeSitterParser(CodeParser):
                 return text.strip()
         return None
 
-    def _extract_ts_js_units(
-        self, root: Node, source: str, file_path: str, language: str
-    ) -> list[SemanticUnit]:
-        """Extract TypeScript/JavaScript units."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            # Handle export statements that wrap declarations
-            actual_node = node
-            if node.type == "export_statement":
-                # Get the declaration inside the export
-                declaration = node.child_by_field_name("declaration")
-                if declaration:
-                    actual_node = declaration
-                else:
-                    continue
-
-            if actual_node.type in ("function_declaration", "arrow_function"):
-                unit = self._extract_ts_js_function(
-                    actual_node, source, file_path, module_name, None, language
-                )
-                if unit:
-                    units.append(unit)
-            elif actual_node.type == "class_declaration":
-                units.extend(
-                    self._extract_ts_js_class(
-                        actual_node, source, file_path, module_name, language
-                    )
-                )
-            elif (
-                actual_node.type == "interface_declaration"
-                and language == "typescript"
-            ):
-                unit = self._extract_ts_interface(
-                    actual_node, source, file_path, module_name
-                )
-                if unit:
-                    units.append(unit)
-
-        return units
-
-    def _extract_ts_js_class(
-        self, node: Node, source: str, file_path: str, module_name: str, language: str
-    ) -> list[SemanticUnit]:
-        """Extract a TypeScript/JavaScript class and its methods."""
-        units: list[SemanticUnit] = []
-
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return units
-
-        class_name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{class_name}"
-
-        # Extract JSDoc
-        docstring = self._extract_jsdoc(node, source)
-
-        signature = self._extract_text(node, source).split("\n")[0]
-
-        units.append(
-            SemanticUnit(
-                name=class_name,
-                qualified_name=qualified_name,
-                unit_type=UnitType.CLASS,
-                signature=signature,
-                content=self._extract_text(node, source),
-                file_path=file_path,
-                start_line=node.start_point[0] + 1,
-                end_line=node.end_point[0] + 1,
-                language=language,
-                docstring=docstring if docstring else None,
-                complexity=None,
-            )
-        )
-
-        # Extract methods
-        body = node.child_by_field_name("body")
-        if body:
-            for child in body.children:
-                if child.type == "method_definition":
-                    method = self._extract_ts_js_function(
-                        child, source, file_path, module_name, class_name, language
-                    )
-                    if method:
-                        units.append(method)
-
-        return units
-
-    def _extract_ts_js_function(
-        self,
-        node: Node,
-        source: str,
-        file_path: str,
-        module_name: str,
-        class_name: str | None,
-        language: str,
-    ) -> SemanticUnit | None:
-        """Extract a TypeScript/JavaScript function or method."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-
-        if class_name:
-            qualified_name = f"{module_name}.{class_name}.{name}"
-            unit_type = UnitType.METHOD
-        else:
-            qualified_name = f"{module_name}.{name}"
-            unit_type = UnitType.FUNCTION
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        docstring = self._extract_jsdoc(node, source)
-        complexity = self._compute_complexity(node, language)
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=unit_type,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language=language,
-            docstring=docstring if docstring else None,
-            complexity=complexity,
-        )
-
-    def _extract_ts_interface(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> SemanticUnit | None:
-        """Extract a TypeScript interface."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        docstring = self._extract_jsdoc(node, source)
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.CLASS,  # Treat interfaces like classes
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="typescript",
-            docstring=docstring if docstring else None,
-            complexity=None,
-        )
-
-    def _extract_jsdoc(self, node: Node, source: str) -> str | None:
-        """Extract JSDoc comment preceding a node."""
-        # Look for comment node before this node
-        if node.prev_sibling and node.prev_sibling.type == "comment":
-            comment = self._extract_text(node.prev_sibling, source)
-            if comment.startswith("/**") and comment.endswith("*/"):
-                # Remove /** and */
-                return comment[3:-2].strip()
-        return None
-
-    def _extract_lua_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract Lua functions."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            if node.type in ("function_declaration", "local_function"):
-                unit = self._extract_lua_function(node, source, file_path, module_name)
-                if unit:
-                    units.append(unit)
-
-        return units
-
-    def _extract_lua_function(
-        self, node: Node, source: str, file_path: str, module_name: str
+    def _extract_rust_module(
+        self, root: Node, source: str, file_path: str, module_name: str
     ) -> SemanticUnit | None:
-        """Extract a Lua function."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        complexity = self._compute_complexity(node, "lua")
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.FUNCTION,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="lua",
-            docstring=None,
-            complexity=complexity,
-        )
-
-    def _extract_rust_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract Rust functions, structs, enums, impl blocks, traits."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
+        """Extract inner doc comments (//! or /*! */) as a module unit."""
+        module_doc_nodes: list[Node] = []
         for node in root.children:
-            if node.type == "function_item":
-                unit = self._extract_rust_function(node, source, file_path, module_name)
-                if unit:
-                    units.append(unit)
-            elif node.type in ("struct_item", "enum_item", "trait_item"):
-                unit = self._extract_rust_type(node, source, file_path, module_name)
-                if unit:
-                    units.append(unit)
-
-        return units
-
-    def _extract_rust_function(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> SemanticUnit | None:
-        """Extract a Rust function."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}::{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        complexity = self._compute_complexity(node, "rust")
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.FUNCTION,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="rust",
-            docstring=None,
-            complexity=complexity,
-        )
-
-    def _extract_rust_type(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> SemanticUnit | None:
-        """Extract a Rust struct, enum, or trait."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
+            if node.type not in ("line_comment", "block_comment"):
+                break
+            text = self._extract_text(node, source)
+            if text.startswith(("//!", "/*!")):
+                module_doc_nodes.append(node)
+        if not module_doc_nodes:
             return None
 
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}::{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-
+        docstring = "\n".join(
+            self._clean_rust_doc_comment(self._extract_text(n, source))
+            for n in module_doc_nodes
+        ).strip()
         return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.CLASS,
-            signature=signature,
-            content=self._extract_text(node, source),
+            name=module_name.rsplit("::", 1)[-1],
+            qualified_name=module_name,
+            unit_type=UnitType.MODULE,
+            signature=f"// Module: {module_name}",
+            content=docstring,
             file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
+            start_line=module_doc_nodes[0].start_point[0] + 1,
+            end_line=module_doc_nodes[-1].end_point[0] + 1,
             language="rust",
-            docstring=None,
+            docstring=docstring,
             complexity=None,
         )
 
-    def _extract_swift_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract Swift functions, classes, structs, enums, protocols."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            if node.type == "function_declaration":
-                unit = self._extract_swift_function(
-                    node, source, file_path, module_name, None
-                )
-                if unit:
-                    units.append(unit)
-            elif node.type in (
-                "class_declaration",
-                "struct_declaration",
-                "enum_declaration",
-                "protocol_declaration",
-            ):
-                units.extend(
-                    self._extract_swift_type(node, source, file_path, module_name)
-                )
-
-        return units
+    def _resolve_rust_module_path(self, file_path: str) -> str:
+        """Resolve the crate module path for a Rust file.
 
-    def _extract_swift_type(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> list[SemanticUnit]:
-        """Extract a Swift class/struct/enum/protocol and its methods."""
-        units: list[SemanticUnit] = []
-
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return units
-
-        type_name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{type_name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-
-        units.append(
-            SemanticUnit(
-                name=type_name,
-                qualified_name=qualified_name,
-                unit_type=UnitType.CLASS,
-                signature=signature,
-                content=self._extract_text(node, source),
-                file_path=file_path,
-                start_line=node.start_point[0] + 1,
-                end_line=node.end_point[0] + 1,
-                language="swift",
-                docstring=None,
-                complexity=None,
-            )
+        Cargo lookups are cached per directory since every file in a crate
+        resolves to the same manifest.
+        """
+        directory = str(Path(file_path).parent)
+        if directory not in self._cargo_crates:
+            self._cargo_crates[directory] = find_crate(file_path)
+
+        crate = self._cargo_crates[directory]
+        if crate is None:
+            return Path(file_path).stem
+        return rust_module_path(file_path, crate)
+
+    def _finish_rust_unit(self, unit: SemanticUnit, node: Node, source: str) -> None:
+        """Set a Rust unit's doc comments, attributes and visibility."""
+        unit.docstring, unit.attributes = self._extract_rust_doc_and_attributes(
+            node, source
         )
-
-        # Extract methods
+        unit.visibility = self._extract_rust_visibility(node, source)
+
+        # Trait impl methods take the trait's visibility
+        impl = node.parent.parent if node.parent else None
+        if impl and impl.type == "impl_item" and impl.child_by_field_name("trait"):
+            unit.visibility = None
+
+    def _extract_rust_doc_and_attributes(
+        self, node: Node, source: str
+    ) -> tuple[str | None, list[str]]:
+        """Collect outer doc comments and attributes preceding a Rust item.
+
+        Walks backwards over sibling comments and ``#[...]`` attributes.
+        Only ``///`` and ``/** */`` comments count as documentation; plain
+        comments are skipped. Attributes are returned without the ``#[ ]``
+        wrapper (e.g. ``derive(Debug, Clone)``), in source order.
+        """
+        doc_lines: list[str] = []
+        attributes: list[str] = []
+
+        sibling = node.prev_sibling
+        while sibling and sibling.type in (
+            "line_comment",
+            "block_comment",
+            "attribute_item",
+        ):
+            text = self._extract_text(sibling, source).strip()
+            if sibling.type == "attribute_item":
+                attributes.insert(0, text[2:-1].strip())
+            elif self._is_rust_outer_doc_comment(text):
+                doc_lines.insert(0, self._clean_rust_doc_comment(text))
+            sibling = sibling.prev_sibling
+
+        docstring = "\n".join(doc_lines).strip()
+        return (docstring if docstring else None), attributes
+
+    def _is_rust_outer_doc_comment(self, text: str) -> bool:
+        """Check whether a comment is an outer doc comment (/// or /** */)."""
+        if text.startswith("///"):
+            return not text.startswith("////")
+        if text.startswith("/**"):
+            return not text.startswith("/***") and text != "/**/"
+        return False
+
+    def _clean_rust_doc_comment(self, text: str) -> str:
+        """Strip comment markers from a Rust doc comment."""
+        text = text.strip()
+        if text.startswith(("/**", "/*!")):
+            lines = text[3:-2].strip().split("\n")
+            return "\n".join(line.strip().lstrip("*").strip() for line in lines)
+        # Line doc comment: /// or //!
+        text = text[3:]
+        return text[1:] if text.startswith(" ") else text
+
+    def _extract_rust_visibility(self, node: Node, source: str) -> str:
+        """Extract Rust visibility (e.g. "pub", "pub(crate)"), or "private"."""
         for child in node.children:
-            if child.type == "function_declaration":
-                method = self._extract_swift_function(
-                    child, source, file_path, module_name, type_name
-                )
-                if method:
-                    units.append(method)
-
-        return units
-
-    def _extract_swift_function(
-        self,
-        node: Node,
-        source: str,
-        file_path: str,
-        module_name: str,
-        class_name: str | None,
-    ) -> SemanticUnit | None:
-        """Extract a Swift function or method."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-
-        if class_name:
-            qualified_name = f"{module_name}.{class_name}.{name}"
-            unit_type = UnitType.METHOD
-        else:
-            qualified_name = f"{module_name}.{name}"
-            unit_type = UnitType.FUNCTION
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        complexity = self._compute_complexity(node, "swift")
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=unit_type,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="swift",
-            docstring=None,
-            complexity=complexity,
-        )
-
-    def _extract_java_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract Java classes, interfaces, enums, methods."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            if node.type in (
-                "class_declaration",
-                "interface_declaration",
-                "enum_declaration",
-            ):
-                units.extend(
-                    self._extract_java_type(node, source, file_path, module_name)
-                )
-
-        return units
-
-    def _extract_java_type(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> list[SemanticUnit]:
-        """Extract a Java class/interface/enum and its methods."""
-        units: list[SemanticUnit] = []
-
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return units
-
-        type_name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{type_name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        docstring = self._extract_javadoc(node, source)
-
-        units.append(
-            SemanticUnit(
-                name=type_name,
-                qualified_name=qualified_name,
-                unit_type=UnitType.CLASS,
-                signature=signature,
-                content=self._extract_text(node, source),
-                file_path=file_path,
-                start_line=node.start_point[0] + 1,
-                end_line=node.end_point[0] + 1,
-                language="java",
-                docstring=docstring if docstring else None,
-                complexity=None,
-            )
+            if child.type == "visibility_modifier":
+                return "".join(self._extract_text(child, source).split())
+        return "private"
+
+    def _finish_csharp_unit(self, unit: SemanticUnit, node: Node, source: str) -> None:
+        """Strip the XML tags from a C# unit's doc comment."""
+        if unit.docstring:
+            lines = _XML_TAG_RE.sub("", unit.docstring).split("\n")
+            text = "\n".join(line.strip() for line in lines).strip()
+            unit.docstring = text if text else None
+
+    def _finish_ruby_unit(self, unit: SemanticUnit, node: Node, source: str) -> None:
+        """Join a Ruby method to its owner with ``.`` (``Outer::Inner.method``).
+
+        Classes and modules keep their ``::`` path; top-level methods are
+        qualified with the file stem instead.
+        """
+        if unit.unit_type in (UnitType.FUNCTION, UnitType.METHOD):
+            owner, _, name = unit.qualified_name.rpartition("::")
+            unit.qualified_name = f"{owner or Path(unit.file_path).stem}.{name}"
+
+    def _clean_comment(self, text: str) -> str:
+        """Strip comment markers from each line of a comment."""
+        lines = (
+            _COMMENT_MARKER_RE.sub("", line).removesuffix("*/").rstrip()
+            for line in text.split("\n")
         )
+        return "\n".join(lines).strip()
 
-        # Extract methods and constructors
-        body = node.child_by_field_name("body")
-        if body:
-            for child in body.children:
-                if child.type in ("method_declaration", "constructor_declaration"):
-                    method = self._extract_java_method(
-                        child, source, file_path, module_name, type_name
-                    )
-                    if method:
-                        units.append(method)
-
-        return units
-
-    def _extract_java_method(
-        self, node: Node, source: str, file_path: str, module_name: str, class_name: str
-    ) -> SemanticUnit | None:
-        """Extract a Java method or constructor."""
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{class_name}.{name}"
+    def _preceding_sibling(self, node: Node) -> Node | None:
+        """Get the named node before ``node``, looking past its parent's start.
 
-        signature = self._extract_text(node, source).split("\n")[0]
-        docstring = self._extract_javadoc(node, source)
-        complexity = self._compute_complexity(node, "java")
+        Some grammars attach a comment before a body's first statement to
+        the enclosing node rather than the body (e.g. Ruby's class bodies),
+        and a wrapper such as TypeScript's ``export`` comes between a
+        declaration and its comment.
+        """
+        if node.prev_named_sibling is None and node.parent is not None:
+            return node.parent.prev_named_sibling
+        return node.prev_named_sibling
 
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.METHOD,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="java",
-            docstring=docstring if docstring else None,
-            complexity=complexity,
-        )
+    def _extract_line_comment_doc(
+        self, node: Node, source: str, prefix: str
+    ) -> str | None:
+        """Collect the line comments directly above a node as its docstring.
 
-    def _extract_javadoc(self, node: Node, source: str) -> str | None:
-        """Extract Javadoc comment preceding a node."""
-        if node.prev_sibling and node.prev_sibling.type == "comment":
-            comment = self._extract_text(node.prev_sibling, source)
+        Only comments starting with ``prefix`` on consecutive lines count.
+        The prefix, repeats of its last character (as in ``---``) and one
+        following space are removed.
+        """
+        lines: list[str] = []
+        row = node.start_point[0]
+        sibling = self._preceding_sibling(node)
+        while (
+            sibling
+            and sibling.type in COMMENT_TYPES
+            and row - sibling.end_point[0] <= 1
+        ):
+            text = self._extract_text(sibling, source).strip()
+            if not text.startswith(prefix):
+                break
+            text = text[len(prefix) :].lstrip(prefix[-1])
+            lines.insert(0, text[1:] if text.startswith(" ") else text)
+            row = sibling.start_point[0]
+            sibling = sibling.prev_named_sibling
+
+        docstring = "\n".join(lines).strip()
+        return docstring if docstring else None
+
+    def _extract_block_doc(self, node: Node, source: str) -> str | None:
+        """Extract a ``/** */`` doc comment (e.g. Javadoc) preceding a node."""
+        sibling = self._preceding_sibling(node)
+        if sibling and sibling.type in COMMENT_TYPES:
+            comment = self._extract_text(sibling, source)
             if comment.startswith("/**") and comment.endswith("*/"):
-                return comment[3:-2].strip()
-        return None
-
-    def _extract_c_cpp_units(
-        self, root: Node, source: str, file_path: str, language: str
-    ) -> list[SemanticUnit]:
-        """Extract C/C++ functions, classes, structs."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            if node.type == "function_definition":
-                unit = self._extract_c_cpp_function(
-                    node, source, file_path, module_name, language
-                )
-                if unit:
-                    units.append(unit)
-            elif (
-                node.type in ("class_specifier", "struct_specifier")
-                and language == "cpp"
-            ):
-                units.extend(
-                    self._extract_cpp_class(node, source, file_path, module_name)
-                )
-
-        return units
-
-    def _extract_c_cpp_function(
-        self, node: Node, source: str, file_path: str, module_name: str, language: str
-    ) -> SemanticUnit | None:
-        """Extract a C/C++ function."""
-        declarator = node.child_by_field_name("declarator")
-        if not declarator:
-            return None
-
-        # Get function name from declarator
-        name = self._extract_function_name_from_declarator(declarator, source)
-        if not name:
-            return None
-
-        qualified_name = f"{module_name}.{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-        complexity = self._compute_complexity(node, language)
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=UnitType.FUNCTION,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language=language,
-            docstring=None,
-            complexity=complexity,
-        )
-
-    def _extract_function_name_from_declarator(
-        self, declarator: Node, source: str
-    ) -> str | None:
-        """Extract function name from C/C++ declarator."""
-        # Handle different declarator types
-        if declarator.type == "function_declarator":
-            child_declarator = declarator.child_by_field_name("declarator")
-            if child_declarator:
-                return self._extract_function_name_from_declarator(
-                    child_declarator, source
-                )
-        elif declarator.type == "identifier":
-            return self._extract_text(declarator, source)
+                docstring = self._clean_comment(comment)
+                return docstring if docstring else None
         return None
 
-    def _extract_cpp_class(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> list[SemanticUnit]:
-        """Extract a C++ class and its methods."""
-        units: list[SemanticUnit] = []
-
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            return units
-
-        class_name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}::{class_name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-
-        units.append(
-            SemanticUnit(
-                name=class_name,
-                qualified_name=qualified_name,
-                unit_type=UnitType.CLASS,
-                signature=signature,
-                content=self._extract_text(node, source),
-                file_path=file_path,
-                start_line=node.start_point[0] + 1,
-                end_line=node.end_point[0] + 1,
-                language="cpp",
-                docstring=None,
-                complexity=None,
-            )
-        )
-
-        # Extract methods
-        body = node.child_by_field_name("body")
-        if body:
-            for child in body.children:
-                if child.type == "function_definition":
-                    method = self._extract_c_cpp_function(
-                        child, source, file_path, module_name, "cpp"
-                    )
-                    if method:
-                        # Update qualified name to include class
-                        qname = f"{module_name}::{class_name}::{method.name}"
-                        method.qualified_name = qname
-                        method.unit_type = UnitType.METHOD
-                        units.append(method)
-
-        return units
-
-    def _extract_sql_units(
-        self, root: Node, source: str, file_path: str
-    ) -> list[SemanticUnit]:
-        """Extract SQL CREATE statements."""
-        units: list[SemanticUnit] = []
-        module_name = Path(file_path).stem
-
-        for node in root.children:
-            if node.type in (
-                "create_table_statement",
-                "create_view_statement",
-                "create_function_statement",
-                "create_procedure_statement",
-            ):
-                unit = self._extract_sql_object(node, source, file_path, module_name)
-                if unit:
-                    units.append(unit)
-
-        return units
-
-    def _extract_sql_object(
-        self, node: Node, source: str, file_path: str, module_name: str
-    ) -> SemanticUnit | None:
-        """Extract a SQL object (table, view, function, procedure)."""
-        # Try to find name node
-        name_node = node.child_by_field_name("name")
-        if not name_node:
-            # Fallback: look for identifier in children
-            for child in node.children:
-                if child.type == "identifier":
-                    name_node = child
-                    break
-
-        if not name_node:
-            return None
-
-        name = self._extract_text(name_node, source)
-        qualified_name = f"{module_name}.{name}"
-
-        signature = self._extract_text(node, source).split("\n")[0]
-
-        # Determine unit type
-        if "table" in node.type:
-            unit_type = UnitType.CLASS
-        elif "view" in node.type:
-            unit_type = UnitType.CLASS
-        else:
-            unit_type = UnitType.FUNCTION
-
-        return SemanticUnit(
-            name=name,
-            qualified_name=qualified_name,
-            unit_type=unit_type,
-            signature=signature,
-            content=self._extract_text(node, source),
-            file_path=file_path,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
-            language="sql",
-            docstring=None,
-            complexity=None,  # SQL complexity N/A
-        )
-
     def _compute_complexity(self, node: Node, language: str) -> int:
         """Compute cyclomatic complexity for a function/method."""
-        branch_nodes = BRANCH_TYPES.get(language, set())
+        spec = self._specs.get(language)
+        branch_nodes = spec.branch_types if spec else frozenset()
         count = 1  # Base complexity
 
         # Walk the tree and count branch points
@@ -1142,3 +750,17 @@ class TreeSitterParser(CodeParser):
         start_byte = node.start_byte
         end_byte = node.end_byte
         return source[start_byte:end_byte]
+
+
+def parse_file_in_worker(path: str) -> list[SemanticUnit]:
+    """Parse a file synchronously in a process pool worker.
+
+    Module-level so it can be pickled; each worker builds its parser once.
+
+    Raises:
+        ParseError: If file cannot be parsed
+    """
+    global _worker_parser
+    if _worker_parser is None:
+        _worker_parser = TreeSitterParser()
+    return _worker_parser._parse_file_sync(path)
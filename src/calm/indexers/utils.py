@@ -1,26 +1,20 @@
 """Utility functions and constants for code indexing."""
 
 import hashlib
+import json
 
-# Extension to language mapping (shared between parser and indexer)
-# Note: Lua is not included as tree-sitter-lua is not available on PyPI
-EXTENSION_MAP = {
-    ".py": "python",
-    ".ts": "typescript",
-    ".tsx": "typescript",
-    ".js": "javascript",
-    ".jsx": "javascript",
-    ".rs": "rust",
-    ".swift": "swift",
-    ".java": "java",
-    ".c": "c",
-    ".h": "c",
-    ".cpp": "cpp",
-    ".hpp": "cpp",
-    ".cc": "cpp",
-    ".cxx": "cpp",
-    ".sql": "sql",
-}
+from .base import ParseError, SemanticUnit
+from .languages import LazyDict, extension_map
+
+# Characters per token assumed when sizing text for an embedding model
+CHARS_PER_TOKEN = 4
+
+# Text limit for embedding models that don't report a maximum input length
+DEFAULT_MAX_EMBEDDING_CHARS = 4000
+
+# Extension to language mapping (shared between parser and indexer), built
+# when first used
+EXTENSION_MAP: LazyDict[str] = LazyDict(extension_map)
 
 
 def generate_unit_id(project: str, file_path: str, qualified_name: str) -> str:
@@ -33,6 +27,27 @@ def generate_unit_id(project: str, file_path: str, qualified_name: str) -> str:
     return hashlib.sha256(content.encode()).hexdigest()[:32]
 
 
+def read_source(path: str) -> str | None:
+    """Read a file to parse as UTF-8 text.
+
+    Returns None for binary files (null bytes in the first 8KB).
+
+    Raises:
+        ParseError: If the file isn't valid UTF-8 or can't be read
+    """
+    try:
+        with open(path, "rb") as f:
+            if b"\x00" in f.read(8192):
+                return None
+
+        with open(path, encoding="utf-8") as f:
+            return f.read()
+    except UnicodeDecodeError:
+        raise ParseError("encoding_error", "Not valid UTF-8", path)
+    except OSError as e:
+        raise ParseError("io_error", str(e), path)
+
+
 def compute_file_hash(path: str) -> str:
     """Compute SHA-256 hash of file contents."""
     hasher = hashlib.sha256()
@@ -40,3 +55,51 @@ def compute_file_hash(path: str) -> str:
         for chunk in iter(lambda: f.read(65536), b""):
             hasher.update(chunk)
     return hasher.hexdigest()
+
+
+def compute_unit_hash(unit: SemanticUnit) -> str:
+    """Compute SHA-256 hash of the unit fields that are embedded or stored.
+
+    Line numbers are left out, so a unit that only moved within its file
+    keeps its hash.
+    """
+    fields = [
+        unit.unit_type.value,
+        unit.name,
+        unit.signature,
+        unit.docstring,
+        unit.content,
+        unit.language,
+        unit.complexity,
+        unit.visibility,
+        unit.attributes,
+    ]
+    return hashlib.sha256(json.dumps(fields).encode()).hexdigest()
+
+
+def max_embedding_chars(max_tokens: int | None) -> int:
+    """Get how many characters of text fit a model's maximum input length."""
+    if max_tokens is None:
+        return DEFAULT_MAX_EMBEDDING_CHARS
+    return max_tokens * CHARS_PER_TOKEN
+
+
+def unit_embedding_text(
+    signature: str,
+    docstring: str | None,
+    content: str,
+    max_chars: int = DEFAULT_MAX_EMBEDDING_CHARS,
+) -> str:
+    """Format a unit's signature, docstring and code for embedding.
+
+    The code is cut so the whole text fits in ``max_chars``.
+    """
+    parts = [signature]
+
+    if docstring:
+        parts.append(docstring)
+
+    header = sum(len(part) + 2 for part in parts)
+    parts.append(content[: max(max_chars - header, 0)])
+
+    return "\n\n".join(parts)
@@ -86,9 +86,16 @@ async def create_qdrant_snapshot(
     try:
         client = AsyncQdrantClient(url=qdrant_url, timeout=30)
 
-        # Get existing collections to skip ones that have not been created
+        # Get existing collections to skip ones that have not been created.
+        # Collections swapped in by re-embedding are served through aliases,
+        # so snapshot the collection behind each alias.
         collections_response = await client.get_collections()
+        aliases_response = await client.get_aliases()
+        physical_names = {
+            a.alias_name: a.collection_name for a in aliases_response.aliases
+        }
         existing_names = {c.name for c in collections_response.collections}
+        existing_names |= physical_names.keys()
 
         dest_dir = _qdrant_snapshot_path_for(backup_name)
         dest_dir.mkdir(parents=True, exist_ok=True)
@@ -102,8 +109,9 @@ async def create_qdrant_snapshot(
                 continue
 
             # Create per-collection snapshot
+            physical_name = physical_names.get(collection_name, collection_name)
             snapshot_info = await client.create_snapshot(
-                collection_name=collection_name, wait=True
+                collection_name=physical_name, wait=True
             )
             if snapshot_info is None:
                 logger.warning(
@@ -116,7 +124,7 @@ async def create_qdrant_snapshot(
 
             # Download the snapshot file via HTTP
             download_url = (
-                f"{qdrant_url}/collections/{collection_name}"
+                f"{qdrant_url}/collections/{physical_name}"
                 f"/snapshots/{snapshot_name}"
             )
             dest_path = dest_dir / f"{collection_name}.snapshot"
@@ -129,7 +137,7 @@ async def create_qdrant_snapshot(
             # Clean up server-side snapshot to avoid accumulation
             try:
                 await client.delete_snapshot(
-                    collection_name=collection_name,
+                    collection_name=physical_name,
                     snapshot_name=snapshot_name,
                 )
             except Exception:
@@ -194,15 +202,29 @@ async def restore_qdrant_snapshot(
         logger.info("No Qdrant snapshot directory for backup '%s'", backup_name)
         return False
 
+    snapshot_files = sorted(snapshot_dir.glob("*.snapshot"))
+    if not snapshot_files:
+        return False
+
     try:
+        # Restore into the collection behind an existing alias
+        async with httpx.AsyncClient(timeout=30.0) as http_client:
+            response = await http_client.get(f"{qdrant_url}/aliases")
+            response.raise_for_status()
+            physical_names = {
+                a["alias_name"]: a["collection_name"]
+                for a in response.json()["result"]["aliases"]
+            }
+
         restored_count = 0
-        for snapshot_file in sorted(snapshot_dir.glob("*.snapshot")):
+        for snapshot_file in snapshot_files:
             # Derive collection name from filename
             # e.g. "memories.snapshot" -> "memories"
             collection_name = snapshot_file.stem
+            physical_name = physical_names.get(collection_name, collection_name)
 
             upload_url = (
-                f"{qdrant_url}/collections/{collection_name}/snapshots/upload"
+                f"{qdrant_url}/collections/{physical_name}/snapshots/upload"
             )
 
             async with httpx.AsyncClient(timeout=300.0) as http_client:
@@ -0,0 +1,106 @@
+"""Collapsing chunk hits back into one result per code unit.
+
+Long code units are indexed as several chunks (see
+:mod:`calm.indexers.chunking`), so a search can hit the same unit more
+than once. Results are grouped by the ``parent_id`` in their payload and
+only each unit's best hit is kept, with the unit's own payload and the
+matching chunk's lines reported as ``match_start_line`` and
+``match_end_line``.
+"""
+
+from typing import Any
+
+from calm.storage.base import SearchResult, VectorStore
+
+from .collections import CollectionName
+
+# Chunked collections fetch this many hits per requested result, so enough
+# distinct units remain after collapsing
+CHUNK_OVERFETCH = 3
+
+# Collections whose points may be chunks of a larger unit
+CHUNKED_COLLECTIONS = frozenset({CollectionName.CODE_UNITS})
+
+# Payload fields describing a chunk rather than its unit
+CHUNK_FIELDS = (
+    "parent_id",
+    "chunk_index",
+    "chunk_count",
+    "chunk_start_line",
+    "chunk_end_line",
+)
+
+
+def candidate_limit(collection: str, limit: int) -> int:
+    """Get how many hits to fetch for ``limit`` results from a collection."""
+    if collection in CHUNKED_COLLECTIONS:
+        return limit * CHUNK_OVERFETCH
+    return limit
+
+
+async def collapse_chunks(
+    vector_store: VectorStore,
+    collection: str,
+    results: list[SearchResult],
+    limit: int,
+) -> list[SearchResult]:
+    """Keep the best hit of each unit, as a result for the unit itself.
+
+    Results without a ``parent_id`` (other collections, or units indexed
+    before chunking) are kept as they are.
+
+    Args:
+        vector_store: Store to fetch a unit's payload from when only a
+            later chunk of it matched
+        collection: Collection searched
+        results: Hits, best first
+        limit: Maximum results to return
+
+    Returns:
+        At most ``limit`` results, one per unit, best first
+    """
+    if collection not in CHUNKED_COLLECTIONS:
+        return results[:limit]
+
+    collapsed: list[SearchResult] = []
+    seen: set[str] = set()
+    for result in results:
+        parent_id = result.payload.get("parent_id")
+        if parent_id is None:
+            if result.id not in seen:
+                seen.add(result.id)
+                collapsed.append(result)
+        elif parent_id not in seen:
+            seen.add(parent_id)
+            collapsed.append(
+                await _unit_result(vector_store, collection, parent_id, result)
+            )
+        if len(collapsed) == limit:
+            break
+    return collapsed
+
+
+async def _unit_result(
+    vector_store: VectorStore, collection: str, parent_id: str, hit: SearchResult
+) -> SearchResult:
+    """Turn a chunk hit into a result for its unit."""
+    payload: dict[str, Any] = hit.payload
+    if hit.id != parent_id:
+        parent = await vector_store.get(collection, parent_id)
+        if parent is not None:
+            payload = parent.payload
+
+    unit_payload = {k: v for k, v in payload.items() if k not in CHUNK_FIELDS}
+    unit_payload["match_start_line"] = hit.payload.get(
+        "chunk_start_line", payload.get("start_line")
+    )
+    unit_payload["match_end_line"] = hit.payload.get(
+        "chunk_end_line", payload.get("end_line")
+    )
+    return SearchResult(
+        id=parent_id,
+        score=hit.score,
+        payload=unit_payload,
+        vector=hit.vector,
+        ranking=hit.ranking,
+    )
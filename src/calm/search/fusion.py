@@ -0,0 +1,121 @@
+"""Fusion of semantic and keyword result lists for hybrid search.
+
+Semantic scores (cosine similarity) and keyword scores (normalized BM25 or
+substring heuristics) live on different scales, so they are never added
+directly. Two strategies are supported:
+
+``rrf``
+    Reciprocal Rank Fusion: each list contributes ``weight / (k + rank)``
+    for every result it contains. Only ranks matter, so the scales of the
+    raw scores are irrelevant.
+
+``weighted``
+    Each list's scores are min-max normalized to [0, 1], then combined as
+    ``semantic_weight * semantic + keyword_weight * keyword``. A result
+    missing from a list contributes 0 for it.
+
+Every fused result carries a ``ranking`` breakdown (ranks are 1-based and
+``None`` when the result is absent from a list) for debugging.
+"""
+
+from typing import Any
+
+from calm.storage.base import SearchResult
+
+FUSION_METHODS = ("rrf", "weighted")
+
+
+def fuse_results(
+    semantic_results: list[SearchResult],
+    keyword_results: list[SearchResult],
+    limit: int,
+    method: str = "rrf",
+    rrf_k: int = 60,
+    semantic_weight: float = 0.5,
+    keyword_weight: float = 0.5,
+) -> list[SearchResult]:
+    """Fuse semantic and keyword results into a single ranking.
+
+    Args:
+        semantic_results: Results ordered by semantic similarity.
+        keyword_results: Results ordered by keyword relevance.
+        limit: Maximum results to return.
+        method: Fusion method, one of ``FUSION_METHODS``.
+        rrf_k: RRF rank constant (larger values flatten the rank curve).
+        semantic_weight: Weight of the semantic list.
+        keyword_weight: Weight of the keyword list.
+
+    Returns:
+        Fused results, best first, with ``score`` set to the fused score
+        and ``ranking`` set to the per-result breakdown.
+
+    Raises:
+        ValueError: If ``method`` is not a supported fusion method.
+    """
+    if method not in FUSION_METHODS:
+        valid = ", ".join(f"'{m}'" for m in FUSION_METHODS)
+        raise ValueError(f"Invalid fusion method '{method}'. Must be one of: {valid}")
+
+    semantic = _ranked(semantic_results)
+    keyword = _ranked(keyword_results)
+    semantic_norm = normalize_scores(semantic_results)
+    keyword_norm = normalize_scores(keyword_results)
+
+    # Payloads come from the first list a result appears in
+    payloads: dict[str, dict[str, Any]] = {}
+    for r in [*semantic_results, *keyword_results]:
+        payloads.setdefault(r.id, r.payload)
+
+    fused: list[SearchResult] = []
+    for id, payload in payloads.items():
+        semantic_rank, semantic_score = semantic.get(id, (None, None))
+        keyword_rank, keyword_score = keyword.get(id, (None, None))
+
+        if method == "rrf":
+            score = 0.0
+            if semantic_rank is not None:
+                score += semantic_weight / (rrf_k + semantic_rank)
+            if keyword_rank is not None:
+                score += keyword_weight / (rrf_k + keyword_rank)
+        else:
+            score = semantic_weight * semantic_norm.get(id, 0.0)
+            score += keyword_weight * keyword_norm.get(id, 0.0)
+
+        fused.append(
+            SearchResult(
+                id=id,
+                score=score,
+                payload=payload,
+                vector=None,
+                ranking={
+                    "method": method,
+                    "semantic_rank": semantic_rank,
+                    "semantic_score": semantic_score,
+                    "keyword_rank": keyword_rank,
+                    "keyword_score": keyword_score,
+                    "fused_score": score,
+                },
+            )
+        )
+
+    # Stable sort keeps semantic order for ties
+    fused.sort(key=lambda r: r.score, reverse=True)
+    return fused[:limit]
+
+
+def _ranked(results: list[SearchResult]) -> dict[str, tuple[int, float]]:
+    """Map result IDs to their (1-based rank, raw score)."""
+    ranked: dict[str, tuple[int, float]] = {}
+    for rank, r in enumerate(results, start=1):
+        ranked.setdefault(r.id, (rank, r.score))
+    return ranked
+
+
+def normalize_scores(results: list[SearchResult]) -> dict[str, float]:
+    """Min-max normalize scores to [0, 1] (all 1.0 when scores are equal)."""
+    if not results:
+        return {}
+    scores = [r.score for r in results]
+    low, high = min(scores), max(scores)
+    span = high - low
+    return {r.id: (r.score - low) / span if span > 0 else 1.0 for r in results}
@@ -0,0 +1,176 @@
+"""Query preprocessing for code-aware search.
+
+Queries are split on whitespace and each token classified:
+
+- Inline qualifiers ``lang:rust``, ``path:src/indexers`` and
+  ``type:method`` (also ``language:``, ``file:`` and ``kind:``) become
+  payload filters on ``language``, ``file_path`` (a ``$glob``) and
+  ``unit_type``. Repeating a qualifier matches any of its values.
+- File globs such as ``*.rs`` or ``src/**/test_*.py`` become ``file_path``
+  filters too.
+- Identifiers (``parse_test_results``, ``Point::translate``,
+  ``HttpServer``, ``self.items``) and paths stay in the query, and are
+  expanded into their word pieces so ``Point::translate`` also searches
+  ``point translate``.
+
+Everything else is kept as free text.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any
+
+from calm.storage.keyword_index import tokenize
+
+# Qualifier name -> payload field
+QUALIFIERS: dict[str, str] = {
+    "lang": "language",
+    "language": "language",
+    "path": "file_path",
+    "file": "file_path",
+    "type": "unit_type",
+    "kind": "unit_type",
+}
+
+# Common short language names -> indexed language names
+LANGUAGE_ALIASES: dict[str, str] = {
+    "py": "python",
+    "rs": "rust",
+    "ts": "typescript",
+    "js": "javascript",
+    "golang": "go",
+    "c++": "cpp",
+    "cs": "csharp",
+    "c#": "csharp",
+    "rb": "ruby",
+    "kt": "kotlin",
+    "md": "markdown",
+    "yml": "yaml",
+}
+
+_QUALIFIER_RE = re.compile(rf"^({'|'.join(QUALIFIERS)}):(\S+)$", re.IGNORECASE)
+
+# snake_case, CamelCase, dotted/`::`/`->` paths and the like
+_IDENTIFIER_RE = re.compile(
+    r"^[A-Za-z_$][\w$]*(?:(?:::|\.|->|#)[A-Za-z_$][\w$]*)*(?:\(\))?$"
+)
+_PATH_RE = re.compile(r"^[\w.\-~]*(?:/[\w.\-]+)+/?$|^[\w\-]+\.[A-Za-z0-9]{1,5}$")
+
+# Punctuation that wraps tokens in prose, e.g. `foo_bar`, (Point::new)
+_WRAPPING = "`'\"()[]{},;"
+
+
+@dataclass
+class ParsedQuery:
+    """A query split into free text, payload filters and identifiers."""
+
+    text: str  # Query with qualifiers and globs removed
+    filters: dict[str, Any] = field(default_factory=dict)
+    identifiers: list[str] = field(default_factory=list)  # Incl. paths
+
+    @property
+    def expanded(self) -> str:
+        """The text followed by the word pieces of its identifiers."""
+        return expand_identifiers(self.text, self.identifiers)
+
+
+def parse_query(query: str) -> ParsedQuery:
+    """Split a search query into free text, filters and identifiers."""
+    words: list[str] = []
+    identifiers: list[str] = []
+    values: dict[str, list[str]] = {}
+
+    for raw in query.split():
+        token = raw.strip(_WRAPPING) or raw
+        match = _QUALIFIER_RE.match(token)
+        if match:
+            field_name = QUALIFIERS[match.group(1).lower()]
+            values.setdefault(field_name, []).append(match.group(2))
+            continue
+        if ("*" in token or "?" in token) and _is_glob(token):
+            values.setdefault("glob", []).append(token)
+            continue
+        words.append(raw)
+        if is_identifier(token) or _PATH_RE.match(token):
+            identifiers.append(token)
+
+    return ParsedQuery(
+        text=" ".join(words),
+        filters=_build_filters(values),
+        identifiers=list(dict.fromkeys(identifiers)),
+    )
+
+
+def is_identifier(token: str) -> bool:
+    """Check whether a token looks like a code identifier, not a word.
+
+    Plain words (``parser``, ``Parser``) are not identifiers; names with an
+    underscore, an inner capital or a path separator are.
+    """
+    if not _IDENTIFIER_RE.match(token):
+        return False
+    name = token.removesuffix("()")
+    return (
+        "_" in name.strip("_")
+        or any(sep in name for sep in ("::", ".", "->", "#"))
+        or any(c.isupper() for c in name[1:])
+        or token.endswith("()")
+    )
+
+
+def expand_identifiers(text: str, identifiers: list[str] | None = None) -> str:
+    """Append the word pieces of identifiers that aren't already in the text.
+
+    ``Point::translate`` gives ``"Point::translate point translate"``.
+    With ``identifiers`` None, they are detected in ``text``.
+    """
+    if identifiers is None:
+        identifiers = [
+            token
+            for token in (raw.strip(_WRAPPING) for raw in text.split())
+            if is_identifier(token) or _PATH_RE.match(token)
+        ]
+
+    present = {word.lower() for word in text.split()}
+    pieces: list[str] = []
+    for identifier in identifiers:
+        for piece in tokenize(identifier):
+            # Keep word pieces, not the compound form tokenize also yields
+            if "_" in piece or piece in present or piece in pieces:
+                continue
+            if piece != identifier.lower():
+                pieces.append(piece)
+    return " ".join([text, *pieces]) if pieces else text
+
+
+def query_terms(text: str) -> list[str]:
+    """Lowercase terms for keyword matching: words plus identifier pieces."""
+    return expand_identifiers(text).lower().split()
+
+
+def _is_glob(token: str) -> bool:
+    """Check that a token with wildcards is a file glob, not prose."""
+    return bool(re.fullmatch(r"[\w.\-/*?]+", token)) and (
+        "/" in token or "." in token
+    )
+
+
+def _build_filters(values: dict[str, list[str]]) -> dict[str, Any]:
+    """Turn collected qualifier values into payload filters."""
+    filters: dict[str, Any] = {}
+    for field_name in ("language", "unit_type"):
+        if field_name not in values:
+            continue
+        items = [v.lower() for v in values[field_name]]
+        if field_name == "language":
+            items = [LANGUAGE_ALIASES.get(v, v) for v in items]
+        items = list(dict.fromkeys(items))
+        filters[field_name] = items[0] if len(items) == 1 else {"$in": items}
+
+    globs = values.get("file_path", []) + values.get("glob", [])
+    globs = [g for g in dict.fromkeys(globs) if g.strip("/")]
+    if len(globs) == 1:
+        filters["file_path"] = {"$glob": globs[0]}
+    elif globs:
+        filters["$or"] = [{"file_path": {"$glob": g}} for g in globs]
+    return filters
@@ -0,0 +1,74 @@
+"""Second-stage reranking of first-stage search results.
+
+The top candidates of a vector, keyword or hybrid search are rescored with
+a :class:`~calm.embedding.base.Reranker` (a cross-encoder in production)
+against the text of their payloads. Each reranked result keeps its
+first-stage rank and score in ``ranking`` alongside the rerank score.
+"""
+
+from typing import Any
+
+import structlog
+
+from calm.embedding.base import Reranker
+from calm.storage.base import SearchResult
+
+logger = structlog.get_logger()
+
+
+def document_text(payload: dict[str, Any], text_fields: list[str]) -> str:
+    """Join the non-empty text fields of a payload into one document."""
+    parts = [payload.get(field) for field in text_fields]
+    return "\n".join(p for p in parts if isinstance(p, str) and p)
+
+
+async def rerank_results(
+    reranker: Reranker,
+    query: str,
+    results: list[SearchResult],
+    text_fields: list[str],
+    limit: int,
+) -> list[SearchResult]:
+    """Rescore results with a reranker and return the best ``limit``.
+
+    If the reranker fails, the first-stage order is kept so search still
+    answers.
+
+    Args:
+        reranker: Reranker scoring (query, document) pairs.
+        query: User's text query.
+        results: First-stage candidates, best first.
+        text_fields: Payload fields making up each candidate's document.
+        limit: Maximum results to return.
+
+    Returns:
+        Results ordered by rerank score, with ``score`` set to it.
+    """
+    if not results:
+        return []
+
+    documents = [document_text(r.payload, text_fields) for r in results]
+    try:
+        scores = await reranker.score(query, documents)
+    except Exception as e:
+        logger.warning("search.rerank_failed", error=str(e))
+        return results[:limit]
+
+    reranked = [
+        SearchResult(
+            id=r.id,
+            score=score,
+            payload=r.payload,
+            vector=None,
+            ranking={
+                **(r.ranking or {}),
+                "first_stage_rank": rank,
+                "first_stage_score": r.score,
+                "rerank_score": score,
+            },
+        )
+        for rank, (r, score) in enumerate(zip(results, scores), start=1)
+    ]
+    # Stable sort keeps first-stage order for ties
+    reranked.sort(key=lambda r: r.score, reverse=True)
+    return reranked[:limit]
@@ -66,6 +66,9 @@ class CodeResult:
     score: float
     line_start: int
     line_end: int
+    # Lines of the best-matching chunk (the whole unit unless it's chunked)
+    match_line_start: int | None = None
+    match_line_end: int | None = None
 
     @classmethod
     def from_search_result(cls, result: SearchResult) -> "CodeResult":
@@ -78,10 +81,13 @@ class CodeResult:
             language=payload["language"],
             unit_type=payload["unit_type"],
             qualified_name=payload["qualified_name"],
-            code=payload["code"],
+            code=payload.get("code", ""),
             docstring=payload.get("docstring"),
-            line_start=payload["line_start"],
-            line_end=payload["line_end"],
+            # The indexer stores start_line/end_line
+            line_start=payload.get("line_start", payload.get("start_line", 0)),
+            line_end=payload.get("line_end", payload.get("end_line", 0)),
+            match_line_start=payload.get("match_start_line"),
+            match_line_end=payload.get("match_end_line"),
         )
 
 
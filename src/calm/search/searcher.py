@@ -3,11 +3,17 @@
 from datetime import datetime
 from typing import Any
 
+from calm.config import settings
 from calm.context.searcher_types import Searcher as SearcherABC
-from calm.embedding.base import EmbeddingService
+from calm.embedding.base import EmbeddingService, Reranker
 from calm.storage.base import SearchResult, VectorStore
+from calm.storage.keyword_index import KeywordIndexedStore
 
+from .chunks import candidate_limit, collapse_chunks
 from .collections import CollectionName, InvalidAxisError
+from .fusion import fuse_results
+from .query import expand_identifiers, parse_query, query_terms
+from .rerank import rerank_results
 from .results import (
     CodeResult,
     CommitResult,
@@ -19,14 +25,15 @@ from .results import (
 # Valid search modes
 VALID_SEARCH_MODES = ("semantic", "keyword", "hybrid")
 
-# Maximum number of items to scroll through for keyword search.
-# This caps memory usage for keyword-mode collection scans.
+# Maximum number of items to scroll through for keyword search on stores
+# without a keyword index. This caps memory usage for collection scans.
 _KEYWORD_SCROLL_LIMIT = 1000
 
 # Text payload fields to search per collection.
 # Each mapping is collection_name -> list of payload field names that
-# contain user-visible text worth matching against.
-_TEXT_FIELDS: dict[str, list[str]] = {
+# contain user-visible text worth matching against. Also the fields
+# indexed by the BM25 keyword index.
+KEYWORD_TEXT_FIELDS: dict[str, list[str]] = {
     CollectionName.MEMORIES: ["content"],
     CollectionName.CODE_UNITS: ["code", "qualified_name", "docstring"],
     CollectionName.COMMITS: ["message"],
@@ -62,11 +69,6 @@ _TEXT_FIELDS: dict[str, list[str]] = {
     ],
 }
 
-# Hybrid mode boost for keyword matches.  When a result appears in both
-# semantic and keyword results, its semantic score is boosted by this amount.
-_HYBRID_KEYWORD_BOOST = 0.15
-
-
 class SearchError(Exception):
     """Base exception for search operations."""
 
@@ -139,7 +141,8 @@ def _keyword_match_score(
     """
     best_score = 0.0
     query_lower = query.lower()
-    query_terms = query_lower.split()
+    # Identifiers also match by their word pieces (parse_file -> parse, file)
+    terms = query_terms(query)
 
     for field in text_fields:
         value = payload.get(field)
@@ -160,10 +163,10 @@ def _keyword_match_score(
             continue
 
         # Term-level matching: count how many query terms appear
-        if query_terms:
-            matched_terms = sum(1 for t in query_terms if t in value_lower)
+        if terms:
+            matched_terms = sum(1 for t in terms if t in value_lower)
             if matched_terms > 0:
-                term_ratio = matched_terms / len(query_terms)
+                term_ratio = matched_terms / len(terms)
                 score = 0.3 * term_ratio
                 best_score = max(best_score, score)
 
@@ -178,10 +181,13 @@ async def _keyword_search(
     filters: dict[str, Any] | None,
     text_fields: list[str],
 ) -> list[SearchResult]:
-    """Perform keyword (text substring) search over a collection.
+    """Perform keyword search over a collection.
 
-    Scrolls through the collection, filters by text match, and returns
-    the top results sorted by keyword relevance score.
+    Stores wrapped in ``KeywordIndexedStore`` are searched with BM25 over
+    their persistent inverted index (normalized so the best match scores
+    1.0). Other stores fall back to scrolling up to
+    ``_KEYWORD_SCROLL_LIMIT`` points and scoring substring matches. Chunks
+    of the same code unit are collapsed into one result.
 
     Args:
         vector_store: The vector store to search.
@@ -194,6 +200,21 @@ async def _keyword_search(
     Returns:
         List of SearchResult with score set to keyword relevance.
     """
+    if isinstance(vector_store, KeywordIndexedStore) and (
+        vector_store.keyword_index.indexes(collection)
+    ):
+        try:
+            results = await vector_store.keyword_search(
+                collection, query, candidate_limit(collection, limit), filters
+            )
+        except Exception as e:
+            if "collection not found" in str(e).lower():
+                raise CollectionNotFoundError(
+                    f"Collection '{collection}' not found."
+                ) from e
+            raise
+        return await collapse_chunks(vector_store, collection, results, limit)
+
     # Fetch candidates from the collection (with metadata filters applied)
     try:
         candidates = await vector_store.scroll(
@@ -224,9 +245,11 @@ async def _keyword_search(
                 ),
             ))
 
-    # Sort by score descending, then take top `limit`
+    # Sort by score descending, then take top `limit` units
     scored.sort(key=lambda x: x[0], reverse=True)
-    return [r for _, r in scored[:limit]]
+    return await collapse_chunks(
+        vector_store, collection, [r for _, r in scored], limit
+    )
 
 
 async def _semantic_search(
@@ -248,22 +271,27 @@ async def _semantic_search(
         filters: Optional payload filters.
 
     Returns:
-        List of SearchResult ordered by semantic similarity.
+        List of SearchResult ordered by semantic similarity, with chunks
+        of the same code unit collapsed into one result.
 
     Raises:
         EmbeddingError: If query embedding fails.
         CollectionNotFoundError: If the collection does not exist.
     """
+    if collection == CollectionName.CODE_UNITS:
+        # Identifiers are embedded with their word pieces, which the code
+        # model understands better than Point::translate or parse_test_results
+        query = expand_identifiers(query)
     try:
         query_vector = await embedding_service.embed(query)
     except Exception as e:
         raise EmbeddingError(f"Failed to embed query: {e}") from e
 
     try:
-        return await vector_store.search(
+        results = await vector_store.search(
             collection=collection,
             query=query_vector,
-            limit=limit,
+            limit=candidate_limit(collection, limit),
             filters=filters,
         )
     except Exception as e:
@@ -272,6 +300,7 @@ async def _semantic_search(
                 f"Collection '{collection}' not found."
             ) from e
         raise
+    return await collapse_chunks(vector_store, collection, results, limit)
 
 
 async def _hybrid_search(
@@ -282,12 +311,13 @@ async def _hybrid_search(
     limit: int,
     filters: dict[str, Any] | None,
     text_fields: list[str],
+    fusion: str | None = None,
 ) -> list[SearchResult]:
-    """Perform hybrid search (semantic + keyword with boost).
+    """Perform hybrid search (semantic + keyword, fused).
 
-    Runs semantic search first, then keyword search.  Results from both
-    are merged: keyword-only results are appended with their keyword score,
-    while results that appear in both get a boost to their semantic score.
+    Runs semantic and keyword search over a wider candidate pool, then
+    fuses the two rankings with ``fusion`` (see :mod:`calm.search.fusion`).
+    Each returned result carries its ``ranking`` breakdown.
 
     Args:
         embedding_service: Service to embed the query text.
@@ -297,46 +327,128 @@ async def _hybrid_search(
         limit: Maximum results to return.
         filters: Optional payload filters.
         text_fields: Payload field names to search for text matches.
+        fusion: "rrf" or "weighted" (default from ``settings.search``).
 
     Returns:
-        Merged and re-ranked list of SearchResult.
+        Fused list of SearchResult, best first.
     """
-    # Run semantic search (get extra results for merging headroom)
+    options = settings.search
+    candidates = limit * max(options.candidate_multiplier, 1)
+
     semantic_results = await _semantic_search(
-        embedding_service, vector_store, collection, query, limit, filters
+        embedding_service, vector_store, collection, query, candidates, filters
     )
-
-    # Run keyword search
     keyword_results = await _keyword_search(
-        vector_store, collection, query, limit, filters, text_fields
+        vector_store, collection, query, candidates, filters, text_fields
     )
 
-    # Build lookup of keyword scores by ID
-    keyword_scores: dict[str, float] = {
-        r.id: r.score for r in keyword_results
-    }
-
-    # Merge: start with semantic results, boosting those with keyword matches
-    merged: dict[str, SearchResult] = {}
-    for r in semantic_results:
-        boosted_score = r.score
-        if r.id in keyword_scores:
-            boosted_score = min(r.score + _HYBRID_KEYWORD_BOOST, 1.0)
-        merged[r.id] = SearchResult(
-            id=r.id,
-            score=boosted_score,
-            payload=r.payload,
-            vector=None,
+    return fuse_results(
+        semantic_results,
+        keyword_results,
+        limit,
+        method=fusion or options.fusion,
+        rrf_k=options.rrf_k,
+        semantic_weight=options.semantic_weight,
+        keyword_weight=options.keyword_weight,
+    )
+
+
+async def search_collection(
+    embedding_service: EmbeddingService,
+    vector_store: VectorStore,
+    search_mode: str,
+    collection: str,
+    query: str,
+    limit: int,
+    filters: dict[str, Any] | None,
+    text_fields: list[str],
+    fusion: str | None = None,
+    reranker: Reranker | None = None,
+    rerank_candidates: int | None = None,
+) -> list[SearchResult]:
+    """Run a semantic, keyword or hybrid search, then rerank if enabled.
+
+    With a reranker, a collection with rerank candidates fetches that many
+    first-stage results in any mode and returns the best ``limit`` after
+    rescoring them. Used by ``Searcher`` and the search tools alike.
+
+    Args:
+        embedding_service: Service to embed the query text.
+        vector_store: The vector store to search.
+        search_mode: One of "semantic", "keyword", "hybrid".
+        collection: Collection name.
+        query: User's text query.
+        limit: Maximum results to return.
+        filters: Optional payload filters.
+        text_fields: Payload fields used for keyword matching and reranking.
+        fusion: "rrf" or "weighted" for hybrid search (default from
+            ``settings.search``).
+        reranker: Second-stage reranker, if any.
+        rerank_candidates: First-stage candidates to rerank (default from
+            ``settings.rerank.collections``; 0 disables reranking).
+
+    Returns:
+        List of SearchResult, best first.
+    """
+    if rerank_candidates is None:
+        rerank_candidates = settings.rerank.collections.get(collection, 0)
+    if reranker is None or rerank_candidates <= 0:
+        return await _first_stage_search(
+            embedding_service,
+            vector_store,
+            search_mode,
+            collection,
+            query,
+            limit,
+            filters,
+            text_fields,
+            fusion,
         )
 
-    # Add keyword-only results (not already in semantic results)
-    for r in keyword_results:
-        if r.id not in merged:
-            merged[r.id] = r
+    results = await _first_stage_search(
+        embedding_service,
+        vector_store,
+        search_mode,
+        collection,
+        query,
+        max(limit, rerank_candidates),
+        filters,
+        text_fields,
+        fusion,
+    )
+    return await rerank_results(reranker, query, results, text_fields, limit)
 
-    # Sort by final score descending, return top `limit`
-    all_results = sorted(merged.values(), key=lambda x: x.score, reverse=True)
-    return all_results[:limit]
+
+async def _first_stage_search(
+    embedding_service: EmbeddingService,
+    vector_store: VectorStore,
+    search_mode: str,
+    collection: str,
+    query: str,
+    limit: int,
+    filters: dict[str, Any] | None,
+    text_fields: list[str],
+    fusion: str | None,
+) -> list[SearchResult]:
+    """Run the semantic, keyword or hybrid search itself."""
+    if search_mode == "keyword":
+        return await _keyword_search(
+            vector_store, collection, query, limit, filters, text_fields
+        )
+    if search_mode == "hybrid":
+        return await _hybrid_search(
+            embedding_service,
+            vector_store,
+            collection,
+            query,
+            limit,
+            filters,
+            text_fields,
+            fusion,
+        )
+    return await _semantic_search(
+        embedding_service, vector_store, collection, query, limit, filters
+    )
 
 
 class Searcher(SearcherABC):
@@ -346,16 +458,30 @@ class Searcher(SearcherABC):
         - ``"semantic"``: Vector similarity search (default).
         - ``"keyword"``: Case-insensitive text substring matching on
           payload fields.
-        - ``"hybrid"``: Semantic search with keyword-match boosting.
+        - ``"hybrid"``: Semantic and keyword rankings fused (RRF or
+          weighted, per each call's ``fusion`` or ``settings.search``).
+
+    With a reranker, collections listed in ``rerank_candidates`` fetch that
+    many first-stage candidates in any mode and return the best ``limit``
+    after rescoring them.
     """
 
     def __init__(
         self,
         embedding_service: EmbeddingService,
         vector_store: VectorStore,
+        reranker: Reranker | None = None,
+        rerank_candidates: dict[str, int] | None = None,
     ):
         self._embedding_service = embedding_service
         self._vector_store = vector_store
+        self._reranker = reranker
+        # collection -> top-N candidates to rerank (default from settings)
+        self._rerank_candidates = (
+            settings.rerank.collections
+            if rerank_candidates is None
+            else rerank_candidates
+        )
 
     # ------------------------------------------------------------------
     # Memories
@@ -367,6 +493,7 @@ class Searcher(SearcherABC):
         category: str | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> list[MemoryResult]:
         if not query or not query.strip():
             return []
@@ -375,10 +502,10 @@ class Searcher(SearcherABC):
 
         filters = _build_filters(category=category)
         collection = CollectionName.MEMORIES
-        text_fields = _TEXT_FIELDS.get(collection, [])
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
 
         results = await self._dispatch_search(
-            search_mode, collection, query, limit, filters, text_fields
+            search_mode, collection, query, limit, filters, text_fields, fusion
         )
         return [MemoryResult.from_search_result(r) for r in results]
 
@@ -394,20 +521,47 @@ class Searcher(SearcherABC):
         unit_type: str | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        visibility: str | None = None,
+        attribute: str | None = None,
+        crate: str | None = None,
+        fusion: str | None = None,
     ) -> list[CodeResult]:
         if not query or not query.strip():
             return []
 
         _validate_search_mode(search_mode)
 
-        filters = _build_filters(
-            project=project, language=language, unit_type=unit_type
-        )
+        # Inline qualifiers (lang:rust, path:src/...) become filters;
+        # explicit arguments take precedence over them
+        parsed = parse_query(query)
+        if not parsed.text.strip():
+            return []
+
+        filters = {
+            **parsed.filters,
+            **(
+                _build_filters(
+                    project=project,
+                    language=language,
+                    unit_type=unit_type,
+                    visibility=visibility,
+                    attribute_names=attribute,
+                    crate=crate.replace("-", "_") if crate else None,
+                )
+                or {}
+            ),
+        }
         collection = CollectionName.CODE_UNITS
-        text_fields = _TEXT_FIELDS.get(collection, [])
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
 
         results = await self._dispatch_search(
-            search_mode, collection, query, limit, filters, text_fields
+            search_mode,
+            collection,
+            parsed.text,
+            limit,
+            filters or None,
+            text_fields,
+            fusion,
         )
         return [CodeResult.from_search_result(r) for r in results]
 
@@ -424,6 +578,7 @@ class Searcher(SearcherABC):
         outcome: str | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> list[ExperienceResult]:
         if not query or not query.strip():
             return []
@@ -438,10 +593,10 @@ class Searcher(SearcherABC):
         filters = _build_filters(
             domain=domain, strategy=strategy, outcome_status=outcome
         )
-        text_fields = _TEXT_FIELDS.get(collection, [])
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
 
         results = await self._dispatch_search(
-            search_mode, collection, query, limit, filters, text_fields
+            search_mode, collection, query, limit, filters, text_fields, fusion
         )
         return [ExperienceResult.from_search_result(r) for r in results]
 
@@ -455,6 +610,7 @@ class Searcher(SearcherABC):
         axis: str | None = None,
         limit: int = 5,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> list[ValueResult]:
         if not query or not query.strip():
             return []
@@ -463,10 +619,10 @@ class Searcher(SearcherABC):
 
         filters = _build_filters(axis=axis)
         collection = CollectionName.VALUES
-        text_fields = _TEXT_FIELDS.get(collection, [])
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
 
         results = await self._dispatch_search(
-            search_mode, collection, query, limit, filters, text_fields
+            search_mode, collection, query, limit, filters, text_fields, fusion
         )
         return [ValueResult.from_search_result(r) for r in results]
 
@@ -481,6 +637,7 @@ class Searcher(SearcherABC):
         since: datetime | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> list[CommitResult]:
         if not query or not query.strip():
             return []
@@ -489,10 +646,10 @@ class Searcher(SearcherABC):
 
         filters = _build_filters(author=author, committed_at=since)
         collection = CollectionName.COMMITS
-        text_fields = _TEXT_FIELDS.get(collection, [])
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
 
         results = await self._dispatch_search(
-            search_mode, collection, query, limit, filters, text_fields
+            search_mode, collection, query, limit, filters, text_fields, fusion
         )
         return [CommitResult.from_search_result(r) for r in results]
 
@@ -508,8 +665,9 @@ class Searcher(SearcherABC):
         limit: int,
         filters: dict[str, Any] | None,
         text_fields: list[str],
+        fusion: str | None = None,
     ) -> list[SearchResult]:
-        """Route to the correct search strategy based on mode.
+        """Route to the correct search strategy, then rerank if enabled.
 
         Args:
             search_mode: One of "semantic", "keyword", "hybrid".
@@ -518,35 +676,21 @@ class Searcher(SearcherABC):
             limit: Maximum results.
             filters: Optional payload filters.
             text_fields: Payload fields used for keyword matching.
+            fusion: Hybrid fusion method (default from settings).
 
         Returns:
             List of SearchResult.
         """
-        if search_mode == "semantic":
-            return await _semantic_search(
-                self._embedding_service,
-                self._vector_store,
-                collection,
-                query,
-                limit,
-                filters,
-            )
-        elif search_mode == "keyword":
-            return await _keyword_search(
-                self._vector_store,
-                collection,
-                query,
-                limit,
-                filters,
-                text_fields,
-            )
-        else:  # hybrid
-            return await _hybrid_search(
-                self._embedding_service,
-                self._vector_store,
-                collection,
-                query,
-                limit,
-                filters,
-                text_fields,
-            )
+        return await search_collection(
+            self._embedding_service,
+            self._vector_store,
+            search_mode,
+            collection,
+            query,
+            limit,
+            filters,
+            text_fields,
+            fusion=fusion,
+            reranker=self._reranker,
+            rerank_candidates=self._rerank_candidates.get(collection, 0),
+        )
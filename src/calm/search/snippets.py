@@ -0,0 +1,81 @@
+"""Snippet extraction with query-term highlighting for code results."""
+
+from typing import Any
+
+# Lines of context kept above the first matching line
+SNIPPET_CONTEXT_LINES = 2
+
+# Maximum lines in a snippet
+SNIPPET_MAX_LINES = 12
+
+# Query terms shorter than this are too noisy to highlight
+_MIN_TERM_LENGTH = 2
+
+
+def _matching_lines(lines: list[str], query: str) -> list[int]:
+    """Return indexes of lines matching the query.
+
+    Lines containing the whole query win; otherwise any line containing
+    one of the query terms matches.
+    """
+    query_lower = query.lower().strip()
+    if not query_lower:
+        return []
+
+    phrase_matches = [i for i, line in enumerate(lines) if query_lower in line.lower()]
+    if phrase_matches:
+        return phrase_matches
+
+    terms = [t for t in query_lower.split() if len(t) >= _MIN_TERM_LENGTH]
+    return [
+        i
+        for i, line in enumerate(lines)
+        if any(term in line.lower() for term in terms)
+    ]
+
+
+def build_snippet(
+    code: str,
+    query: str,
+    start_line: int,
+    context_lines: int = SNIPPET_CONTEXT_LINES,
+    max_lines: int = SNIPPET_MAX_LINES,
+) -> dict[str, Any] | None:
+    """Build a snippet of a code unit around the lines matching a query.
+
+    Matching lines are marked with ``>`` in the rendered text and listed
+    in ``highlight_lines``. When nothing matches (e.g. a purely semantic
+    hit) the snippet is the head of the unit.
+
+    Args:
+        code: Source of the code unit
+        query: Search query
+        start_line: File line number of the first line of ``code``
+        context_lines: Lines of context above the first match
+        max_lines: Maximum lines in the snippet
+
+    Returns:
+        Dict with ``start_line``, ``end_line``, ``text`` and
+        ``highlight_lines`` (file line numbers), or None for empty code.
+    """
+    lines = code.splitlines()
+    if not lines:
+        return None
+
+    matches = _matching_lines(lines, query)
+    first = max(matches[0] - context_lines, 0) if matches else 0
+    last = min(first + max_lines, len(lines))
+
+    highlighted = {i for i in matches if first <= i < last}
+    width = len(str(start_line + last - 1))
+    rendered = [
+        f"{'>' if i in highlighted else ' '} {start_line + i:>{width}} | {lines[i]}"
+        for i in range(first, last)
+    ]
+
+    return {
+        "start_line": start_line + first,
+        "end_line": start_line + last - 1,
+        "text": "\n".join(rendered),
+        "highlight_lines": sorted(start_line + i for i in highlighted),
+    }
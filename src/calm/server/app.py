@@ -5,6 +5,7 @@ This module provides the MCP server for CALM with full tool registration.
 
 from __future__ import annotations
 
+import contextlib
 import json
 from pathlib import Path
 from typing import TYPE_CHECKING, Any
@@ -14,9 +15,10 @@ from mcp.server import Server
 from mcp.types import TextContent, Tool
 
 from calm.config import settings
+from calm.search.fusion import FUSION_METHODS
 
 if TYPE_CHECKING:
-    from calm.embedding.base import EmbeddingService
+    from calm.embedding.base import EmbeddingService, Reranker
     from calm.storage.base import VectorStore
 
 logger = structlog.get_logger()
@@ -58,6 +60,14 @@ ROOT_CAUSE_CATEGORIES = [
     "timing-issue",
 ]
 
+
+# Input schema of the "fusion" argument shared by the hybrid search tools
+FUSION_SCHEMA = {
+    "type": "string",
+    "description": "Hybrid mode only: rrf (reciprocal rank fusion) or weighted (normalized scores); results include a ranking breakdown. Default from config",
+    "enum": list(FUSION_METHODS),
+}
+
 VALID_AXES = ["full", "strategy", "surprise", "root_cause"]
 
 OUTCOME_STATUS_VALUES = ["confirmed", "falsified", "abandoned"]
@@ -116,6 +126,7 @@ def _get_all_tool_definitions() -> list[Tool]:
                         "enum": ["semantic", "keyword", "hybrid"],
                         "default": "semantic",
                     },
+                    "fusion": FUSION_SCHEMA,
                 },
                 "required": ["query"],
             },
@@ -157,6 +168,12 @@ def _get_all_tool_definitions() -> list[Tool]:
                     "directory": {"type": "string", "description": "Absolute path to directory"},
                     "project": {"type": "string", "description": "Project identifier"},
                     "recursive": {"type": "boolean", "description": "Recurse subdirectories (default True)", "default": True},
+                    "exclude": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Gitignore-style patterns to exclude (saved for the project; omit to reuse saved patterns)",
+                    },
+                    "respect_gitignore": {"type": "boolean", "description": "Honor .gitignore, .git/info/exclude and global git excludes (saved for the project; default True)"},
                 },
                 "required": ["directory", "project"],
             },
@@ -167,7 +184,7 @@ def _get_all_tool_definitions() -> list[Tool]:
             inputSchema={
                 "type": "object",
                 "properties": {
-                    "query": {"type": "string", "description": "Search query"},
+                    "query": {"type": "string", "description": "Search query; may include lang:rust, path:src/indexers, type:method qualifiers and file globs like *.rs"},
                     "project": {"type": "string", "description": "Optional project filter"},
                     "language": {"type": "string", "description": "Optional language filter (python, typescript, etc.)"},
                     "limit": {"type": "integer", "description": "Max results (1-50, default 10)", "default": 10},
@@ -177,6 +194,10 @@ def _get_all_tool_definitions() -> list[Tool]:
                         "enum": ["semantic", "keyword", "hybrid"],
                         "default": "semantic",
                     },
+                    "fusion": FUSION_SCHEMA,
+                    "visibility": {"type": "string", "description": "Optional visibility filter (e.g. pub, pub(crate), private)"},
+                    "attribute": {"type": "string", "description": "Optional attribute filter (e.g. derive, cfg, test)"},
+                    "crate": {"type": "string", "description": "Optional Cargo crate name filter"},
                 },
                 "required": ["query"],
             },
@@ -194,6 +215,34 @@ def _get_all_tool_definitions() -> list[Tool]:
                 "required": ["snippet"],
             },
         ),
+        Tool(
+            name="get_callers",
+            description="Find functions and methods that call a code unit.",
+            inputSchema={
+                "type": "object",
+                "properties": {
+                    "qualified_name": {"type": "string", "description": "Qualified name of the called unit (e.g. module.Class.method)"},
+                    "project": {"type": "string", "description": "Project identifier"},
+                    "depth": {"type": "integer", "description": "Levels of indirect callers to include (1-5, default 1)", "default": 1},
+                    "limit": {"type": "integer", "description": "Max results (1-200, default 50)", "default": 50},
+                },
+                "required": ["qualified_name", "project"],
+            },
+        ),
+        Tool(
+            name="get_callees",
+            description="Find functions and methods called by a code unit.",
+            inputSchema={
+                "type": "object",
+                "properties": {
+                    "qualified_name": {"type": "string", "description": "Qualified name of the calling unit (e.g. module.Class.method)"},
+                    "project": {"type": "string", "description": "Project identifier"},
+                    "depth": {"type": "integer", "description": "Levels of indirect callees to include (1-5, default 1)", "default": 1},
+                    "limit": {"type": "integer", "description": "Max results (1-200, default 50)", "default": 50},
+                },
+                "required": ["qualified_name", "project"],
+            },
+        ),
         # === Git Tools ===
         Tool(
             name="index_commits",
@@ -356,6 +405,7 @@ def _get_all_tool_definitions() -> list[Tool]:
                         "enum": ["semantic", "keyword", "hybrid"],
                         "default": "semantic",
                     },
+                    "fusion": FUSION_SCHEMA,
                 },
                 "required": ["query"],
             },
@@ -437,6 +487,31 @@ def _get_all_tool_definitions() -> list[Tool]:
                 "required": ["query"],
             },
         ),
+        # === Federated Search Tools ===
+        Tool(
+            name="search_all",
+            description="Search memories, code, experiences, values and commits at once, interleaving results.",
+            inputSchema={
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "Search query"},
+                    "sources": {
+                        "type": "array",
+                        "items": {"type": "string", "enum": ["memories", "code", "experiences", "values", "commits"]},
+                        "description": "Sources to search (default: all)",
+                    },
+                    "limit": {"type": "integer", "description": "Max results overall (1-50, default 10)", "default": 10},
+                    "search_mode": {
+                        "type": "string",
+                        "description": "Search mode: semantic (vector similarity), keyword (text matching), or hybrid (both)",
+                        "enum": ["semantic", "keyword", "hybrid"],
+                        "default": "semantic",
+                    },
+                    "project": {"type": "string", "description": "Optional project filter (applies to code)"},
+                },
+                "required": ["query"],
+            },
+        ),
         # === Session Journal Tools ===
         Tool(
             name="store_journal_entry",
@@ -550,12 +625,20 @@ def _get_all_tool_definitions() -> list[Tool]:
     ]
 
 
-async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]:
+async def create_server(
+    use_mock: bool = False,
+    watch: bool = False,
+    exit_stack: contextlib.AsyncExitStack | None = None,
+) -> tuple[Server, dict[str, Any]]:
     """Create the CALM MCP server with all tools registered.
 
     Args:
         use_mock: If True, use MockEmbeddingService + MemoryStore (for tests).
                   If False, use real Qdrant + embedding models.
+        watch: If True (real services only), watch registered projects and
+               re-index changed files as they are saved.
+        exit_stack: Receives cleanup callbacks (such as stopping the
+                    watcher) to run when the server shuts down.
 
     Returns:
         Tuple of (Server, tool_registry). tool_registry maps tool names
@@ -571,6 +654,7 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
         get_journal_tools,
         get_learning_tools,
         get_memory_tools,
+        get_search_tools,
     )
 
     server = Server("calm", version=__version__)
@@ -581,6 +665,8 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
     experience_clusterer = None
     value_store_instance = None
     context_assembler = None
+    watcher = None
+    reranker: Reranker | None = None
 
     vector_store: VectorStore
     semantic_embedder: EmbeddingService
@@ -599,20 +685,45 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
 
         from calm.clustering import Clusterer, ExperienceClusterer
         from calm.context import ContextAssembler
+        from calm.embedding.migration import ReembedGuard
         from calm.embedding.registry import EmbeddingRegistry
         from calm.indexers import CodeIndexer, TreeSitterParser
-        from calm.search.searcher import Searcher
+        from calm.search.searcher import KEYWORD_TEXT_FIELDS, Searcher
+        from calm.storage.keyword_index import KeywordIndex, KeywordIndexedStore
         from calm.storage.metadata import MetadataStore
-        from calm.storage.qdrant import QdrantVectorStore
         from calm.values import ValueStore
 
-        # Real vector store (Qdrant)
-        vector_store = QdrantVectorStore(url=settings.qdrant_url)
+        # Real vector store (Qdrant server, or embedded SQLite)
+        if settings.vector_store == "sqlite":
+            from calm.storage.sqlite import SqliteVectorStore
+
+            vector_store = SqliteVectorStore(settings.vector_db_path)
+        else:
+            from calm.storage.qdrant import QdrantVectorStore
+
+            vector_store = QdrantVectorStore(url=settings.qdrant_url)
+
+        # Holds back writes and vector searches to stale collections until
+        # the startup job has re-embedded them with the configured models
+        reembed_guard: ReembedGuard | None = None
+        if settings.reembed_on_startup:
+            reembed_guard = ReembedGuard(vector_store)
+            vector_store = reembed_guard
+
+        # BM25 keyword index, maintained on every upsert/delete
+        vector_store = KeywordIndexedStore(
+            vector_store,
+            KeywordIndex(settings.keyword_index_path, KEYWORD_TEXT_FIELDS),
+        )
 
         # Real embedders (loaded lazily on first embed() call)
         registry = EmbeddingRegistry(
             code_model=settings.code_model,
             semantic_model=settings.semantic_model,
+            rerank_model=settings.rerank.model,
+            code_provider=settings.code_provider,
+            semantic_provider=settings.semantic_provider,
+            provider_options=settings.embedding_provider_options,
         )
         semantic_embedder = registry.get_semantic_embedder()
         code_embedder = registry.get_code_embedder()
@@ -660,22 +771,66 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
             clusterer=experience_clusterer,
         )
 
-        # Search + context assembly
+        # Search + context assembly (the reranker only loads if configured,
+        # and is shared with the search tools)
+        if settings.rerank.collections:
+            reranker = registry.get_reranker()
         searcher = Searcher(
             embedding_service=semantic_embedder,
             vector_store=vector_store,
+            reranker=reranker,
         )
         context_assembler = ContextAssembler(searcher=searcher)
 
+        # Re-embed collections written with another model in the background.
+        # Until a stale collection is swapped, its writes are queued and
+        # vector searches on it are rejected (keyword search still works).
+        if reembed_guard:
+            from calm.embedding.migration import Reembedder, start_reembed_job
+
+            start_reembed_job(
+                Reembedder(
+                    vector_store,
+                    metadata_store,
+                    code_embedder,
+                    semantic_embedder,
+                    settings.code_model,
+                    settings.semantic_model,
+                    guard=reembed_guard,
+                )
+            )
+
+        # Watch mode: incremental re-indexing of registered projects
+        if watch:
+            from calm.server.watcher import ProjectWatcher
+
+            watcher = ProjectWatcher(code_indexer)
+            try:
+                await watcher.start()
+            except Exception as e:
+                logger.warning("watcher.start_failed", error=str(e))
+                watcher = None
+            else:
+                if exit_stack is not None:
+                    exit_stack.push_async_callback(watcher.stop)
+
     # Build tool registry
     tool_registry: dict[str, Any] = {}
 
     # Memory tools
-    tool_registry.update(get_memory_tools(vector_store, semantic_embedder))
+    tool_registry.update(
+        get_memory_tools(vector_store, semantic_embedder, reranker=reranker)
+    )
 
     # Code tools
     tool_registry.update(
-        get_code_tools(vector_store, code_embedder, code_indexer=code_indexer)
+        get_code_tools(
+            vector_store,
+            code_embedder,
+            code_indexer=code_indexer,
+            watcher=watcher,
+            reranker=reranker,
+        )
     )
 
     # Git tools
@@ -700,6 +855,7 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
             semantic_embedder,
             experience_clusterer=experience_clusterer,
             value_store=value_store_instance,
+            reranker=reranker,
         )
     )
 
@@ -712,6 +868,13 @@ async def create_server(use_mock: bool = False) -> tuple[Server, dict[str, Any]]
         )
     )
 
+    # Federated search tools
+    tool_registry.update(
+        get_search_tools(
+            vector_store, semantic_embedder, code_embedder, reranker=reranker
+        )
+    )
+
     # Journal tools
     tool_registry.update(get_journal_tools())
 
@@ -93,11 +93,14 @@ def is_server_running() -> bool:
         return False
 
 
-def start_daemon() -> None:
+def start_daemon(watch: bool = False) -> None:
     """Start the CALM server as a background daemon.
 
     Uses subprocess.Popen to avoid fork issues on macOS.
     The parent process exits immediately, leaving the daemon running.
+
+    Args:
+        watch: Re-index registered projects as their files change
     """
     log_file = get_log_file()
     log_file.parent.mkdir(parents=True, exist_ok=True)
@@ -109,6 +112,8 @@ def start_daemon() -> None:
         "--host", settings.server_host,
         "--port", str(settings.server_port),
     ]
+    if watch:
+        cmd.append("--watch")
 
     # Open log file for output
     with open(log_file, "w") as log_out:
@@ -131,13 +136,13 @@ def start_daemon() -> None:
     print(f"Log file: {log_file}")
 
 
-def run_foreground() -> None:
+def run_foreground(watch: bool = False) -> None:
     """Run the server in the foreground (for debugging).
 
     This runs the server in the current process without daemonizing.
     """
     from calm.server.main import run_server
-    run_server(settings.server_host, settings.server_port)
+    run_server(settings.server_host, settings.server_port, watch)
 
 
 def stop_server() -> bool:
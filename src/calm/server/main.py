@@ -42,20 +42,26 @@ def parse_args() -> argparse.Namespace:
         default=6335,
         help="HTTP server port (default: 6335)",
     )
+    parser.add_argument(
+        "--watch",
+        action="store_true",
+        help="Re-index registered projects as files change",
+    )
     return parser.parse_args()
 
 
-def run_server(host: str, port: int) -> None:
+def run_server(host: str, port: int, watch: bool = False) -> None:
     """Run the CALM server.
 
     Args:
         host: Host to bind to
         port: Port to bind to
+        watch: Watch registered projects for file changes
     """
-    asyncio.run(_run_server_async(host, port))
+    asyncio.run(_run_server_async(host, port, watch))
 
 
-async def _run_server_async(host: str, port: int) -> None:
+async def _run_server_async(host: str, port: int, watch: bool = False) -> None:
     """Run the server asynchronously."""
     import structlog
     import uvicorn
@@ -73,10 +79,15 @@ async def _run_server_async(host: str, port: int) -> None:
 
     logger = structlog.get_logger()
 
-    logger.info("calm.starting", version=__version__, host=host, port=port)
+    logger.info(
+        "calm.starting", version=__version__, host=host, port=port, watch=watch
+    )
 
-    # Create MCP server
-    mcp_server, _tool_registry = await create_server()
+    # Create MCP server; cleanup (such as stopping the watcher) runs on exit
+    exit_stack = contextlib.AsyncExitStack()
+    mcp_server, _tool_registry = await create_server(
+        watch=watch, exit_stack=exit_stack
+    )
 
     # Create Streamable HTTP session manager.
     # The session manager handles session creation, tracking, and
@@ -156,7 +167,8 @@ async def _run_server_async(host: str, port: int) -> None:
     logger.info("calm.server_ready", host=host, port=port)
 
     try:
-        await server.serve()
+        async with exit_stack:
+            await server.serve()
     finally:
         # Clean up PID file
         if pid_file.exists():
@@ -172,7 +184,7 @@ async def _run_server_async(host: str, port: int) -> None:
 def main() -> None:
     """Entry point when run as a module."""
     args = parse_args()
-    run_server(args.host, args.port)
+    run_server(args.host, args.port, args.watch)
 
 
 if __name__ == "__main__":
@@ -0,0 +1,297 @@
+"""File-watcher mode for incremental re-indexing of registered projects.
+
+The daemon subscribes to filesystem events for every project recorded in
+the ``projects`` table (inotify on Linux, via watchdog), collapses bursts of
+events into a single batch once the tree has been quiet for a debounce
+period, then re-indexes changed files through ``CodeIndexer.index_file`` and
+removes units for deleted or renamed-away files. A moved, deleted or created
+directory is expanded into the indexed files under it and the files now in
+it. Projects indexed while the server runs are added with ``watch_project``.
+
+watchdog delivers events on its own thread; they are handed to the event
+loop with ``call_soon_threadsafe`` so all indexing happens on the loop.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import os
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+import structlog
+
+from calm.config import settings
+from calm.indexers.ignore import ExclusionSettings
+
+if TYPE_CHECKING:
+    from calm.indexers.indexer import CodeIndexer
+    from calm.storage.metadata import ProjectConfig
+
+logger = structlog.get_logger(__name__)
+
+
+class ProjectWatcher:
+    """Watch registered project roots and keep their index up to date."""
+
+    def __init__(
+        self,
+        code_indexer: CodeIndexer,
+        debounce_seconds: float | None = None,
+    ) -> None:
+        self.code_indexer = code_indexer
+        self.debounce_seconds = (
+            settings.indexer.watch_debounce_seconds
+            if debounce_seconds is None
+            else debounce_seconds
+        )
+        # project name -> root path, normalized as the indexer records it
+        self.roots: dict[str, str] = {}
+        # project name -> stored exclusion settings
+        self.exclusions: dict[str, ExclusionSettings] = {}
+        # (project, path) -> True if the file was deleted
+        self._pending: dict[tuple[str, str], bool] = {}
+        # (project, path) of directories moved, deleted or created
+        self._pending_dirs: set[tuple[str, str]] = set()
+        self._wake = asyncio.Event()
+        self._loop: asyncio.AbstractEventLoop | None = None
+        self._observer: Any = None
+        # project name -> watchdog watch of its root
+        self._watches: dict[str, Any] = {}
+        self._task: asyncio.Task[None] | None = None
+
+    async def start(self) -> list[str]:
+        """Subscribe to filesystem events for all registered projects.
+
+        Returns:
+            Names of the projects being watched
+        """
+        # Lazy import: watchdog starts native observer threads
+        from watchdog.observers import Observer
+
+        self._loop = asyncio.get_running_loop()
+        self._observer = Observer()
+
+        for project, root in (await self.load_projects()).items():
+            self._schedule(project, root)
+
+        self._observer.start()
+        self._task = asyncio.create_task(self._run())
+        logger.info(
+            "watcher.started",
+            projects=sorted(self.roots),
+            debounce_seconds=self.debounce_seconds,
+        )
+        return sorted(self.roots)
+
+    async def load_projects(self) -> dict[str, str]:
+        """Load project roots from the ``projects`` table.
+
+        Returns:
+            Mapping of project name to root directory
+        """
+        projects = await self.code_indexer.metadata_store.list_projects()
+        for project in projects:
+            self._load_project(project)
+        return dict(self.roots)
+
+    async def watch_project(self, project: str) -> bool:
+        """Follow a project registered (or re-rooted) after ``start``.
+
+        Args:
+            project: Project name in the ``projects`` table
+
+        Returns:
+            True if the project is being watched
+        """
+        if self._observer is None:
+            return False
+        record = await self.code_indexer.metadata_store.get_project(project)
+        if record is None:
+            return False
+
+        previous = self.roots.get(project)
+        root = self._load_project(record)
+        if root is None:
+            return False
+        if root != previous or project not in self._watches:
+            if project in self._watches:
+                self._observer.unschedule(self._watches.pop(project))
+            self._schedule(project, root)
+            logger.info("watcher.project_added", project=project, root=root)
+        return True
+
+    def _load_project(self, project: ProjectConfig) -> str | None:
+        """Record a project's root and exclusions.
+
+        The root is not resolved: watchdog reports paths under the watched
+        root as given, and they must match the file paths the indexer
+        recorded, including through a symlinked root.
+
+        Returns:
+            The root, or None if it is not a directory
+        """
+        root = self.code_indexer.normalize_path(project.root_path)
+        if not Path(root).is_dir():
+            logger.warning("watcher.root_missing", project=project.name, root=root)
+            return None
+        self.roots[project.name] = root
+        self.exclusions[project.name] = ExclusionSettings.from_settings(
+            project.settings
+        )
+        return self.roots[project.name]
+
+    def _schedule(self, project: str, root: str) -> None:
+        self._watches[project] = self._observer.schedule(
+            _EventHandler(self, project), root, recursive=True
+        )
+
+    async def stop(self) -> None:
+        """Stop watching and flush any pending changes."""
+        if self._observer is not None:
+            self._observer.stop()
+            await asyncio.to_thread(self._observer.join)
+            self._observer = None
+            self._watches.clear()
+        if self._task is not None:
+            self._task.cancel()
+            try:
+                await self._task
+            except asyncio.CancelledError:
+                pass
+            self._task = None
+        await self.flush()
+        logger.info("watcher.stopped")
+
+    def on_change(
+        self,
+        project: str,
+        path: str,
+        deleted: bool = False,
+        directory: bool = False,
+    ) -> None:
+        """Record a filesystem change (safe to call from any thread)."""
+        if self._loop is not None and not self._in_loop_thread():
+            self._loop.call_soon_threadsafe(
+                self._record, project, path, deleted, directory
+            )
+        else:
+            self._record(project, path, deleted, directory)
+
+    def _in_loop_thread(self) -> bool:
+        try:
+            return asyncio.get_running_loop() is self._loop
+        except RuntimeError:
+            return False
+
+    def _record(
+        self, project: str, path: str, deleted: bool, directory: bool = False
+    ) -> None:
+        if directory:
+            self._pending_dirs.add((project, path))
+        else:
+            self._pending[(project, path)] = deleted
+        self._wake.set()
+
+    async def _run(self) -> None:
+        """Wait for events, debounce, then flush."""
+        while True:
+            await self._wake.wait()
+            # Keep waiting while events are still arriving
+            while self._wake.is_set():
+                self._wake.clear()
+                await asyncio.sleep(self.debounce_seconds)
+            try:
+                await self.flush()
+            except Exception as e:
+                logger.error("watcher.flush_failed", error=str(e), exc_info=True)
+
+    async def flush(self) -> dict[str, int]:
+        """Apply all pending changes.
+
+        Returns:
+            Counts of files indexed and removed
+        """
+        pending, self._pending = self._pending, {}
+        directories, self._pending_dirs = self._pending_dirs, set()
+        for project, directory in directories:
+            for path in await self._directory_files(project, directory):
+                # Gone files are removed below, present ones re-indexed
+                pending.setdefault((project, path), False)
+
+        counts = {"indexed": 0, "removed": 0}
+        touched: set[str] = set()
+
+        for (project, path), deleted in pending.items():
+            root = self.roots.get(project)
+            if root is None:
+                continue
+
+            indexer = self.code_indexer
+            if deleted or not Path(path).is_file():
+                if await indexer.is_file_indexed(path, project):
+                    await indexer.remove_file(path, project)
+                    counts["removed"] += 1
+                    touched.add(project)
+                continue
+
+            if not indexer.should_index(
+                path, root, exclusions=self.exclusions.get(project)
+            ):
+                continue
+            try:
+                stats = await indexer.index_file(path, project, update_calls=False)
+            except Exception as e:
+                logger.warning("watcher.index_failed", path=path, error=str(e))
+                continue
+            if stats.files_indexed:
+                counts["indexed"] += 1
+                touched.add(project)
+
+        for project in touched:
+            await self.code_indexer.update_call_graph(project)
+
+        if touched:
+            logger.info("watcher.flushed", projects=sorted(touched), **counts)
+        return counts
+
+
+    async def _directory_files(self, project: str, directory: str) -> list[str]:
+        """Indexed files under a directory, plus the files now in it."""
+        metadata_store = self.code_indexer.metadata_store
+        files = [
+            indexed.file_path
+            for indexed in await metadata_store.list_indexed_files(project)
+            if Path(indexed.file_path).is_relative_to(directory)
+        ]
+        excluded_dirs = self.code_indexer.DEFAULT_EXCLUDED_DIRS
+        for dirpath, dirnames, filenames in os.walk(directory):
+            dirnames[:] = [d for d in dirnames if d not in excluded_dirs]
+            files.extend(os.path.join(dirpath, name) for name in filenames)
+        return files
+
+
+class _EventHandler:
+    """watchdog event handler forwarding file events for one project."""
+
+    def __init__(self, watcher: ProjectWatcher, project: str) -> None:
+        self.watcher = watcher
+        self.project = project
+
+    def dispatch(self, event: Any) -> None:
+        if event.is_directory:
+            # One event stands for everything under the directory
+            if event.event_type == "moved":
+                self.watcher.on_change(self.project, event.src_path, directory=True)
+                self.watcher.on_change(self.project, event.dest_path, directory=True)
+            elif event.event_type in ("deleted", "created"):
+                self.watcher.on_change(self.project, event.src_path, directory=True)
+            return
+        if event.event_type == "moved":
+            # A rename is a delete of the old path plus a change at the new one
+            self.watcher.on_change(self.project, event.src_path, deleted=True)
+            self.watcher.on_change(self.project, event.dest_path)
+        elif event.event_type == "deleted":
+            self.watcher.on_change(self.project, event.src_path, deleted=True)
+        elif event.event_type in ("created", "modified", "closed"):
+            self.watcher.on_change(self.project, event.src_path)
@@ -1,9 +1,11 @@
 """Storage abstractions for CALM."""
 
 from .base import SearchResult, VectorStore
+from .filters import FilterError
 from .memory import MemoryStore
 from .metadata import IndexedFile, MetadataStore
 from .qdrant import QdrantVectorStore
+from .sqlite import SqliteVectorStore
 
 __all__ = [
     "VectorStore",
@@ -12,4 +14,6 @@ __all__ = [
     "MetadataStore",
     "IndexedFile",
     "MemoryStore",
+    "SqliteVectorStore",
+    "FilterError",
 ]
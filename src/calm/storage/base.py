@@ -1,6 +1,7 @@
 """Base classes and types for vector storage."""
 
 from abc import ABC, abstractmethod
+from collections.abc import AsyncIterator
 from dataclasses import dataclass
 from typing import Any
 
@@ -19,6 +20,8 @@ class SearchResult:
     score: float
     payload: dict[str, Any]
     vector: Vector | None = None
+    # Hybrid search score breakdown (see calm.search.fusion)
+    ranking: dict[str, Any] | None = None
 
 
 @dataclass
@@ -55,6 +58,20 @@ class VectorStore(ABC):
         """
         pass
 
+    @abstractmethod
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        """Atomically replace a collection with another one.
+
+        Afterwards ``name`` serves the points of ``replacement`` and the old
+        points are gone. Used to switch to a fully re-embedded shadow
+        collection without a window where searches see partial data.
+
+        Args:
+            name: Collection to replace (need not exist yet)
+            replacement: Collection holding the new points
+        """
+        pass
+
     @abstractmethod
     async def upsert(
         self,
@@ -73,6 +90,27 @@ class VectorStore(ABC):
         """
         pass
 
+    @abstractmethod
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        """Insert or update many vectors at once.
+
+        Args:
+            collection: Collection name
+            ids: Unique identifiers, one per vector
+            vectors: Vectors to store
+            payloads: Metadata for each vector
+
+        Raises:
+            ValueError: If the three lists differ in length
+        """
+        pass
+
     @abstractmethod
     async def search(
         self,
@@ -125,6 +163,32 @@ class VectorStore(ABC):
         """
         pass
 
+    async def scroll_batches(
+        self,
+        collection: str,
+        batch_size: int = 256,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> AsyncIterator[list[SearchResult]]:
+        """Iterate over every vector of a collection in bounded batches.
+
+        The default implementation reads the collection with a single
+        ``scroll``; stores that can page through it override this.
+
+        Args:
+            collection: Collection name
+            batch_size: Maximum number of results per batch
+            filters: Optional filters on payload fields
+            with_vectors: Whether to include vector data
+
+        Yields:
+            Lists of at most ``batch_size`` results
+        """
+        total = await self.count(collection, filters)
+        results = await self.scroll(collection, max(total, 1), filters, with_vectors)
+        for start in range(0, len(results), batch_size):
+            yield results[start : start + batch_size]
+
     @abstractmethod
     async def count(
         self, collection: str, filters: dict[str, Any] | None = None
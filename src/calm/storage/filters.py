@@ -0,0 +1,266 @@
+"""Payload filter language shared by all vector stores.
+
+Filters are dicts whose keys are payload fields (dotted paths reach into
+nested objects, e.g. ``"meta.language"``) or logical operators. All
+top-level conditions must hold.
+
+Field conditions:
+    {"field": value}                      equality (any element, for lists)
+    {"field": {"$in": [a, b]}}            value is one of a, b
+    {"field": {"$nin": [a, b]}}           value is none of a, b (or missing)
+    {"field": {"$gte": x, "$lt": y}}      range; also $gt and $lte
+    {"field": {"$exists": True}}          field is present, non-null, non-empty
+    {"field": {"$contains": "rust"}}      list field has the element
+    {"field": {"$contains": [a, b]}}      list field has every element
+    {"field": {"$glob": "src/**/*.rs"}}   path matches a glob (see below)
+
+Logical operators:
+    {"$or": [filter, ...]}                at least one filter holds
+    {"$not": filter}                      the filter does not hold
+
+Globs use ``*`` and ``?`` within a path segment and ``**`` across
+segments. A pattern without a leading ``/`` may start at any segment, and
+a pattern naming a directory matches everything below it, so
+``src/indexers`` matches ``/repo/src/indexers/base.py``.
+
+Filters are parsed into a small AST (:func:`parse_filters`), which
+``MemoryStore`` and ``SqliteVectorStore`` evaluate directly and
+``QdrantVectorStore`` translates to ``must``/``should``/``must_not``
+clauses. Anything else raises :class:`FilterError`.
+"""
+
+import re
+from dataclasses import dataclass
+from functools import lru_cache
+from typing import Any
+
+RANGE_OPERATORS = ("$gt", "$gte", "$lt", "$lte")
+FIELD_OPERATORS = (
+    "$in",
+    "$nin",
+    "$exists",
+    "$contains",
+    "$glob",
+    *RANGE_OPERATORS,
+)
+LOGICAL_OPERATORS = ("$or", "$not")
+
+
+class FilterError(ValueError):
+    """Raised when a filter is malformed or uses an unsupported operator."""
+
+    pass
+
+
+@dataclass(frozen=True)
+class FieldCondition:
+    """A single condition on a payload field.
+
+    ``op`` is ``"$eq"``, ``"$range"`` (``value`` maps bound operators to
+    limits) or one of the field operators.
+    """
+
+    key: str
+    op: str
+    value: Any
+
+
+@dataclass(frozen=True)
+class And:
+    """All children hold."""
+
+    children: tuple["FilterNode", ...]
+
+
+@dataclass(frozen=True)
+class Or:
+    """At least one child holds."""
+
+    children: tuple["FilterNode", ...]
+
+
+@dataclass(frozen=True)
+class Not:
+    """The child does not hold."""
+
+    child: "FilterNode"
+
+
+FilterNode = FieldCondition | And | Or | Not
+
+
+def parse_filters(filters: dict[str, Any]) -> And:
+    """Parse and validate a filter dict.
+
+    Raises:
+        FilterError: If the filter is malformed or uses an unknown operator
+    """
+    if not isinstance(filters, dict):
+        raise FilterError(f"Filter must be a dict, got {type(filters).__name__}")
+
+    children: list[FilterNode] = []
+    for key, value in filters.items():
+        if key == "$or":
+            if not isinstance(value, list) or not value:
+                raise FilterError("$or requires a non-empty list of filters")
+            children.append(Or(tuple(parse_filters(f) for f in value)))
+        elif key == "$not":
+            children.append(Not(parse_filters(value)))
+        elif key.startswith("$"):
+            raise FilterError(
+                f"Unsupported logical operator {key!r} "
+                f"(supported: {', '.join(LOGICAL_OPERATORS)})"
+            )
+        elif isinstance(value, dict):
+            children.extend(_parse_operators(key, value))
+        else:
+            children.append(FieldCondition(key, "$eq", value))
+    return And(tuple(children))
+
+
+def _parse_operators(key: str, operators: dict[str, Any]) -> list[FilterNode]:
+    """Parse the operator dict of one field."""
+    if not operators:
+        raise FilterError(f"Empty operator dict for field {key!r}")
+
+    conditions: list[FilterNode] = []
+    bounds: dict[str, Any] = {}
+    for op, value in operators.items():
+        if op not in FIELD_OPERATORS:
+            raise FilterError(
+                f"Unsupported operator {op!r} for field {key!r} "
+                f"(supported: {', '.join(FIELD_OPERATORS)})"
+            )
+        if op in RANGE_OPERATORS:
+            bounds[op] = value
+        elif op in ("$in", "$nin"):
+            if not isinstance(value, list):
+                raise FilterError(f"{op} for field {key!r} requires a list")
+            conditions.append(FieldCondition(key, op, tuple(value)))
+        elif op == "$exists":
+            if not isinstance(value, bool):
+                raise FilterError(f"$exists for field {key!r} requires a bool")
+            conditions.append(FieldCondition(key, op, value))
+        elif op == "$glob":
+            if not isinstance(value, str) or not value.strip("/"):
+                raise FilterError(f"$glob for field {key!r} requires a pattern")
+            conditions.append(FieldCondition(key, op, value))
+        else:  # $contains
+            values = tuple(value) if isinstance(value, list) else (value,)
+            conditions.append(FieldCondition(key, op, values))
+
+    if bounds:
+        conditions.insert(0, FieldCondition(key, "$range", bounds))
+    return conditions
+
+
+def matches_filters(payload: dict[str, Any], filters: dict[str, Any] | None) -> bool:
+    """Check whether a payload satisfies every filter condition."""
+    if not filters:
+        return True
+    return evaluate(parse_filters(filters), payload)
+
+
+def evaluate(node: FilterNode, payload: dict[str, Any]) -> bool:
+    """Evaluate a parsed filter against a payload."""
+    if isinstance(node, And):
+        return all(evaluate(child, payload) for child in node.children)
+    if isinstance(node, Or):
+        return any(evaluate(child, payload) for child in node.children)
+    if isinstance(node, Not):
+        return not evaluate(node.child, payload)
+    return _evaluate_condition(node, field_values(payload, node.key))
+
+
+def _evaluate_condition(condition: FieldCondition, values: list[Any]) -> bool:
+    """Evaluate a field condition against the field's values."""
+    op = condition.op
+    if op == "$exists":
+        present = any(v is not None for v in values)
+        return present == condition.value
+    if op == "$nin":
+        return not any(v in condition.value for v in values)
+    if op == "$eq":
+        return condition.value in values
+    if op == "$in":
+        return any(v in condition.value for v in values)
+    if op == "$contains":
+        return all(v in values for v in condition.value)
+    if op == "$glob":
+        regex = glob_regex(condition.value)
+        return any(isinstance(v, str) and regex.search(v) for v in values)
+    # $range: any value within all bounds (mirrors Qdrant for list payloads)
+    return any(_in_range(v, condition.value) for v in values)
+
+
+def _in_range(value: Any, bounds: dict[str, Any]) -> bool:
+    try:
+        return all(
+            (op != "$gt" or value > limit)
+            and (op != "$gte" or value >= limit)
+            and (op != "$lt" or value < limit)
+            and (op != "$lte" or value <= limit)
+            for op, limit in bounds.items()
+        )
+    except TypeError:
+        return False
+
+
+@lru_cache(maxsize=256)
+def glob_regex(pattern: str) -> re.Pattern[str]:
+    """Compile a path glob to a regex for ``re.search``."""
+    anchored = pattern.startswith("/")
+    pattern = pattern.strip("/")
+    out: list[str] = []
+    i = 0
+    while i < len(pattern):
+        if pattern.startswith("**/", i):
+            out.append("(?:.*/)?")
+            i += 3
+        elif pattern.startswith("**", i):
+            out.append(".*")
+            i += 2
+        elif pattern[i] == "*":
+            out.append("[^/]*")
+            i += 1
+        elif pattern[i] == "?":
+            out.append("[^/]")
+            i += 1
+        else:
+            out.append(re.escape(pattern[i]))
+            i += 1
+    prefix = "^/" if anchored else "(?:^|/)"
+    return re.compile(prefix + "".join(out) + "(?:/|$)")
+
+
+def glob_literals(pattern: str) -> list[str]:
+    """The wildcard-free parts of a glob, e.g. ``["src/", ".rs"]``."""
+    return [part for part in re.split(r"\*+|\?", pattern) if part.strip("/")]
+
+
+def field_values(payload: dict[str, Any], key: str) -> list[Any]:
+    """Collect the values of a (possibly dotted) field.
+
+    List values are flattened, as in Qdrant, so ``tags`` holding
+    ``["a", "b"]`` gives ``["a", "b"]`` and ``items.name`` over a list of
+    objects gives every object's ``name``. A missing field gives ``[]``.
+    """
+    if key in payload:
+        current: list[Any] = [payload[key]]
+    else:
+        current = [payload]
+        for part in key.split("."):
+            current = [
+                item[part]
+                for value in current
+                for item in (value if isinstance(value, list) else [value])
+                if isinstance(item, dict) and part in item
+            ]
+
+    values: list[Any] = []
+    for value in current:
+        if isinstance(value, list):
+            values.extend(value)
+        else:
+            values.append(value)
+    return values
@@ -0,0 +1,491 @@
+"""Persistent BM25 keyword index kept in step with a vector store.
+
+``KeywordIndex`` is an inverted index in SQLite (``~/.calm/keyword.db`` by
+default): per collection, a posting list of term frequencies and each
+document's length and indexed text. Text is tokenized code-aware, so
+``parse_file``, ``ParseFile`` and ``Parser::parse_file`` all yield the
+terms ``parse`` and ``file``.
+
+``KeywordIndexedStore`` wraps any ``VectorStore`` and updates the index on
+every upsert and delete, so keyword search sees the whole collection
+instead of a capped scroll. Payloads are read back from the vector store
+by ID for each hit, and a hit whose point is gone or whose text changed
+behind the wrapper's back is re-indexed from the store. A collection
+written before the index existed (or whose point count no longer matches
+the index) is backfilled from the vector store page by page.
+"""
+
+import math
+import re
+from collections import Counter
+from collections.abc import AsyncIterator
+from pathlib import Path
+from typing import Any
+
+import aiosqlite
+import structlog
+
+from .base import CollectionInfo, SearchResult, Vector, VectorStore
+from .filters import FilterNode, evaluate, parse_filters
+
+logger = structlog.get_logger(__name__)
+
+# BM25 term-frequency saturation and length normalization
+BM25_K1 = 1.2
+BM25_B = 0.75
+
+# Identifier-like runs of word characters
+_WORD_RE = re.compile(r"\w+")
+
+# Sub-words of a CamelCase/camelCase identifier, e.g. "HTTPServer2" ->
+# "HTTP", "Server", "2"
+_CAMEL_RE = re.compile(r"[A-Z]+(?=[A-Z][a-z])|[A-Z]?[a-z]+|[A-Z]+|\d+")
+
+# SQLite host-parameter limit headroom for IN (...) queries
+_IN_CHUNK_SIZE = 500
+
+KEYWORD_TABLES = [
+    """
+    CREATE TABLE IF NOT EXISTS keyword_collections (
+        collection TEXT PRIMARY KEY
+    );
+    """,
+    """
+    CREATE TABLE IF NOT EXISTS keyword_docs (
+        collection TEXT NOT NULL,
+        doc_id TEXT NOT NULL,
+        length INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        PRIMARY KEY (collection, doc_id)
+    );
+    """,
+    """
+    CREATE TABLE IF NOT EXISTS keyword_postings (
+        collection TEXT NOT NULL,
+        term TEXT NOT NULL,
+        doc_id TEXT NOT NULL,
+        tf INTEGER NOT NULL,
+        PRIMARY KEY (collection, term, doc_id)
+    );
+    """,
+    """
+    CREATE INDEX IF NOT EXISTS idx_keyword_postings_doc
+        ON keyword_postings(collection, doc_id);
+    """,
+]
+
+
+def tokenize(text: str) -> list[str]:
+    """Split text into lowercase search terms.
+
+    Compound identifiers are split on ``_`` and case changes and also kept
+    whole, so ``hello_world`` gives ``hello_world``, ``hello`` and
+    ``world``. Path separators such as ``::``, ``.`` and ``/`` always split.
+    """
+    tokens: list[str] = []
+    for word in _WORD_RE.findall(text):
+        parts = [
+            part.lower()
+            for piece in word.split("_")
+            if piece
+            for part in (_CAMEL_RE.findall(piece) or [piece])
+        ]
+        whole = word.strip("_").lower()
+        if len(parts) > 1 and whole:
+            tokens.append(whole)
+        tokens.extend(parts)
+    return tokens
+
+
+class KeywordIndex:
+    """SQLite inverted index with BM25 scoring."""
+
+    def __init__(
+        self, db_path: str | Path, text_fields: dict[str, list[str]]
+    ) -> None:
+        """Initialize the index (the database is opened on first use).
+
+        Args:
+            db_path: Path to the SQLite file
+            text_fields: Collection name -> payload fields to index.
+                Other collections are not indexed.
+        """
+        self.db_path = Path(db_path).expanduser()
+        self.text_fields = text_fields
+        self._conn: aiosqlite.Connection | None = None
+
+    async def _connect(self) -> aiosqlite.Connection:
+        if self._conn is None:
+            self.db_path.parent.mkdir(parents=True, exist_ok=True)
+            self._conn = await aiosqlite.connect(str(self.db_path))
+            await self._conn.execute("PRAGMA journal_mode=WAL;")
+            for table_sql in KEYWORD_TABLES:
+                await self._conn.execute(table_sql)
+            await self._conn.commit()
+        return self._conn
+
+    async def close(self) -> None:
+        """Close the database connection."""
+        if self._conn:
+            await self._conn.close()
+            self._conn = None
+
+    def indexes(self, collection: str) -> bool:
+        """Whether a collection is covered by this index."""
+        return collection in self.text_fields
+
+    def document_text(self, collection: str, payload: dict[str, Any]) -> str:
+        """Concatenate the indexed text fields of a payload."""
+        values = (payload.get(f) for f in self.text_fields.get(collection, []))
+        return "\n".join(v for v in values if isinstance(v, str))
+
+    async def indexed_count(self, collection: str) -> int | None:
+        """Number of indexed documents, or None if never backfilled."""
+        conn = await self._connect()
+        cursor = await conn.execute(
+            "SELECT 1 FROM keyword_collections WHERE collection = ?", (collection,)
+        )
+        if await cursor.fetchone() is None:
+            return None
+        cursor = await conn.execute(
+            "SELECT COUNT(*) FROM keyword_docs WHERE collection = ?", (collection,)
+        )
+        row = await cursor.fetchone()
+        return int(row[0]) if row else 0
+
+    async def mark_synced(self, collection: str) -> None:
+        """Record that every document of a collection is indexed."""
+        conn = await self._connect()
+        await conn.execute(
+            "INSERT OR IGNORE INTO keyword_collections (collection) VALUES (?)",
+            (collection,),
+        )
+        await conn.commit()
+
+    async def add(
+        self, collection: str, ids: list[str], payloads: list[dict[str, Any]]
+    ) -> None:
+        """Index (or re-index) documents."""
+        if not self.indexes(collection) or not ids:
+            return
+        conn = await self._connect()
+        await self._delete_postings(conn, collection, ids)
+
+        docs = []
+        postings = []
+        for doc_id, payload in zip(ids, payloads):
+            text = self.document_text(collection, payload)
+            terms = tokenize(text)
+            docs.append((collection, doc_id, len(terms), text))
+            postings.extend(
+                (collection, term, doc_id, tf) for term, tf in Counter(terms).items()
+            )
+
+        await conn.executemany(
+            "INSERT OR REPLACE INTO keyword_docs "
+            "(collection, doc_id, length, text) VALUES (?, ?, ?, ?)",
+            docs,
+        )
+        await conn.executemany(
+            "INSERT OR REPLACE INTO keyword_postings "
+            "(collection, term, doc_id, tf) VALUES (?, ?, ?, ?)",
+            postings,
+        )
+        await conn.commit()
+
+    async def remove(self, collection: str, ids: list[str]) -> None:
+        """Remove documents from the index."""
+        if not self.indexes(collection) or not ids:
+            return
+        conn = await self._connect()
+        await self._delete_postings(conn, collection, ids)
+        await conn.executemany(
+            "DELETE FROM keyword_docs WHERE collection = ? AND doc_id = ?",
+            [(collection, doc_id) for doc_id in ids],
+        )
+        await conn.commit()
+
+    async def drop(self, collection: str) -> None:
+        """Remove a collection and its sync marker."""
+        conn = await self._connect()
+        for table in ("keyword_postings", "keyword_docs", "keyword_collections"):
+            await conn.execute(
+                f"DELETE FROM {table} WHERE collection = ?", (collection,)
+            )
+        await conn.commit()
+
+    async def _delete_postings(
+        self, conn: aiosqlite.Connection, collection: str, ids: list[str]
+    ) -> None:
+        await conn.executemany(
+            "DELETE FROM keyword_postings WHERE collection = ? AND doc_id = ?",
+            [(collection, doc_id) for doc_id in ids],
+        )
+
+    async def document(self, collection: str, doc_id: str) -> str | None:
+        """Indexed text of a document, or None if it is not indexed."""
+        conn = await self._connect()
+        cursor = await conn.execute(
+            "SELECT text FROM keyword_docs WHERE collection = ? AND doc_id = ?",
+            (collection, doc_id),
+        )
+        row = await cursor.fetchone()
+        return str(row[0]) if row else None
+
+    async def search(self, collection: str, query: str) -> list[tuple[str, float]]:
+        """Rank every matching document by BM25 against the query.
+
+        Returns:
+            (document ID, BM25 score) pairs, best first
+        """
+        terms = set(tokenize(query))
+        conn = await self._connect()
+
+        cursor = await conn.execute(
+            "SELECT COUNT(*), AVG(length) FROM keyword_docs WHERE collection = ?",
+            (collection,),
+        )
+        row = await cursor.fetchone()
+        total_docs = int(row[0]) if row else 0
+        if not terms or not total_docs:
+            return []
+        avg_length = float(row[1]) if row and row[1] else 1.0
+
+        # term -> [(doc_id, tf)]
+        postings: dict[str, list[tuple[str, int]]] = {}
+        for term in terms:
+            cursor = await conn.execute(
+                "SELECT doc_id, tf FROM keyword_postings "
+                "WHERE collection = ? AND term = ?",
+                (collection, term),
+            )
+            rows = await cursor.fetchall()
+            if rows:
+                postings[term] = [(r[0], int(r[1])) for r in rows]
+
+        candidates = {doc_id for rows in postings.values() for doc_id, _ in rows}
+        lengths = await self._load_lengths(conn, collection, candidates)
+
+        scores: dict[str, float] = {}
+        for rows in postings.values():
+            df = len(rows)
+            idf = math.log(1 + (total_docs - df + 0.5) / (df + 0.5))
+            for doc_id, tf in rows:
+                length = lengths.get(doc_id, avg_length)
+                norm = 1 - BM25_B + BM25_B * length / avg_length
+                scores[doc_id] = scores.get(doc_id, 0.0) + idf * (
+                    tf * (BM25_K1 + 1) / (tf + BM25_K1 * norm)
+                )
+
+        ranked = [
+            (doc_id, score) for doc_id, score in scores.items() if doc_id in lengths
+        ]
+        ranked.sort(key=lambda x: (-x[1], x[0]))
+        return ranked
+
+    async def _load_lengths(
+        self, conn: aiosqlite.Connection, collection: str, ids: set[str]
+    ) -> dict[str, int]:
+        """Load the token length of documents."""
+        lengths: dict[str, int] = {}
+        id_list = sorted(ids)
+        for start in range(0, len(id_list), _IN_CHUNK_SIZE):
+            chunk = id_list[start : start + _IN_CHUNK_SIZE]
+            placeholders = ",".join("?" * len(chunk))
+            cursor = await conn.execute(
+                "SELECT doc_id, length FROM keyword_docs "
+                f"WHERE collection = ? AND doc_id IN ({placeholders})",
+                (collection, *chunk),
+            )
+            for doc_id, length in await cursor.fetchall():
+                lengths[doc_id] = int(length)
+        return lengths
+
+
+class KeywordIndexedStore(VectorStore):
+    """Vector store wrapper that maintains a ``KeywordIndex``."""
+
+    def __init__(self, store: VectorStore, keyword_index: KeywordIndex) -> None:
+        self.store = store
+        self.keyword_index = keyword_index
+
+    async def create_collection(
+        self, name: str, dimension: int, distance: str = "cosine"
+    ) -> None:
+        await self.store.create_collection(name, dimension, distance)
+        if self.keyword_index.indexes(name):
+            await self.keyword_index.drop(name)
+            await self.keyword_index.mark_synced(name)
+
+    async def delete_collection(self, name: str) -> None:
+        await self.store.delete_collection(name)
+        await self.keyword_index.drop(name)
+
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        await self.store.swap_collection(name, replacement)
+        # Backfilled from the new points on the next keyword search
+        await self.keyword_index.drop(name)
+
+    async def upsert(
+        self,
+        collection: str,
+        id: str,
+        vector: Vector,
+        payload: dict[str, Any],
+    ) -> None:
+        await self.store.upsert(collection, id, vector, payload)
+        await self.keyword_index.add(collection, [id], [payload])
+
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        await self.store.upsert_batch(collection, ids, vectors, payloads)
+        await self.keyword_index.add(collection, ids, payloads)
+
+    async def search(
+        self,
+        collection: str,
+        query: Vector,
+        limit: int = 10,
+        filters: dict[str, Any] | None = None,
+    ) -> list[SearchResult]:
+        return await self.store.search(collection, query, limit, filters)
+
+    async def delete(self, collection: str, id: str) -> None:
+        await self.store.delete(collection, id)
+        await self.keyword_index.remove(collection, [id])
+
+    async def scroll(
+        self,
+        collection: str,
+        limit: int = 100,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> list[SearchResult]:
+        return await self.store.scroll(collection, limit, filters, with_vectors)
+
+    async def scroll_batches(
+        self,
+        collection: str,
+        batch_size: int = 256,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> AsyncIterator[list[SearchResult]]:
+        async for results in self.store.scroll_batches(
+            collection, batch_size, filters, with_vectors
+        ):
+            yield results
+
+    async def count(
+        self, collection: str, filters: dict[str, Any] | None = None
+    ) -> int:
+        return await self.store.count(collection, filters)
+
+    async def get(
+        self, collection: str, id: str, with_vector: bool = False
+    ) -> SearchResult | None:
+        return await self.store.get(collection, id, with_vector)
+
+    async def get_collection_info(self, name: str) -> CollectionInfo | None:
+        return await self.store.get_collection_info(name)
+
+    async def keyword_search(
+        self,
+        collection: str,
+        query: str,
+        limit: int = 10,
+        filters: dict[str, Any] | None = None,
+    ) -> list[SearchResult]:
+        """BM25 search, with payloads read from the vector store.
+
+        The index is backfilled when its document count differs from the
+        collection's point count. Hits whose point was deleted or rewritten
+        without going through this wrapper are re-indexed by ID from the
+        store, and the search runs again.
+
+        Scores are normalized so the best match scores 1.0.
+
+        Raises:
+            FilterError: If the filter is malformed or uses an unknown operator
+        """
+        if not self.keyword_index.indexes(collection):
+            return []
+        node = parse_filters(filters) if filters else None
+        await self._backfill_if_drifted(collection)
+
+        results, stale = await self._keyword_matches(collection, query, limit, node)
+        if stale:
+            await self._resync(collection, stale)
+            await self._backfill_if_drifted(collection)
+            results, _ = await self._keyword_matches(collection, query, limit, node)
+        return results
+
+    async def _backfill_if_drifted(self, collection: str) -> None:
+        """Rebuild the index if its document count differs from the store's."""
+        indexed = await self.keyword_index.indexed_count(collection)
+        if indexed != await self.store.count(collection):
+            await self.rebuild(collection)
+
+    async def _keyword_matches(
+        self,
+        collection: str,
+        query: str,
+        limit: int,
+        node: FilterNode | None,
+    ) -> tuple[list[SearchResult], list[str]]:
+        """Top BM25 hits that still match the store, plus the stale hit IDs."""
+        matched: list[SearchResult] = []
+        stale: list[str] = []
+        for doc_id, score in await self.keyword_index.search(collection, query):
+            point = await self.store.get(collection, doc_id)
+            indexed_text = await self.keyword_index.document(collection, doc_id)
+            if point is None or indexed_text != self.keyword_index.document_text(
+                collection, point.payload
+            ):
+                stale.append(doc_id)
+                continue
+            if node is not None and not evaluate(node, point.payload):
+                continue
+            matched.append(
+                SearchResult(id=doc_id, score=score, payload=point.payload, vector=None)
+            )
+            if len(matched) == limit:
+                break
+
+        best = matched[0].score if matched else 0.0
+        for result in matched:
+            result.score = result.score / best if best > 0 else 0.0
+        return matched, stale
+
+    async def _resync(self, collection: str, ids: list[str]) -> None:
+        """Re-index documents by ID from the vector store."""
+        gone: list[str] = []
+        for doc_id in ids:
+            point = await self.store.get(collection, doc_id)
+            if point is None:
+                gone.append(doc_id)
+            else:
+                await self.keyword_index.add(collection, [doc_id], [point.payload])
+        await self.keyword_index.remove(collection, gone)
+        logger.info("keyword_index.resynced", collection=collection, documents=len(ids))
+
+    async def rebuild(self, collection: str) -> int:
+        """Re-index every document in a collection from the vector store.
+
+        Returns:
+            Number of documents indexed
+        """
+        await self.keyword_index.drop(collection)
+        indexed = 0
+        async for results in self.store.scroll_batches(collection):
+            await self.keyword_index.add(
+                collection, [r.id for r in results], [r.payload for r in results]
+            )
+            indexed += len(results)
+        await self.keyword_index.mark_synced(collection)
+        logger.info("keyword_index.rebuilt", collection=collection, documents=indexed)
+        return indexed
@@ -5,6 +5,7 @@ from typing import Any
 import numpy as np
 
 from .base import CollectionInfo, SearchResult, Vector, VectorStore
+from .filters import evaluate, parse_filters
 
 
 class MemoryStore(VectorStore):
@@ -34,6 +35,12 @@ class MemoryStore(VectorStore):
             raise ValueError(f"Collection {name} not found")
         del self._collections[name]
 
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        """Replace a collection with another one."""
+        if replacement not in self._collections:
+            raise ValueError(f"Collection {replacement} not found")
+        self._collections[name] = self._collections.pop(replacement)
+
     async def upsert(
         self,
         collection: str,
@@ -57,6 +64,31 @@ class MemoryStore(VectorStore):
         coll["vectors"][id] = vector.copy()
         coll["payloads"][id] = payload.copy()
 
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        """Insert or update many vectors."""
+        if not len(ids) == len(vectors) == len(payloads):
+            raise ValueError("ids, vectors and payloads must have the same length")
+
+        # Validate everything first so a bad vector leaves the batch unapplied
+        if collection not in self._collections:
+            raise ValueError(f"Collection {collection} not found")
+        expected_dim = self._collections[collection]["dimension"]
+        for vector in vectors:
+            if vector.shape[0] != expected_dim:
+                raise ValueError(
+                    f"Vector dimension {vector.shape[0]} does not match "
+                    f"collection dimension {expected_dim}"
+                )
+
+        for id, vector, payload in zip(ids, vectors, payloads):
+            await self.upsert(collection, id, vector, payload)
+
     async def search(
         self,
         collection: str,
@@ -183,72 +215,18 @@ class MemoryStore(VectorStore):
     def _apply_filters(
         self, payloads: dict[str, dict[str, Any]], filters: dict[str, Any] | None
     ) -> list[str]:
-        """Apply filters to payloads, supporting operators like $gte/$lte/$gt/$lt/$in.
+        """Apply filters to payloads (see ``calm.storage.filters`` for syntax).
 
         Returns list of IDs that match all filters.
+
+        Raises:
+            FilterError: If the filter is malformed or uses an unknown operator
         """
         if not filters:
             return list(payloads.keys())
 
-        matching_ids = []
-        for id, payload in payloads.items():
-            # Check if all filter conditions match
-            matches = True
-            for key, value in filters.items():
-                if key not in payload:
-                    matches = False
-                    break
-
-                payload_value = payload[key]
-
-                # Check if value is operator-based filter (dict with $operators)
-                if isinstance(value, dict):
-                    if not self._match_operators(payload_value, value):
-                        matches = False
-                        break
-                else:
-                    # Simple equality check
-                    if payload_value != value:
-                        matches = False
-                        break
-
-            if matches:
-                matching_ids.append(id)
-
-        return matching_ids
-
-    def _match_operators(self, payload_value: Any, operators: dict[str, Any]) -> bool:
-        """Check if payload value matches all operator conditions.
-
-        Supports:
-            $gte: Greater than or equal
-            $lte: Less than or equal
-            $gt: Greater than
-            $lt: Less than
-            $in: Value is in list
-        """
-        for op, op_value in operators.items():
-            if op == "$gte":
-                if not (payload_value >= op_value):
-                    return False
-            elif op == "$lte":
-                if not (payload_value <= op_value):
-                    return False
-            elif op == "$gt":
-                if not (payload_value > op_value):
-                    return False
-            elif op == "$lt":
-                if not (payload_value < op_value):
-                    return False
-            elif op == "$in":
-                if payload_value not in op_value:
-                    return False
-            else:
-                # Unknown operator - treat as equality with the dict
-                if payload_value != operators:
-                    return False
-                break
-        return True
+        node = parse_filters(filters)
+        return [id for id, payload in payloads.items() if evaluate(node, payload)]
 
     async def get_collection_info(self, name: str) -> CollectionInfo | None:
         """Get collection metadata from in-memory storage.
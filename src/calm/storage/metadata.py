@@ -52,6 +52,39 @@ CREATE INDEX IF NOT EXISTS idx_call_graph_callee
     ON call_graph(callee_qualified_name, project);
 """
 
+# SQL Schema for code symbols (definitions used to resolve call sites)
+CODE_SYMBOLS_TABLE = """
+CREATE TABLE IF NOT EXISTS code_symbols (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    qualified_name TEXT NOT NULL,
+    name TEXT NOT NULL,
+    unit_type TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    project TEXT NOT NULL
+);
+"""
+
+CODE_SYMBOLS_INDEX = """
+CREATE INDEX IF NOT EXISTS idx_code_symbols_file
+    ON code_symbols(project, file_path);
+"""
+
+# SQL Schema for unresolved call sites (callee names as written in source)
+CALL_SITES_TABLE = """
+CREATE TABLE IF NOT EXISTS call_sites (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    caller_qualified_name TEXT NOT NULL,
+    callee_name TEXT NOT NULL,
+    caller_file TEXT NOT NULL,
+    project TEXT NOT NULL
+);
+"""
+
+CALL_SITES_INDEX = """
+CREATE INDEX IF NOT EXISTS idx_call_sites_file
+    ON call_sites(project, caller_file);
+"""
+
 # SQL Schema for projects
 PROJECTS_TABLE = """
 CREATE TABLE IF NOT EXISTS projects (
@@ -75,17 +108,32 @@ CREATE TABLE IF NOT EXISTS git_index_state (
 );
 """
 
+# SQL Schema for the embedding model each vector collection was written with
+COLLECTION_EMBEDDINGS_TABLE = """
+CREATE TABLE IF NOT EXISTS collection_embeddings (
+    collection TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    dimension INTEGER NOT NULL,
+    embedded_at TEXT NOT NULL
+);
+"""
+
 ALL_TABLES = [
     INDEXED_FILES_TABLE,
     CALL_GRAPH_TABLE,
+    CODE_SYMBOLS_TABLE,
+    CALL_SITES_TABLE,
     PROJECTS_TABLE,
     GIT_INDEX_STATE_TABLE,
+    COLLECTION_EMBEDDINGS_TABLE,
 ]
 
 ALL_INDEXES = [
     INDEXED_FILES_INDEX,
     CALL_GRAPH_INDEX_CALLER,
     CALL_GRAPH_INDEX_CALLEE,
+    CODE_SYMBOLS_INDEX,
+    CALL_SITES_INDEX,
 ]
 
 
@@ -116,6 +164,25 @@ class CallGraphEntry:
     indexed_at: datetime
 
 
+@dataclass
+class CodeSymbol:
+    """Represents a named definition that call sites can resolve to."""
+
+    qualified_name: str
+    name: str
+    unit_type: str
+    file_path: str
+
+
+@dataclass
+class CallSite:
+    """Represents an unresolved call from a function to a callee name."""
+
+    caller_qualified_name: str
+    callee_name: str
+    caller_file: str
+
+
 @dataclass
 class ProjectConfig:
     """Represents project configuration and settings."""
@@ -139,6 +206,16 @@ class GitIndexState:
     commit_count: int
 
 
+@dataclass
+class CollectionEmbedding:
+    """Embedding model a vector collection's points were embedded with."""
+
+    collection: str
+    model: str
+    dimension: int
+    embedded_at: datetime
+
+
 class MetadataStore:
     """Async SQLite metadata storage with WAL mode for concurrent access."""
 
@@ -333,16 +410,201 @@ class MetadataStore:
         if not self._conn:
             raise RuntimeError("Database not initialized")
 
-        # Delete in order: call_graph, indexed_files, projects
+        # Delete in order: call_graph, symbols, indexed_files, projects
         await self._conn.execute(
             "DELETE FROM call_graph WHERE project = ?", (name,)
         )
+        await self._conn.execute(
+            "DELETE FROM code_symbols WHERE project = ?", (name,)
+        )
+        await self._conn.execute(
+            "DELETE FROM call_sites WHERE project = ?", (name,)
+        )
         await self._conn.execute(
             "DELETE FROM indexed_files WHERE project = ?", (name,)
         )
         await self._conn.execute("DELETE FROM projects WHERE name = ?", (name,))
         await self._conn.commit()
 
+    # Call graph operations
+
+    async def replace_file_symbols(
+        self,
+        file_path: str,
+        project: str,
+        symbols: list[CodeSymbol],
+        call_sites: list[CallSite],
+    ) -> None:
+        """Replace the symbols and call sites recorded for a file."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        await self._delete_file_symbols(file_path, project)
+        await self._conn.executemany(
+            """
+            INSERT INTO code_symbols
+                (qualified_name, name, unit_type, file_path, project)
+            VALUES (?, ?, ?, ?, ?)
+            """,
+            [
+                (s.qualified_name, s.name, s.unit_type, file_path, project)
+                for s in symbols
+            ],
+        )
+        await self._conn.executemany(
+            """
+            INSERT INTO call_sites
+                (caller_qualified_name, callee_name, caller_file, project)
+            VALUES (?, ?, ?, ?)
+            """,
+            [
+                (c.caller_qualified_name, c.callee_name, file_path, project)
+                for c in call_sites
+            ],
+        )
+        await self._conn.commit()
+
+    async def delete_file_symbols(self, file_path: str, project: str) -> None:
+        """Delete symbols, call sites, and outgoing call edges for a file."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        await self._delete_file_symbols(file_path, project)
+        await self._conn.execute(
+            "DELETE FROM call_graph WHERE project = ? AND caller_file = ?",
+            (project, file_path),
+        )
+        await self._conn.commit()
+
+    async def _delete_file_symbols(self, file_path: str, project: str) -> None:
+        """Delete symbol and call site rows for a file (no commit)."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        await self._conn.execute(
+            "DELETE FROM code_symbols WHERE project = ? AND file_path = ?",
+            (project, file_path),
+        )
+        await self._conn.execute(
+            "DELETE FROM call_sites WHERE project = ? AND caller_file = ?",
+            (project, file_path),
+        )
+
+    async def list_symbols(self, project: str) -> list[CodeSymbol]:
+        """List all code symbols for a project."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            """
+            SELECT qualified_name, name, unit_type, file_path
+            FROM code_symbols
+            WHERE project = ?
+            """,
+            (project,),
+        )
+        rows = await cursor.fetchall()
+        return [
+            CodeSymbol(
+                qualified_name=row[0], name=row[1], unit_type=row[2], file_path=row[3]
+            )
+            for row in rows
+        ]
+
+    async def list_call_sites(self, project: str) -> list[CallSite]:
+        """List all unresolved call sites for a project."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            """
+            SELECT caller_qualified_name, callee_name, caller_file
+            FROM call_sites
+            WHERE project = ?
+            """,
+            (project,),
+        )
+        rows = await cursor.fetchall()
+        return [
+            CallSite(caller_qualified_name=row[0], callee_name=row[1], caller_file=row[2])
+            for row in rows
+        ]
+
+    async def replace_call_graph(
+        self, project: str, entries: list[tuple[str, str, str, str]]
+    ) -> None:
+        """Replace a project's call graph.
+
+        Args:
+            project: Project identifier
+            entries: (caller_qualified_name, callee_qualified_name,
+                caller_file, callee_file) tuples
+        """
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        indexed_at = datetime.now().isoformat()
+        await self._conn.execute(
+            "DELETE FROM call_graph WHERE project = ?", (project,)
+        )
+        await self._conn.executemany(
+            """
+            INSERT OR IGNORE INTO call_graph
+                (caller_qualified_name, callee_qualified_name, caller_file,
+                 callee_file, project, indexed_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            """,
+            [(*entry, project, indexed_at) for entry in entries],
+        )
+        await self._conn.commit()
+
+    async def get_callees(
+        self, qualified_name: str, project: str
+    ) -> list[CallGraphEntry]:
+        """Get call edges where the given unit is the caller."""
+        return await self._query_call_graph(
+            "caller_qualified_name", qualified_name, project
+        )
+
+    async def get_callers(
+        self, qualified_name: str, project: str
+    ) -> list[CallGraphEntry]:
+        """Get call edges where the given unit is the callee."""
+        return await self._query_call_graph(
+            "callee_qualified_name", qualified_name, project
+        )
+
+    async def _query_call_graph(
+        self, column: str, qualified_name: str, project: str
+    ) -> list[CallGraphEntry]:
+        """Query call graph edges by caller or callee column."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            f"""
+            SELECT id, caller_qualified_name, callee_qualified_name,
+                   caller_file, callee_file, project, indexed_at
+            FROM call_graph
+            WHERE {column} = ? AND project = ?
+            ORDER BY caller_qualified_name, callee_qualified_name
+            """,
+            (qualified_name, project),
+        )
+        rows = await cursor.fetchall()
+        return [
+            CallGraphEntry(
+                id=row[0],
+                caller_qualified_name=row[1],
+                callee_qualified_name=row[2],
+                caller_file=row[3],
+                callee_file=row[4],
+                project=row[5],
+                indexed_at=datetime.fromisoformat(row[6]),
+            )
+            for row in rows
+        ]
+
     # Git index state operations
 
     async def get_git_index_state(self, repo_path: str) -> GitIndexState | None:
@@ -485,3 +747,118 @@ class MetadataStore:
             created_at=datetime.fromisoformat(row[4]),
             last_indexed=datetime.fromisoformat(row[5]) if row[5] else None,
         )
+
+    async def list_projects(self) -> list[ProjectConfig]:
+        """List all registered projects."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            """
+            SELECT id, name, root_path, settings, created_at, last_indexed
+            FROM projects
+            ORDER BY name
+            """
+        )
+        rows = await cursor.fetchall()
+
+        return [
+            ProjectConfig(
+                id=row[0],
+                name=row[1],
+                root_path=row[2],
+                settings=json.loads(row[3]),
+                created_at=datetime.fromisoformat(row[4]),
+                last_indexed=datetime.fromisoformat(row[5]) if row[5] else None,
+            )
+            for row in rows
+        ]
+
+    async def update_project_last_indexed(
+        self, name: str, last_indexed: datetime | None = None
+    ) -> None:
+        """Record when a project was last indexed."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        when = last_indexed or datetime.now()
+        await self._conn.execute(
+            "UPDATE projects SET last_indexed = ? WHERE name = ?",
+            (when.isoformat(), name),
+        )
+        await self._conn.commit()
+
+    # Collection embedding model operations
+
+    async def get_collection_embedding(
+        self, collection: str
+    ) -> CollectionEmbedding | None:
+        """Get the embedding model recorded for a vector collection."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            """
+            SELECT collection, model, dimension, embedded_at
+            FROM collection_embeddings
+            WHERE collection = ?
+            """,
+            (collection,),
+        )
+        row = await cursor.fetchone()
+
+        if not row:
+            return None
+
+        return CollectionEmbedding(
+            collection=row[0],
+            model=row[1],
+            dimension=row[2],
+            embedded_at=datetime.fromisoformat(row[3]),
+        )
+
+    async def list_collection_embeddings(self) -> list[CollectionEmbedding]:
+        """List the embedding models recorded for all vector collections."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        cursor = await self._conn.execute(
+            """
+            SELECT collection, model, dimension, embedded_at
+            FROM collection_embeddings
+            ORDER BY collection
+            """
+        )
+        rows = await cursor.fetchall()
+
+        return [
+            CollectionEmbedding(
+                collection=row[0],
+                model=row[1],
+                dimension=row[2],
+                embedded_at=datetime.fromisoformat(row[3]),
+            )
+            for row in rows
+        ]
+
+    async def set_collection_embedding(
+        self, collection: str, model: str, dimension: int
+    ) -> None:
+        """Record the embedding model a vector collection was written with."""
+        if not self._conn:
+            raise RuntimeError("Database not initialized")
+
+        await self._conn.execute(
+            """
+            INSERT INTO collection_embeddings (
+                collection, model, dimension, embedded_at
+            )
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(collection) DO UPDATE SET
+                model=excluded.model,
+                dimension=excluded.dimension,
+                embedded_at=excluded.embedded_at
+            """,
+            (collection, model, dimension, datetime.now().isoformat()),
+        )
+        await self._conn.commit()
@@ -1,6 +1,14 @@
-"""Qdrant vector store implementation."""
+"""Qdrant vector store implementation.
+
+``swap_collection`` (used by re-embedding) turns the replaced name into a
+Qdrant alias for the replacement collection, so later swaps are a single
+atomic alias update. Collections are otherwise created and used under their
+plain names; collection-level calls resolve aliases to the collection
+behind them.
+"""
 
 import uuid
+from collections.abc import AsyncIterator
 from typing import Any
 
 import numpy as np
@@ -8,10 +16,23 @@ from qdrant_client import AsyncQdrantClient
 from qdrant_client.http import models as qmodels
 
 from .base import CollectionInfo, SearchResult, Vector, VectorStore
+from .filters import And, FilterNode, Not, Or, glob_literals, parse_filters
 
 # Namespace UUID for generating deterministic UUIDs from string IDs
 _NAMESPACE = uuid.UUID("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
 
+# Points sent per request by upsert_batch
+_UPSERT_CHUNK_SIZE = 256
+
+_QdrantCondition = (
+    qmodels.FieldCondition
+    | qmodels.IsEmptyCondition
+    | qmodels.IsNullCondition
+    | qmodels.HasIdCondition
+    | qmodels.NestedCondition
+    | qmodels.Filter
+)
+
 
 def _to_qdrant_id(id: str) -> str:
     """Convert a string ID to a valid Qdrant UUID.
@@ -30,6 +51,35 @@ def _to_qdrant_id(id: str) -> str:
     return str(uuid.uuid5(_NAMESPACE, id))
 
 
+def _delete_alias(name: str) -> qmodels.DeleteAliasOperation:
+    """Alias operation removing ``name``."""
+    return qmodels.DeleteAliasOperation(
+        delete_alias=qmodels.DeleteAlias(alias_name=name)
+    )
+
+
+def _create_alias(name: str, collection: str) -> qmodels.CreateAliasOperation:
+    """Alias operation pointing ``name`` at ``collection``."""
+    return qmodels.CreateAliasOperation(
+        create_alias=qmodels.CreateAlias(collection_name=collection, alias_name=name)
+    )
+
+
+def _scroll_result(point: qmodels.Record, with_vectors: bool) -> SearchResult:
+    """Convert a scrolled Qdrant point to a result."""
+    payload = point.payload or {}
+    return SearchResult(
+        id=payload.get("_original_id", str(point.id)),
+        score=0.0,
+        payload={k: v for k, v in payload.items() if k != "_original_id"},
+        vector=(
+            np.array(point.vector, dtype=np.float32)
+            if with_vectors and point.vector is not None
+            else None
+        ),
+    )
+
+
 class QdrantVectorStore(VectorStore):
     """Vector store implementation using Qdrant."""
 
@@ -83,6 +133,9 @@ class QdrantVectorStore(VectorStore):
                 f"Supported: {list(distance_map.keys())}"
             )
 
+        if name in await self._aliases():
+            raise ValueError(f"Collection {name} already exists")
+
         await self._client.create_collection(
             collection_name=name,
             vectors_config=qmodels.VectorParams(
@@ -92,8 +145,48 @@ class QdrantVectorStore(VectorStore):
         )
 
     async def delete_collection(self, name: str) -> None:
-        """Delete a collection."""
-        await self._client.delete_collection(collection_name=name)
+        """Delete a collection (and the alias, if ``name`` is one)."""
+        target = (await self._aliases()).get(name)
+        if target is None:
+            await self._client.delete_collection(collection_name=name)
+            return
+
+        await self._client.update_collection_aliases(
+            change_aliases_operations=[_delete_alias(name)]
+        )
+        await self._client.delete_collection(collection_name=target)
+
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        """Point ``name`` at ``replacement`` and delete the old collection.
+
+        Moving the alias is a single atomic update. A plain collection
+        (one that was never swapped) can't be aliased over, so its first
+        swap deletes it before creating the alias; searches briefly find
+        no collection, and if the alias update fails ``replacement`` is
+        kept and still holds every point.
+        """
+        aliases = await self._aliases()
+        current = aliases.get(name)
+        target = aliases.get(replacement, replacement)
+        operations: list[qmodels.AliasOperations] = []
+        if current is None:
+            await self._client.delete_collection(collection_name=name)
+        else:
+            operations.append(_delete_alias(name))
+        if replacement in aliases:
+            operations.append(_delete_alias(replacement))
+        operations.append(_create_alias(name, target))
+
+        await self._client.update_collection_aliases(
+            change_aliases_operations=operations
+        )
+        if current is not None and current != target:
+            await self._client.delete_collection(collection_name=current)
+
+    async def _aliases(self) -> dict[str, str]:
+        """Map alias names to the collections they point at."""
+        response = await self._client.get_aliases()
+        return {a.alias_name: a.collection_name for a in response.aliases}
 
     async def upsert(
         self,
@@ -115,6 +208,31 @@ class QdrantVectorStore(VectorStore):
             ],
         )
 
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        """Insert or update many vectors in chunked requests."""
+        if not len(ids) == len(vectors) == len(payloads):
+            raise ValueError("ids, vectors and payloads must have the same length")
+
+        points = [
+            qmodels.PointStruct(
+                id=_to_qdrant_id(id),
+                vector=vector.tolist(),
+                payload={**payload, "_original_id": id},
+            )
+            for id, vector, payload in zip(ids, vectors, payloads)
+        ]
+        for start in range(0, len(points), _UPSERT_CHUNK_SIZE):
+            await self._client.upsert(
+                collection_name=collection,
+                points=points[start : start + _UPSERT_CHUNK_SIZE],
+            )
+
     async def search(
         self,
         collection: str,
@@ -176,23 +294,32 @@ class QdrantVectorStore(VectorStore):
             with_vectors=with_vectors,
         )
 
-        return [
-            SearchResult(
-                id=(result.payload or {}).get("_original_id", str(result.id)),
-                score=0.0,
-                payload={
-                    k: v
-                    for k, v in (result.payload or {}).items()
-                    if k != "_original_id"
-                },
-                vector=(
-                    np.array(result.vector, dtype=np.float32)
-                    if with_vectors and result.vector is not None
-                    else None
-                ),
+        return [_scroll_result(result, with_vectors) for result in results]
+
+    async def scroll_batches(
+        self,
+        collection: str,
+        batch_size: int = 256,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> AsyncIterator[list[SearchResult]]:
+        """Page through a collection with Qdrant's scroll offsets."""
+        qdrant_filter = self._build_filter(filters) if filters else None
+
+        offset: qmodels.ExtendedPointId | None = None
+        while True:
+            results, offset = await self._client.scroll(
+                collection_name=collection,
+                limit=batch_size,
+                offset=offset,
+                scroll_filter=qdrant_filter,
+                with_payload=True,
+                with_vectors=with_vectors,
             )
-            for result in results
-        ]
+            if results:
+                yield [_scroll_result(result, with_vectors) for result in results]
+            if offset is None:
+                return
 
     async def count(
         self, collection: str, filters: dict[str, Any] | None = None
@@ -237,60 +364,92 @@ class QdrantVectorStore(VectorStore):
         )
 
     def _build_filter(self, filters: dict[str, Any]) -> qmodels.Filter:
-        """Build Qdrant filter from simple key-value pairs.
+        """Build a Qdrant filter from the shared filter syntax.
 
-        Supports:
-        - Equality matching: {"field": "value"}
-        - Multi-value matching: {"field": {"$in": ["val1", "val2"]}}
-        - Range queries: {"field": {"$gte": value}}, {"field": {"$lte": value}}
+        See ``calm.storage.filters`` for the syntax. Top-level conditions
+        become ``must`` clauses, ``$or`` a nested ``should`` filter and
+        ``$not``/``$nin``/``$exists: true`` nested ``must_not`` filters.
 
         Note: Range queries only work with numeric values (int, float).
         Timestamps should be stored as Unix timestamps for range queries.
+        ``$glob`` is approximated by substring matches on its literal parts.
+
+        Raises:
+            FilterError: If the filter is malformed or uses an unknown operator
         """
-        conditions: list[
-            qmodels.FieldCondition
-            | qmodels.IsEmptyCondition
-            | qmodels.IsNullCondition
-            | qmodels.HasIdCondition
-            | qmodels.NestedCondition
-            | qmodels.Filter
-        ] = []
-
-        for key, value in filters.items():
-            # Handle operator queries
-            if isinstance(value, dict):
-                if "$in" in value:
-                    # Multi-value match (ANY of the values)
-                    conditions.append(
-                        qmodels.FieldCondition(
-                            key=key,
-                            match=qmodels.MatchAny(any=value["$in"]),
-                        )
+        root = parse_filters(filters)
+        conditions = [self._to_condition(child) for child in root.children]
+        # Qdrant accepts Sequence but list is covariant-compatible
+        return qmodels.Filter(must=conditions if conditions else None)
+
+    def _to_condition(self, node: FilterNode) -> _QdrantCondition:
+        """Translate one filter AST node to a Qdrant condition."""
+        if isinstance(node, And):
+            return qmodels.Filter(
+                must=[self._to_condition(child) for child in node.children]
+            )
+        if isinstance(node, Or):
+            return qmodels.Filter(
+                should=[self._to_condition(child) for child in node.children]
+            )
+        if isinstance(node, Not):
+            return qmodels.Filter(must_not=[self._to_condition(node.child)])
+
+        key = node.key
+        if node.op == "$eq":
+            return qmodels.FieldCondition(
+                key=key, match=qmodels.MatchValue(value=node.value)
+            )
+        if node.op == "$in":
+            # Multi-value match (ANY of the values)
+            return qmodels.FieldCondition(
+                key=key, match=qmodels.MatchAny(any=list(node.value))
+            )
+        if node.op == "$nin":
+            # must_not (rather than MatchExcept) so missing fields match
+            return qmodels.Filter(
+                must_not=[
+                    qmodels.FieldCondition(
+                        key=key, match=qmodels.MatchAny(any=list(node.value))
                     )
-                elif any(op in value for op in ("$gte", "$lte", "$gt", "$lt")):
-                    # Range query - can combine multiple operators
-                    conditions.append(
-                        qmodels.FieldCondition(
-                            key=key,
-                            range=qmodels.Range(
-                                gte=value.get("$gte"),
-                                lte=value.get("$lte"),
-                                gt=value.get("$gt"),
-                                lt=value.get("$lt"),
-                            ),
-                        )
+                ]
+            )
+        if node.op == "$exists":
+            is_empty = qmodels.IsEmptyCondition(
+                is_empty=qmodels.PayloadField(key=key)
+            )
+            return qmodels.Filter(must_not=[is_empty]) if node.value else is_empty
+        if node.op == "$contains":
+            return qmodels.Filter(
+                must=[
+                    qmodels.FieldCondition(
+                        key=key, match=qmodels.MatchValue(value=value)
                     )
-            else:
-                # Simple equality match
-                conditions.append(
+                    for value in node.value
+                ]
+            )
+        if node.op == "$glob":
+            # Qdrant has no glob match. Without a full-text index MatchText
+            # is a substring match, so require every literal part of the
+            # pattern (a superset of the exact glob matches).
+            return qmodels.Filter(
+                must=[
                     qmodels.FieldCondition(
-                        key=key,
-                        match=qmodels.MatchValue(value=value),
+                        key=key, match=qmodels.MatchText(text=literal)
                     )
-                )
-
-        # Qdrant accepts Sequence but list is covariant-compatible
-        return qmodels.Filter(must=conditions if conditions else None)
+                    for literal in glob_literals(node.value)
+                ]
+            )
+        # $range - can combine multiple bounds
+        return qmodels.FieldCondition(
+            key=key,
+            range=qmodels.Range(
+                gte=node.value.get("$gte"),
+                lte=node.value.get("$lte"),
+                gt=node.value.get("$gt"),
+                lt=node.value.get("$lt"),
+            ),
+        )
 
     async def get_collection_info(self, name: str) -> CollectionInfo | None:
         """Get collection metadata from Qdrant.
@@ -306,7 +465,8 @@ class QdrantVectorStore(VectorStore):
             Exception: For Qdrant connection/network errors
         """
         try:
-            collection = await self._client.get_collection(name)
+            target = (await self._aliases()).get(name, name)
+            collection = await self._client.get_collection(target)
             # Handle both single vector and named vector configs
             vectors_config = collection.config.params.vectors
             if isinstance(vectors_config, dict):
@@ -0,0 +1,417 @@
+"""Embedded SQLite vector store for machines without a Qdrant server.
+
+Vectors are stored as float32 BLOBs next to their JSON payloads in a single
+database file (``~/.calm/vectors.db`` by default). Search is brute force
+with numpy over a per-collection matrix that is loaded on first use and
+kept up to date by this store's writes, which is fast enough for the tens
+of thousands of points a single developer's projects produce. Writes from
+other processes (such as ``calm reembed`` or a second server) are detected
+through ``PRAGMA data_version`` and drop the cached matrices.
+"""
+
+import json
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+import aiosqlite
+import numpy as np
+import numpy.typing as npt
+
+from .base import CollectionInfo, SearchResult, Vector, VectorStore
+from .filters import evaluate, parse_filters
+
+COLLECTIONS_TABLE = """
+CREATE TABLE IF NOT EXISTS collections (
+    name TEXT PRIMARY KEY,
+    dimension INTEGER NOT NULL,
+    distance TEXT NOT NULL
+);
+"""
+
+POINTS_TABLE = """
+CREATE TABLE IF NOT EXISTS points (
+    collection TEXT NOT NULL,
+    id TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    payload TEXT NOT NULL,
+    PRIMARY KEY (collection, id)
+);
+"""
+
+
+@dataclass
+class _CollectionCache:
+    """In-memory copy of a collection used for search and filtering.
+
+    Rows are kept in insertion order. Deleted rows are left as holes (with
+    a ``None`` payload) until they make up half of the rows, and the matrix
+    grows by doubling, so writes don't copy the whole collection.
+    """
+
+    dimension: int
+    distance: str
+    ids: list[str]
+    payloads: list[dict[str, Any] | None]  # None for deleted rows
+    buffer: npt.NDArray[np.float32]  # One row per point, in ids order
+    index: dict[str, int]  # Row of each live point
+    holes: int = 0
+
+    @property
+    def matrix(self) -> npt.NDArray[np.float32]:
+        """Rows of all points, including holes."""
+        return self.buffer[: len(self.ids)]
+
+    def put(self, id: str, vector: Vector, payload: dict[str, Any]) -> None:
+        """Insert or update a point."""
+        row = self.index.get(id)
+        if row is None:
+            row = len(self.ids)
+            if row == len(self.buffer):
+                grown = np.zeros(
+                    (max(2 * row, 16), self.dimension), dtype=np.float32
+                )
+                grown[:row] = self.buffer
+                self.buffer = grown
+            self.ids.append(id)
+            self.payloads.append(payload)
+            self.index[id] = row
+        else:
+            self.payloads[row] = payload
+        self.buffer[row] = vector
+
+    def remove(self, id: str) -> None:
+        """Delete a point, compacting the rows once half are holes."""
+        row = self.index.pop(id, None)
+        if row is None:
+            return
+        self.payloads[row] = None
+        self.holes += 1
+        if self.holes * 2 < len(self.ids):
+            return
+
+        rows = [i for i, payload in enumerate(self.payloads) if payload is not None]
+        self.buffer = self.matrix[rows].copy()
+        self.ids = [self.ids[i] for i in rows]
+        self.payloads = [self.payloads[i] for i in rows]
+        self.index = {id: i for i, id in enumerate(self.ids)}
+        self.holes = 0
+
+
+class SqliteVectorStore(VectorStore):
+    """Vector store persisted in a local SQLite database."""
+
+    def __init__(self, db_path: str | Path | None = None) -> None:
+        """Initialize the store (the database is opened on first use).
+
+        Args:
+            db_path: Path to the SQLite file. Defaults to CalmSettings value.
+        """
+        if db_path is None:
+            from calm.config import settings
+
+            db_path = settings.vector_db_path
+        self.db_path = Path(db_path).expanduser()
+        self._conn: aiosqlite.Connection | None = None
+        self._cache: dict[str, _CollectionCache] = {}
+        self._data_version: int | None = None
+
+    async def _connect(self) -> aiosqlite.Connection:
+        """Open the database and create the schema if needed."""
+        if self._conn is None:
+            self.db_path.parent.mkdir(parents=True, exist_ok=True)
+            self._conn = await aiosqlite.connect(str(self.db_path))
+            await self._conn.execute("PRAGMA journal_mode=WAL;")
+            await self._conn.execute(COLLECTIONS_TABLE)
+            await self._conn.execute(POINTS_TABLE)
+            await self._conn.commit()
+        return self._conn
+
+    async def close(self) -> None:
+        """Close the database connection."""
+        if self._conn:
+            await self._conn.close()
+            self._conn = None
+        self._cache.clear()
+
+    async def _collection(self, name: str) -> tuple[int, str]:
+        """Return (dimension, distance) of a collection.
+
+        Raises:
+            ValueError: If the collection does not exist
+        """
+        conn = await self._connect()
+        cursor = await conn.execute(
+            "SELECT dimension, distance FROM collections WHERE name = ?", (name,)
+        )
+        row = await cursor.fetchone()
+        if row is None:
+            raise ValueError(f"Collection {name} not found")
+        return int(row[0]), str(row[1])
+
+    async def _load(self, name: str) -> _CollectionCache:
+        """Load (or reuse) the in-memory copy of a collection.
+
+        All cached collections are dropped when another connection has
+        committed since the last check, as its writes aren't in them.
+        """
+        conn = await self._connect()
+        cursor = await conn.execute("PRAGMA data_version")
+        row = await cursor.fetchone()
+        data_version = int(row[0]) if row else None
+        if data_version != self._data_version:
+            self._cache.clear()
+            self._data_version = data_version
+        if name in self._cache:
+            return self._cache[name]
+
+        dimension, distance = await self._collection(name)
+        cursor = await conn.execute(
+            "SELECT id, vector, payload FROM points WHERE collection = ? "
+            "ORDER BY rowid",
+            (name,),
+        )
+        rows = await cursor.fetchall()
+        matrix = np.zeros((len(rows), dimension), dtype=np.float32)
+        for i, row in enumerate(rows):
+            matrix[i] = np.frombuffer(row[1], dtype=np.float32)
+
+        ids = [row[0] for row in rows]
+        cache = _CollectionCache(
+            dimension=dimension,
+            distance=distance,
+            ids=ids,
+            payloads=[json.loads(row[2]) for row in rows],
+            buffer=matrix,
+            index={id: i for i, id in enumerate(ids)},
+        )
+        self._cache[name] = cache
+        return cache
+
+    async def create_collection(
+        self, name: str, dimension: int, distance: str = "cosine"
+    ) -> None:
+        """Create a new collection."""
+        conn = await self._connect()
+        try:
+            await conn.execute(
+                "INSERT INTO collections (name, dimension, distance) VALUES (?, ?, ?)",
+                (name, dimension, distance),
+            )
+        except aiosqlite.IntegrityError:
+            raise ValueError(f"Collection {name} already exists") from None
+        await conn.commit()
+
+    async def delete_collection(self, name: str) -> None:
+        """Delete a collection and all of its points."""
+        await self._collection(name)
+        conn = await self._connect()
+        await conn.execute("DELETE FROM points WHERE collection = ?", (name,))
+        await conn.execute("DELETE FROM collections WHERE name = ?", (name,))
+        await conn.commit()
+        self._cache.pop(name, None)
+
+    async def swap_collection(self, name: str, replacement: str) -> None:
+        """Replace a collection with another one in a single transaction."""
+        await self._collection(replacement)
+        conn = await self._connect()
+        await conn.execute("DELETE FROM points WHERE collection = ?", (name,))
+        await conn.execute("DELETE FROM collections WHERE name = ?", (name,))
+        await conn.execute(
+            "UPDATE points SET collection = ? WHERE collection = ?",
+            (name, replacement),
+        )
+        await conn.execute(
+            "UPDATE collections SET name = ? WHERE name = ?", (name, replacement)
+        )
+        await conn.commit()
+        self._cache.pop(name, None)
+        self._cache.pop(replacement, None)
+
+    async def upsert(
+        self,
+        collection: str,
+        id: str,
+        vector: Vector,
+        payload: dict[str, Any],
+    ) -> None:
+        """Insert or update a vector."""
+        await self.upsert_batch(collection, [id], [vector], [payload])
+
+    async def upsert_batch(
+        self,
+        collection: str,
+        ids: list[str],
+        vectors: list[Vector],
+        payloads: list[dict[str, Any]],
+    ) -> None:
+        """Insert or update many vectors in one transaction."""
+        if not len(ids) == len(vectors) == len(payloads):
+            raise ValueError("ids, vectors and payloads must have the same length")
+
+        expected_dim, _ = await self._collection(collection)
+        for vector in vectors:
+            if vector.shape[0] != expected_dim:
+                raise ValueError(
+                    f"Vector dimension {vector.shape[0]} does not match "
+                    f"collection dimension {expected_dim}"
+                )
+
+        rows = [
+            (collection, id, np.asarray(vector, dtype=np.float32), json.dumps(payload))
+            for id, vector, payload in zip(ids, vectors, payloads)
+        ]
+        conn = await self._connect()
+        # Updates keep their rowid, so points stay in insertion order
+        await conn.executemany(
+            "INSERT INTO points (collection, id, vector, payload) "
+            "VALUES (?, ?, ?, ?) ON CONFLICT (collection, id) DO UPDATE "
+            "SET vector = excluded.vector, payload = excluded.payload",
+            [(c, id, vector.tobytes(), payload) for c, id, vector, payload in rows],
+        )
+        await conn.commit()
+
+        cache = self._cache.get(collection)
+        if cache is not None:
+            # Cache the payloads as they will be read back from the database
+            for _, id, vector, payload in rows:
+                cache.put(id, vector, json.loads(payload))
+
+    async def search(
+        self,
+        collection: str,
+        query: Vector,
+        limit: int = 10,
+        filters: dict[str, Any] | None = None,
+    ) -> list[SearchResult]:
+        """Search for similar vectors by brute force."""
+        cache = await self._load(collection)
+        rows = self._filter(cache, filters)
+        if not rows:
+            return []
+
+        matrix = cache.matrix[rows]
+        query = np.asarray(query, dtype=np.float32)
+        if cache.distance == "euclidean":
+            # Qdrant reports euclidean distance, smallest first
+            scores = np.linalg.norm(matrix - query, axis=1)
+            order = np.argsort(scores, kind="stable")
+        else:
+            scores = matrix @ query
+            if cache.distance != "dot":
+                norms = np.linalg.norm(matrix, axis=1) * np.linalg.norm(query)
+                scores = np.divide(
+                    scores, norms, out=np.zeros_like(scores), where=norms != 0
+                )
+            order = np.argsort(-scores, kind="stable")
+
+        return [
+            SearchResult(
+                id=cache.ids[rows[i]],
+                score=float(scores[i]),
+                payload=dict(cache.payloads[rows[i]] or {}),
+                vector=None,
+            )
+            for i in order[:limit]
+        ]
+
+    async def delete(self, collection: str, id: str) -> None:
+        """Delete a vector by ID."""
+        await self._collection(collection)
+        conn = await self._connect()
+        await conn.execute(
+            "DELETE FROM points WHERE collection = ? AND id = ?", (collection, id)
+        )
+        await conn.commit()
+        if collection in self._cache:
+            self._cache[collection].remove(id)
+
+    async def scroll(
+        self,
+        collection: str,
+        limit: int = 100,
+        filters: dict[str, Any] | None = None,
+        with_vectors: bool = False,
+    ) -> list[SearchResult]:
+        """Retrieve vectors without search."""
+        cache = await self._load(collection)
+        return [
+            self._result(cache, row, with_vectors)
+            for row in self._filter(cache, filters)[:limit]
+        ]
+
+    async def count(
+        self, collection: str, filters: dict[str, Any] | None = None
+    ) -> int:
+        """Count vectors in a collection."""
+        if not filters:
+            await self._collection(collection)
+            conn = await self._connect()
+            cursor = await conn.execute(
+                "SELECT COUNT(*) FROM points WHERE collection = ?", (collection,)
+            )
+            row = await cursor.fetchone()
+            return int(row[0]) if row else 0
+        return len(self._filter(await self._load(collection), filters))
+
+    async def get(
+        self, collection: str, id: str, with_vector: bool = False
+    ) -> SearchResult | None:
+        """Get a specific vector by ID."""
+        await self._collection(collection)
+        conn = await self._connect()
+        cursor = await conn.execute(
+            "SELECT vector, payload FROM points WHERE collection = ? AND id = ?",
+            (collection, id),
+        )
+        row = await cursor.fetchone()
+        if row is None:
+            return None
+        return SearchResult(
+            id=id,
+            score=0.0,
+            payload=json.loads(row[1]),
+            vector=np.frombuffer(row[0], dtype=np.float32).copy()
+            if with_vector
+            else None,
+        )
+
+    async def get_collection_info(self, name: str) -> CollectionInfo | None:
+        """Get collection metadata.
+
+        Args:
+            name: Collection name
+
+        Returns:
+            CollectionInfo if collection exists, None if not found
+        """
+        try:
+            dimension, _ = await self._collection(name)
+        except ValueError:
+            return None
+        return CollectionInfo(
+            name=name,
+            dimension=dimension,
+            vector_count=await self.count(name),
+        )
+
+    def _filter(
+        self, cache: _CollectionCache, filters: dict[str, Any] | None
+    ) -> list[int]:
+        """Return indexes of the live rows whose payloads match the filters."""
+        node = parse_filters(filters) if filters else None
+        return [
+            i
+            for i, payload in enumerate(cache.payloads)
+            if payload is not None and (node is None or evaluate(node, payload))
+        ]
+
+    def _result(
+        self, cache: _CollectionCache, row: int, with_vector: bool
+    ) -> SearchResult:
+        return SearchResult(
+            id=cache.ids[row],
+            score=0.0,
+            payload=dict(cache.payloads[row] or {}),
+            vector=cache.matrix[row].copy() if with_vector else None,
+        )
@@ -7,6 +7,7 @@ from .git import get_git_tools
 from .journal import get_journal_tools
 from .learning import get_learning_tools
 from .memory import get_memory_tools
+from .search import get_search_tools
 
 __all__ = [
     "get_code_tools",
@@ -16,4 +17,5 @@ __all__ = [
     "get_journal_tools",
     "get_learning_tools",
     "get_memory_tools",
+    "get_search_tools",
 ]
@@ -1,19 +1,22 @@
 """Code indexing and search tools for CALM MCP server."""
 
+import dataclasses
 from collections.abc import Callable, Coroutine
 from pathlib import Path
 from typing import Any
 
 import structlog
 
-from calm.embedding.base import EmbeddingService
+from calm.embedding.base import EmbeddingService, Reranker
+from calm.search.chunks import candidate_limit, collapse_chunks
 from calm.search.collections import CollectionName
+from calm.search.fusion import FUSION_METHODS
+from calm.search.query import parse_query
 from calm.search.searcher import (
     VALID_SEARCH_MODES,
-    _hybrid_search,
-    _keyword_search,
-    _semantic_search,
+    search_collection,
 )
+from calm.search.snippets import build_snippet
 from calm.storage.base import VectorStore
 
 from .validation import ValidationError, validate_query_string
@@ -39,6 +42,10 @@ SUPPORTED_LANGUAGES = [
     "swift",
     "kotlin",
     "scala",
+    "lua",
+    "markdown",
+    "toml",
+    "yaml",
 ]
 
 # Project identifier constraints
@@ -150,12 +157,17 @@ def get_code_tools(
     vector_store: VectorStore,
     code_embedder: EmbeddingService,
     code_indexer: Any = None,
+    watcher: Any = None,
+    reranker: Reranker | None = None,
 ) -> dict[str, ToolFunc]:
     """Get code tool implementations for the dispatcher.
 
     Args:
         vector_store: Initialized vector store
         code_embedder: Initialized code embedding service
+        code_indexer: Code indexer (None when running with mock services)
+        watcher: Project watcher to follow newly indexed projects, if watching
+        reranker: Reranker for the collections configured in settings.rerank
 
     Returns:
         Dictionary mapping tool names to their implementations
@@ -165,6 +177,8 @@ def get_code_tools(
         directory: str,
         project: str,
         recursive: bool = True,
+        exclude: list[str] | None = None,
+        respect_gitignore: bool | None = None,
     ) -> dict[str, Any]:
         """Index a directory of source code for semantic search.
 
@@ -172,6 +186,10 @@ def get_code_tools(
             directory: Absolute path to directory
             project: Project identifier
             recursive: Recurse subdirectories (default True)
+            exclude: Gitignore-style patterns to exclude (saved for the
+                     project; None reuses the saved patterns)
+            respect_gitignore: Honor git ignore files (saved for the
+                               project; None reuses the saved setting)
 
         Returns:
             Indexing statistics
@@ -196,24 +214,58 @@ def get_code_tools(
                     "Restart server with real services.",
                 }
 
+            if exclude is not None and not all(isinstance(p, str) for p in exclude):
+                raise ValidationError("exclude must be a list of strings")
+
+            # Start from the project's saved exclusions, applying overrides
+            exclusions = None
+            if exclude is not None or respect_gitignore is not None:
+                exclusions = await code_indexer.get_exclusions(project)
+                if exclude is not None:
+                    exclusions = dataclasses.replace(exclusions, patterns=exclude)
+                if respect_gitignore is not None:
+                    exclusions = dataclasses.replace(
+                        exclusions, respect_gitignore=respect_gitignore
+                    )
+
             stats = await code_indexer.index_directory(
                 path=str(dir_path),
                 project=project,
                 recursive=recursive,
                 exclude_patterns=DEFAULT_EXCLUSIONS,
+                exclusions=exclusions,
             )
+            if watcher is not None:
+                await watcher.watch_project(project)
 
-            return {
+            response: dict[str, Any] = {
                 "status": "success",
                 "project": project,
                 "directory": str(dir_path),
                 "files_indexed": stats.files_indexed,
                 "units_indexed": stats.units_indexed,
+                "units_unchanged": stats.units_unchanged,
                 "files_skipped": stats.files_skipped,
+                "files_removed": stats.files_removed,
                 "errors": len(stats.errors),
                 "duration_ms": stats.duration_ms,
             }
 
+            # Report Cargo workspace layout when indexing a Rust workspace
+            workspace = stats.workspace
+            if workspace:
+                response["crates"] = [
+                    {
+                        "name": crate.name,
+                        "version": crate.version,
+                        "path": str(crate.root),
+                        "default_member": crate in workspace.default_members,
+                    }
+                    for crate in workspace.members
+                ]
+
+            return response
+
         except ValidationError as e:
             logger.warning("code.validation_error", error=str(e))
             return _error_response("validation_error", str(e))
@@ -227,9 +279,17 @@ def get_code_tools(
         language: str | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        visibility: str | None = None,
+        attribute: str | None = None,
+        crate: str | None = None,
+        fusion: str | None = None,
     ) -> dict[str, Any]:
         """Search indexed code semantically.
 
+        The query may contain inline qualifiers (``lang:rust``,
+        ``path:src/indexers``, ``type:method``) and file globs (``*.rs``),
+        which become filters. Explicit filter arguments take precedence.
+
         Args:
             query: Search query
             project: Optional project filter
@@ -237,9 +297,16 @@ def get_code_tools(
             limit: Max results (1-50, default 10)
             search_mode: Search mode - "semantic", "keyword", or "hybrid"
                          (default "semantic")
+            visibility: Optional visibility filter (e.g. "pub", "pub(crate)",
+                        "private")
+            attribute: Optional attribute name filter (e.g. "derive", "test")
+            crate: Optional Cargo crate name filter
+            fusion: Hybrid fusion method - "rrf" or "weighted"
+                    (default from config)
 
         Returns:
-            Search results with scores
+            Search results with scores (hybrid results include their
+            ranking breakdown)
         """
         logger.info("code.search", query=query[:50], project=project)
 
@@ -270,45 +337,63 @@ def get_code_tools(
                     f"Invalid search_mode '{search_mode}'. Must be one of: {valid}"
                 )
 
+            # Validate fusion method
+            if fusion is not None and fusion not in FUSION_METHODS:
+                valid = ", ".join(f"'{m}'" for m in FUSION_METHODS)
+                raise ValidationError(
+                    f"Invalid fusion '{fusion}'. Must be one of: {valid}"
+                )
+
             # Handle empty query
             if not query.strip():
                 return {"results": [], "count": 0}
 
+            # Split off inline qualifiers and globs
+            parsed = parse_query(query)
+            query = parsed.text
+            if not query.strip():
+                return {"results": [], "count": 0}
+
             # Build filters
-            filters: dict[str, Any] = {}
+            filters: dict[str, Any] = dict(parsed.filters)
             if project:
                 filters["project"] = project
             if language:
                 filters["language"] = language.lower()
+            if visibility:
+                filters["visibility"] = "".join(visibility.split())
+            if attribute:
+                filters["attribute_names"] = attribute
+            if crate:
+                # Crate names are stored in their Rust path form
+                filters["crate"] = crate.replace("-", "_")
 
             # Dispatch search based on mode
             collection = CollectionName.CODE_UNITS
             text_fields = ["code", "qualified_name", "docstring"]
 
-            if search_mode == "keyword":
-                results = await _keyword_search(
-                    vector_store, collection, query, limit,
-                    filters if filters else None, text_fields,
-                )
-            elif search_mode == "hybrid":
-                results = await _hybrid_search(
-                    code_embedder, vector_store, collection, query, limit,
-                    filters if filters else None, text_fields,
-                )
-            else:
-                results = await _semantic_search(
-                    code_embedder, vector_store, collection, query, limit,
-                    filters if filters else None,
-                )
+            results = await search_collection(
+                code_embedder, vector_store, search_mode, collection, query,
+                limit, filters if filters else None, text_fields,
+                fusion=fusion, reranker=reranker,
+            )
 
-            # Format results
-            formatted = [
-                {
-                    **result.payload,
-                    "score": result.score,
-                }
-                for result in results
-            ]
+            # Format results, replacing the full unit body with a snippet
+            formatted = []
+            for result in results:
+                payload = dict(result.payload)
+                code = payload.pop("code", None) or ""
+                formatted.append(
+                    {
+                        **payload,
+                        "snippet": build_snippet(
+                            code, query, payload.get("start_line", 1)
+                        ),
+                        "score": result.score,
+                    }
+                )
+                if result.ranking is not None:
+                    formatted[-1]["ranking"] = result.ranking
 
             logger.info("code.searched", count=len(formatted))
 
@@ -375,13 +460,15 @@ def get_code_tools(
             # Build filters
             filters = {"project": project} if project else None
 
-            # Search
+            # Search, keeping the best chunk of each unit
+            collection = CollectionName.CODE_UNITS
             results = await vector_store.search(
-                collection=CollectionName.CODE_UNITS,
+                collection=collection,
                 query=snippet_embedding,
-                limit=limit,
+                limit=candidate_limit(collection, limit),
                 filters=filters,
             )
+            results = await collapse_chunks(vector_store, collection, results, limit)
 
             # Format results
             formatted = [
@@ -410,8 +497,139 @@ def get_code_tools(
                 "internal_error", f"Failed to find similar code: {e}"
             )
 
+    async def _traverse_call_graph(
+        qualified_name: str,
+        project: str,
+        depth: int,
+        limit: int,
+        direction: str,
+    ) -> dict[str, Any]:
+        """Breadth-first walk of the call graph in one direction."""
+        validate_project_id(project)
+        if not qualified_name.strip():
+            raise ValidationError("qualified_name cannot be empty")
+        if not 1 <= depth <= 5:
+            raise ValidationError(
+                f"Depth {depth} out of range. Must be between 1 and 5."
+            )
+        if not 1 <= limit <= 200:
+            raise ValidationError(
+                f"Limit {limit} out of range. Must be between 1 and 200."
+            )
+
+        if code_indexer is None:
+            return {
+                "status": "not_available",
+                "message": "Code indexer not initialized. "
+                "Restart server with real services.",
+            }
+
+        store = code_indexer.metadata_store
+        lookup = store.get_callers if direction == "callers" else store.get_callees
+        results: list[dict[str, Any]] = []
+        visited = {qualified_name}
+        frontier = [qualified_name]
+        truncated = False
+
+        for level in range(1, depth + 1):
+            next_frontier: list[str] = []
+            for name in frontier:
+                for entry in await lookup(name, project):
+                    if direction == "callers":
+                        other, other_file = entry.caller_qualified_name, entry.caller_file
+                    else:
+                        other, other_file = entry.callee_qualified_name, entry.callee_file
+                    if other in visited:
+                        continue
+                    if len(results) >= limit:
+                        truncated = True
+                        break
+                    visited.add(other)
+                    next_frontier.append(other)
+                    results.append(
+                        {
+                            "qualified_name": other,
+                            "file_path": other_file,
+                            "depth": level,
+                            "via": name,
+                        }
+                    )
+            frontier = next_frontier
+            if not frontier or truncated:
+                break
+
+        return {
+            "qualified_name": qualified_name,
+            "project": project,
+            direction: results,
+            "count": len(results),
+            "truncated": truncated,
+        }
+
+    async def get_callers(
+        qualified_name: str,
+        project: str,
+        depth: int = 1,
+        limit: int = 50,
+    ) -> dict[str, Any]:
+        """Find functions and methods that call a code unit.
+
+        Args:
+            qualified_name: Qualified name of the called unit
+            project: Project identifier
+            depth: Levels of indirect callers to include (1-5, default 1)
+            limit: Max results (1-200, default 50)
+
+        Returns:
+            Callers with file path and call depth
+        """
+        logger.info("code.get_callers", qualified_name=qualified_name, depth=depth)
+
+        try:
+            return await _traverse_call_graph(
+                qualified_name, project, depth, limit, "callers"
+            )
+        except ValidationError as e:
+            logger.warning("code.validation_error", error=str(e))
+            return _error_response("validation_error", str(e))
+        except Exception as e:
+            logger.error("code.get_callers_failed", error=str(e), exc_info=True)
+            return _error_response("internal_error", f"Failed to get callers: {e}")
+
+    async def get_callees(
+        qualified_name: str,
+        project: str,
+        depth: int = 1,
+        limit: int = 50,
+    ) -> dict[str, Any]:
+        """Find functions and methods called by a code unit.
+
+        Args:
+            qualified_name: Qualified name of the calling unit
+            project: Project identifier
+            depth: Levels of indirect callees to include (1-5, default 1)
+            limit: Max results (1-200, default 50)
+
+        Returns:
+            Callees with file path and call depth
+        """
+        logger.info("code.get_callees", qualified_name=qualified_name, depth=depth)
+
+        try:
+            return await _traverse_call_graph(
+                qualified_name, project, depth, limit, "callees"
+            )
+        except ValidationError as e:
+            logger.warning("code.validation_error", error=str(e))
+            return _error_response("validation_error", str(e))
+        except Exception as e:
+            logger.error("code.get_callees_failed", error=str(e), exc_info=True)
+            return _error_response("internal_error", f"Failed to get callees: {e}")
+
     return {
         "index_codebase": index_codebase,
         "search_code": search_code,
         "find_similar_code": find_similar_code,
+        "get_callers": get_callers,
+        "get_callees": get_callees,
     }
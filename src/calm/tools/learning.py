@@ -7,12 +7,11 @@ from uuid import uuid4
 
 import structlog
 
-from calm.embedding.base import EmbeddingService
+from calm.embedding.base import EmbeddingService, Reranker
+from calm.search.fusion import FUSION_METHODS
 from calm.search.searcher import (
     VALID_SEARCH_MODES,
-    _hybrid_search,
-    _keyword_search,
-    _semantic_search,
+    search_collection,
 )
 from calm.storage.base import VectorStore
 
@@ -89,12 +88,14 @@ def get_learning_tools(
     semantic_embedder: EmbeddingService,
     experience_clusterer: Any = None,
     value_store: Any = None,
+    reranker: Reranker | None = None,
 ) -> dict[str, ToolFunc]:
     """Get learning tool implementations for the dispatcher.
 
     Args:
         vector_store: Initialized vector store
         semantic_embedder: Initialized semantic embedding service
+        reranker: Reranker for the collections configured in settings.rerank
 
     Returns:
         Dictionary mapping tool names to their implementations
@@ -107,6 +108,7 @@ def get_learning_tools(
         outcome: str | None = None,
         limit: int = 10,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> dict[str, Any]:
         """Search experiences semantically, by keyword, or hybrid.
 
@@ -118,9 +120,12 @@ def get_learning_tools(
             limit: Maximum results (default 10, max 50)
             search_mode: Search mode - "semantic", "keyword", or "hybrid"
                          (default "semantic")
+            fusion: Hybrid fusion method - "rrf" or "weighted"
+                    (default from config)
 
         Returns:
-            List of matching experiences with scores
+            List of matching experiences with scores (hybrid results
+            include their ranking breakdown)
         """
         try:
             # Validate query length
@@ -140,6 +145,13 @@ def get_learning_tools(
                     f"Invalid search_mode '{search_mode}'. Must be one of: {valid}"
                 )
 
+            # Validate fusion method
+            if fusion is not None and fusion not in FUSION_METHODS:
+                valid = ", ".join(f"'{m}'" for m in FUSION_METHODS)
+                raise ValidationError(
+                    f"Invalid fusion '{fusion}'. Must be one of: {valid}"
+                )
+
             # Validate domain if provided
             if domain is not None:
                 validate_domain(domain)
@@ -171,24 +183,11 @@ def get_learning_tools(
                 text_fields.append("surprise")
 
             try:
-                # Dispatch search based on mode
-                if search_mode == "keyword":
-                    results = await _keyword_search(
-                        vector_store, collection, query, limit,
-                        filters if filters else None, text_fields,
-                    )
-                elif search_mode == "hybrid":
-                    results = await _hybrid_search(
-                        semantic_embedder, vector_store, collection,
-                        query, limit,
-                        filters if filters else None, text_fields,
-                    )
-                else:
-                    results = await _semantic_search(
-                        semantic_embedder, vector_store, collection,
-                        query, limit,
-                        filters if filters else None,
-                    )
+                results = await search_collection(
+                    semantic_embedder, vector_store, search_mode, collection,
+                    query, limit, filters if filters else None, text_fields,
+                    fusion=fusion, reranker=reranker,
+                )
             except Exception as search_error:
                 # Handle missing collection gracefully
                 error_msg = str(search_error).lower()
@@ -233,6 +232,8 @@ def get_learning_tools(
                         "created_at": created_at,
                     }
                 )
+                if r.ranking is not None:
+                    formatted[-1]["ranking"] = r.ranking
 
             logger.info(
                 "learning.experiences_searched",
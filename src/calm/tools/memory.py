@@ -8,12 +8,11 @@ from uuid import uuid4
 import structlog
 
 from calm.config import settings
-from calm.embedding.base import EmbeddingService
+from calm.embedding.base import EmbeddingService, Reranker
+from calm.search.fusion import FUSION_METHODS
 from calm.search.searcher import (
     VALID_SEARCH_MODES,
-    _hybrid_search,
-    _keyword_search,
-    _semantic_search,
+    search_collection,
 )
 from calm.storage.base import VectorStore
 
@@ -80,13 +79,16 @@ async def _ensure_memories_collection(
 
 
 def get_memory_tools(
-    vector_store: VectorStore, semantic_embedder: EmbeddingService
+    vector_store: VectorStore,
+    semantic_embedder: EmbeddingService,
+    reranker: Reranker | None = None,
 ) -> dict[str, ToolFunc]:
     """Get memory tool implementations for the dispatcher.
 
     Args:
         vector_store: Initialized vector store
         semantic_embedder: Initialized semantic embedding service
+        reranker: Reranker for the collections configured in settings.rerank
 
     Returns:
         Dictionary mapping tool names to their implementations
@@ -181,6 +183,7 @@ def get_memory_tools(
         category: str | None = None,
         min_importance: float = 0.0,
         search_mode: str = "semantic",
+        fusion: str | None = None,
     ) -> dict[str, Any]:
         """Search memories semantically, by keyword, or hybrid."""
         logger.info("memory.retrieve", query=query[:50], limit=limit)
@@ -215,6 +218,13 @@ def get_memory_tools(
                     f"Invalid search_mode '{search_mode}'. Must be one of: {valid}"
                 )
 
+            # Validate fusion method
+            if fusion is not None and fusion not in FUSION_METHODS:
+                valid = ", ".join(f"'{m}'" for m in FUSION_METHODS)
+                raise ValidationError(
+                    f"Invalid fusion '{fusion}'. Must be one of: {valid}"
+                )
+
             # Handle empty query
             if not query.strip():
                 return {"results": [], "count": 0}
@@ -230,30 +240,18 @@ def get_memory_tools(
             collection = "memories"
             text_fields = ["content"]
 
-            if search_mode == "keyword":
-                results = await _keyword_search(
-                    vector_store, collection, query, limit,
-                    filters if filters else None, text_fields,
-                )
-            elif search_mode == "hybrid":
-                results = await _hybrid_search(
-                    semantic_embedder, vector_store, collection, query, limit,
-                    filters if filters else None, text_fields,
-                )
-            else:
-                results = await _semantic_search(
-                    semantic_embedder, vector_store, collection, query, limit,
-                    filters if filters else None,
-                )
+            results = await search_collection(
+                semantic_embedder, vector_store, search_mode, collection, query,
+                limit, filters if filters else None, text_fields,
+                fusion=fusion, reranker=reranker,
+            )
 
             # Format results
-            formatted = [
-                {
-                    **result.payload,
-                    "score": result.score,
-                }
-                for result in results
-            ]
+            formatted = []
+            for result in results:
+                formatted.append({**result.payload, "score": result.score})
+                if result.ranking is not None:
+                    formatted[-1]["ranking"] = result.ranking
 
             logger.info("memory.retrieved", count=len(formatted))
 
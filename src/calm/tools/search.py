@@ -0,0 +1,222 @@
+"""Federated search tool for CALM MCP server.
+
+``search_all`` runs one query against several collections concurrently and
+returns a single interleaved result list, so agents don't have to call
+``retrieve_memories``, ``search_code``, ``search_experiences`` and
+``search_commits`` separately and merge by hand.
+"""
+
+import asyncio
+from collections.abc import Callable, Coroutine
+from typing import Any
+
+import structlog
+
+from calm.embedding.base import EmbeddingService, Reranker
+from calm.search.collections import CollectionName
+from calm.search.fusion import normalize_scores
+from calm.search.query import parse_query
+from calm.search.searcher import (
+    KEYWORD_TEXT_FIELDS,
+    VALID_SEARCH_MODES,
+    search_collection,
+)
+from calm.search.snippets import build_snippet
+from calm.storage.base import SearchResult, VectorStore
+
+from .validation import ValidationError, validate_query_string
+
+logger = structlog.get_logger()
+
+# Type alias for tool functions
+ToolFunc = Callable[..., Coroutine[Any, Any, dict[str, Any]]]
+
+# Searchable sources (names match assemble_context's context types)
+SEARCH_SOURCES: dict[str, str] = {
+    "memories": CollectionName.MEMORIES,
+    "code": CollectionName.CODE_UNITS,
+    "experiences": CollectionName.EXPERIENCES_FULL,
+    "values": CollectionName.VALUES,
+    "commits": CollectionName.COMMITS,
+}
+
+
+def _error_response(error_type: str, message: str) -> dict[str, Any]:
+    """Create a standardized error response."""
+    return {"error": {"type": error_type, "message": message}}
+
+
+def get_search_tools(
+    vector_store: VectorStore,
+    semantic_embedder: EmbeddingService,
+    code_embedder: EmbeddingService,
+    reranker: Reranker | None = None,
+) -> dict[str, ToolFunc]:
+    """Get federated search tool implementations.
+
+    Args:
+        vector_store: Initialized vector store
+        semantic_embedder: Embedding service for memories, experiences,
+            values and commits
+        code_embedder: Embedding service for code units
+        reranker: Reranker for the collections configured in settings.rerank
+
+    Returns:
+        Dictionary mapping tool names to their implementations
+    """
+
+    async def _search_source(
+        source: str,
+        query: str,
+        limit: int,
+        search_mode: str,
+        project: str | None,
+        code_filters: dict[str, Any],
+    ) -> list[SearchResult]:
+        """Search one source, treating a missing collection as empty."""
+        collection = SEARCH_SOURCES[source]
+        embedder = code_embedder if source == "code" else semantic_embedder
+        text_fields = KEYWORD_TEXT_FIELDS.get(collection, [])
+        filters: dict[str, Any] = {}
+        if source == "code":
+            filters = dict(code_filters)
+            if project:
+                filters["project"] = project
+
+        if await vector_store.get_collection_info(collection) is None:
+            logger.info("search.source_empty", source=source)
+            return []
+        return await search_collection(
+            embedder, vector_store, search_mode, collection, query, limit,
+            filters or None, text_fields, reranker=reranker,
+        )
+
+    async def search_all(
+        query: str,
+        sources: list[str] | None = None,
+        limit: int = 10,
+        search_mode: str = "semantic",
+        project: str | None = None,
+    ) -> dict[str, Any]:
+        """Search several collections at once and interleave the results.
+
+        Each source is searched concurrently for up to ``limit`` results.
+        Scores are min-max normalized within each source (so the best hit
+        of every source scores 1.0), then all results are merged by
+        normalized score.
+
+        Inline qualifiers in the query (``lang:rust``, ``path:src/...``,
+        ``type:method``) and file globs filter code results; they are
+        removed from the query for all sources.
+
+        Args:
+            query: Search query
+            sources: Sources to search - any of "memories", "code",
+                     "experiences", "values", "commits" (default all)
+            limit: Max results overall (1-50, default 10)
+            search_mode: Search mode - "semantic", "keyword", or "hybrid"
+                         (default "semantic")
+            project: Optional project filter (applies to code)
+
+        Returns:
+            Interleaved results, each with its ``source``, normalized
+            ``score`` and raw ``source_score``. Sources that failed are
+            reported under ``errors``.
+        """
+        logger.info("search.all", query=query[:50], sources=sources)
+
+        try:
+            validate_query_string(query)
+
+            if not 1 <= limit <= 50:
+                raise ValidationError(
+                    f"Limit {limit} out of range. Must be between 1 and 50."
+                )
+
+            if search_mode not in VALID_SEARCH_MODES:
+                valid = ", ".join(f"'{m}'" for m in VALID_SEARCH_MODES)
+                raise ValidationError(
+                    f"Invalid search_mode '{search_mode}'. Must be one of: {valid}"
+                )
+
+            selected = list(SEARCH_SOURCES) if sources is None else sources
+            invalid = [s for s in selected if s not in SEARCH_SOURCES]
+            if invalid or not selected:
+                raise ValidationError(
+                    f"Invalid sources: {invalid or selected}. "
+                    f"Valid options: {', '.join(SEARCH_SOURCES)}"
+                )
+            selected = list(dict.fromkeys(selected))
+
+            parsed = parse_query(query)
+            query = parsed.text
+            if not query.strip():
+                return {"results": [], "count": 0}
+
+            outcomes = await asyncio.gather(
+                *(
+                    _search_source(
+                        source, query, limit, search_mode, project, parsed.filters
+                    )
+                    for source in selected
+                ),
+                return_exceptions=True,
+            )
+
+            merged: list[tuple[float, int, dict[str, Any]]] = []
+            errors: dict[str, str] = {}
+            for order, (source, outcome) in enumerate(zip(selected, outcomes)):
+                if isinstance(outcome, BaseException):
+                    logger.warning(
+                        "search.source_failed", source=source, error=str(outcome)
+                    )
+                    errors[source] = str(outcome)
+                    continue
+                normalized = normalize_scores(outcome)
+                for result in outcome:
+                    score = normalized[result.id]
+                    merged.append(
+                        (score, order, _format_result(source, result, score, query))
+                    )
+
+            # Best normalized score first; ties keep source and rank order
+            merged.sort(key=lambda item: (-item[0], item[1]))
+            formatted = [item for _, _, item in merged[:limit]]
+
+            logger.info("search.all_done", count=len(formatted))
+
+            response: dict[str, Any] = {"results": formatted, "count": len(formatted)}
+            if errors:
+                response["errors"] = errors
+            return response
+
+        except ValidationError as e:
+            logger.warning("search.validation_error", error=str(e))
+            return _error_response("validation_error", str(e))
+        except Exception as e:
+            logger.error("search.all_failed", error=str(e), exc_info=True)
+            return _error_response("internal_error", f"Failed to search: {e}")
+
+    return {
+        "search_all": search_all,
+    }
+
+
+def _format_result(
+    source: str, result: SearchResult, score: float, query: str
+) -> dict[str, Any]:
+    """Format one result, replacing code bodies with a snippet."""
+    payload = dict(result.payload)
+    if source == "code":
+        code = payload.pop("code", None) or ""
+        payload["snippet"] = build_snippet(code, query, payload.get("start_line", 1))
+    formatted = {
+        **payload,
+        "id": result.id,
+        "source": source,
+        "score": score,
+        "source_score": result.score,
+    }
+    if result.ranking is not None:
+        formatted["ranking"] = result.ranking
+    return formatted
@@ -534,6 +534,7 @@ class TestCreateQdrantSnapshotUnit:
 
         mock_client = AsyncMock()
         mock_client.get_collections = AsyncMock(return_value=mock_collections_response)
+        mock_client.get_aliases = AsyncMock(return_value=MagicMock(aliases=[]))
         mock_client.create_snapshot = AsyncMock(return_value=mock_snapshot_desc)
         mock_client.delete_snapshot = AsyncMock(return_value=True)
         mock_client.close = AsyncMock()
@@ -598,6 +599,7 @@ class TestCreateQdrantSnapshotUnit:
 
         mock_client = AsyncMock()
         mock_client.get_collections = AsyncMock(return_value=mock_collections_response)
+        mock_client.get_aliases = AsyncMock(return_value=MagicMock(aliases=[]))
         mock_client.close = AsyncMock()
 
         with patch(
@@ -641,6 +643,7 @@ class TestCreateQdrantSnapshotUnit:
 
         mock_client = AsyncMock()
         mock_client.get_collections = AsyncMock(return_value=mock_collections_response)
+        mock_client.get_aliases = AsyncMock(return_value=MagicMock(aliases=[]))
         mock_client.create_snapshot = AsyncMock(return_value=mock_snapshot_desc)
         mock_client.delete_snapshot = AsyncMock(return_value=True)
         mock_client.close = AsyncMock()
@@ -708,7 +711,21 @@ class TestRestoreQdrantSnapshotUnit:
         mock_http_response = MagicMock()
         mock_http_response.raise_for_status = MagicMock()
 
+        # memories is served through an alias for a versioned collection
+        mock_alias_response = MagicMock()
+        mock_alias_response.raise_for_status = MagicMock()
+        mock_alias_response.json = MagicMock(
+            return_value={
+                "result": {
+                    "aliases": [
+                        {"alias_name": "memories", "collection_name": "memories__v1"}
+                    ]
+                }
+            }
+        )
+
         mock_http_client = AsyncMock()
+        mock_http_client.get = AsyncMock(return_value=mock_alias_response)
         mock_http_client.post = AsyncMock(return_value=mock_http_response)
         mock_http_client.__aenter__ = AsyncMock(return_value=mock_http_client)
         mock_http_client.__aexit__ = AsyncMock(return_value=False)
@@ -732,7 +749,7 @@ class TestRestoreQdrantSnapshotUnit:
         post_calls = mock_http_client.post.call_args_list
         urls = [call.args[0] for call in post_calls]
         assert "http://localhost:6333/collections/ghap_full/snapshots/upload" in urls
-        assert "http://localhost:6333/collections/memories/snapshots/upload" in urls
+        assert "http://localhost:6333/collections/memories__v1/snapshots/upload" in urls
 
     def test_restore_empty_snapshot_dir_returns_false(
         self, tmp_path: Path, monkeypatch: pytest.MonkeyPatch
@@ -22,7 +22,7 @@ class TestTopLevelHelp:
         assert result.exit_code == 0
         for cmd in [
             "backup", "counter", "gate", "init", "install",
-            "review", "server", "session", "status", "task",
+            "reembed", "review", "server", "session", "status", "task",
             "worker", "worktree",
         ]:
             assert cmd in result.output, f"Missing command: {cmd}"
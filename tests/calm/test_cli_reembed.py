@@ -0,0 +1,86 @@
+"""Tests for the CALM reembed CLI command."""
+
+import asyncio
+from pathlib import Path
+
+import numpy as np
+import pytest
+from click.testing import CliRunner
+
+import calm.cli.reembed
+from calm.cli.main import cli
+from calm.embedding.migration import Reembedder
+from calm.embedding.mock import MockEmbeddingService
+from calm.storage.memory import MemoryStore
+from calm.storage.metadata import MetadataStore
+
+
+@pytest.fixture
+def vector_store(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> MemoryStore:
+    """Run the command against an in-memory store and mock 8-d models."""
+    store = MemoryStore()
+
+    async def create_reembedder() -> tuple[Reembedder, MetadataStore]:
+        metadata_store = MetadataStore(tmp_path / "metadata.db")
+        await metadata_store.initialize()
+        reembedder = Reembedder(
+            store,
+            metadata_store,
+            MockEmbeddingService(dimension=8),
+            MockEmbeddingService(dimension=8),
+            "code-model",
+            "semantic-model",
+        )
+        return reembedder, metadata_store
+
+    monkeypatch.setattr(calm.cli.reembed, "_create_reembedder", create_reembedder)
+    monkeypatch.setattr("calm.server.daemon.is_server_running", lambda: False)
+    return store
+
+
+@pytest.fixture
+def memories(vector_store: MemoryStore) -> MemoryStore:
+    """Two memories embedded at dimension 4."""
+
+    async def populate() -> None:
+        await vector_store.create_collection("memories", dimension=4)
+        for i in range(2):
+            await vector_store.upsert(
+                "memories", f"m{i}", np.ones(4, dtype=np.float32), {"content": f"m{i}"}
+            )
+
+    asyncio.run(populate())
+    return vector_store
+
+
+class TestReembed:
+    """Tests for calm reembed."""
+
+    def test_reembeds_stale_collections(self, memories: MemoryStore) -> None:
+        result = CliRunner().invoke(cli, ["reembed"])
+
+        assert result.exit_code == 0, result.output
+        assert "Re-embedded memories with semantic-model: 2 points" in result.output
+        assert memories._collections["memories"]["dimension"] == 8
+
+    def test_nothing_to_do(self, vector_store: MemoryStore) -> None:
+        result = CliRunner().invoke(cli, ["reembed", "--collection", "memories"])
+
+        assert result.exit_code == 0, result.output
+        assert "All collections are up to date." in result.output
+
+    def test_invalid_collection(self, vector_store: MemoryStore) -> None:
+        result = CliRunner().invoke(cli, ["reembed", "--collection", "scratch"])
+
+        assert result.exit_code != 0
+        assert "Valid options: memories, code_units" in result.output
+
+    def test_refuses_while_server_runs(
+        self, vector_store: MemoryStore, monkeypatch: pytest.MonkeyPatch
+    ) -> None:
+        monkeypatch.setattr("calm.server.daemon.is_server_running", lambda: True)
+
+        result = CliRunner().invoke(cli, ["reembed"])
+
+        assert result.exit_code != 0
+        assert "Stop it first" in result.output
@@ -1,9 +1,16 @@
 """Tests for CALM configuration module."""
 
 import os
+from pathlib import Path
 from unittest.mock import patch
 
-from calm.config import CALM_HOME, DEFAULT_CONFIG, CalmSettings
+from calm.config import (
+    CALM_HOME,
+    DEFAULT_CONFIG,
+    CalmSettings,
+    config_file_path,
+    load_config_file,
+)
 
 
 class TestCalmSettings:
@@ -56,3 +63,69 @@ class TestDefaultConfig:
         assert isinstance(config, dict)
         assert "server" in config
         assert config["server"]["port"] == 6335
+
+
+class TestConfigFile:
+    """Tests for reading settings from config.yaml."""
+
+    def test_default_config_maps_to_defaults(self, tmp_path: Path) -> None:
+        """Test that the default config.yaml yields the default settings."""
+        path = tmp_path / "config.yaml"
+        path.write_text(DEFAULT_CONFIG)
+
+        values = load_config_file(path)
+        assert values["server_port"] == 6335
+        assert values["qdrant_url"] == "http://localhost:6333"
+        assert values["vector_store"] == "qdrant"
+
+    def test_missing_file_and_unknown_keys(self, tmp_path: Path) -> None:
+        """Test that missing files and unknown keys are ignored."""
+        assert load_config_file(tmp_path / "missing.yaml") == {}
+
+        path = tmp_path / "config.yaml"
+        path.write_text("server:\n  port: 7000\n  color: blue\nother: 1\n")
+        assert load_config_file(path) == {"server_port": 7000}
+
+    def test_config_file_selects_vector_store(self, calm_config_file: Path) -> None:
+        """Test that config.yaml applies below environment variables."""
+        calm_config_file.write_text(
+            "vector_store:\n  backend: sqlite\n  path: /data/vectors.db\n"
+            "server:\n  port: 7000\n"
+        )
+
+        settings = CalmSettings()
+        assert settings.vector_store == "sqlite"
+        assert settings.vector_db_path == Path("/data/vectors.db")
+        assert settings.server_port == 7000
+
+        with patch.dict(os.environ, {"CALM_SERVER_PORT": "8080"}):
+            assert CalmSettings().server_port == 8080
+
+    def test_config_file_search_section(self, calm_config_file: Path) -> None:
+        """Test that the search section configures hybrid fusion."""
+        calm_config_file.write_text(
+            "search:\n  fusion: weighted\n  keyword_weight: 0.3\n"
+        )
+
+        settings = CalmSettings()
+        assert settings.search.fusion == "weighted"
+        assert settings.search.keyword_weight == 0.3
+        assert settings.search.rrf_k == 60
+
+        with patch.dict(os.environ, {"CALM_SEARCH__FUSION": "rrf"}):
+            settings = CalmSettings()
+            assert settings.search.fusion == "rrf"
+            assert settings.search.keyword_weight == 0.3
+
+    def test_config_file_path(self, tmp_path: Path) -> None:
+        """Test that config.yaml is found via CALM_CONFIG_FILE or CALM_HOME."""
+        path = tmp_path / "custom.yaml"
+        with patch.dict(os.environ, {"CALM_CONFIG_FILE": str(path)}):
+            assert config_file_path() == path
+
+        env = {k: v for k, v in os.environ.items() if k != "CALM_CONFIG_FILE"}
+        with patch.dict(os.environ, {**env, "CALM_HOME": str(tmp_path)}, clear=True):
+            assert config_file_path() == tmp_path / "config.yaml"
+        with patch.dict(os.environ, env, clear=True):
+            os.environ.pop("CALM_HOME", None)
+            assert config_file_path() == CALM_HOME / "config.yaml"
@@ -16,6 +16,10 @@ import os  # noqa: E402
 import threading  # noqa: E402
 from collections.abc import Generator  # noqa: E402
 
+# Never read the developer's ~/.calm/config.yaml, including for the settings
+# singleton built when calm.config is first imported
+os.environ["CALM_CONFIG_FILE"] = os.devnull
+
 import pytest  # noqa: E402
 
 from calm.utils.platform import PlatformInfo, get_platform_info  # noqa: E402
@@ -34,6 +38,14 @@ def sample_fixture() -> str:
     return "test_value"
 
 
+@pytest.fixture(autouse=True)
+def calm_config_file(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> Path:
+    """Point config.yaml at a per-test file (not created)."""
+    path = tmp_path / "config.yaml"
+    monkeypatch.setenv("CALM_CONFIG_FILE", str(path))
+    return path
+
+
 # Thread names that are expected to be long-running and should be ignored
 # by the resource tracker. These are typically from third-party libraries.
 _IGNORED_THREAD_PREFIXES = (
@@ -0,0 +1,78 @@
+"""Tests for the HTTP embedding provider."""
+
+from typing import Any
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import httpx
+import numpy as np
+import pytest
+
+from calm.embedding.base import EmbeddingModelError
+from calm.embedding.http import HttpEmbedding
+
+
+def _response(body: dict[str, Any]) -> MagicMock:
+    response = MagicMock()
+    response.json.return_value = body
+    response.raise_for_status = MagicMock()
+    return response
+
+
+def _client(response: MagicMock) -> AsyncMock:
+    client = AsyncMock()
+    client.post = AsyncMock(return_value=response)
+    client.__aenter__ = AsyncMock(return_value=client)
+    client.__aexit__ = AsyncMock(return_value=False)
+    return client
+
+
+class TestHttpEmbedding:
+    """Tests for HttpEmbedding."""
+
+    async def test_embed_batch(self) -> None:
+        # Servers may return items out of order
+        body = {
+            "data": [
+                {"index": 1, "embedding": [0.0, 1.0]},
+                {"index": 0, "embedding": [1.0, 0.0]},
+            ]
+        }
+        client = _client(_response(body))
+        embedder = HttpEmbedding(
+            "bge-small", 2, url="http://localhost:9000/v1/embeddings", api_key="k"
+        )
+
+        with patch("calm.embedding.http.httpx.AsyncClient", return_value=client):
+            vectors = await embedder.embed_batch(["a", "b"])
+
+        np.testing.assert_array_equal(vectors[0], [1.0, 0.0])
+        assert vectors[1].dtype == np.float32
+        assert embedder.dimension == 2
+        client.post.assert_awaited_once_with(
+            "http://localhost:9000/v1/embeddings",
+            json={"model": "bge-small", "input": ["a", "b"]},
+            headers={"Authorization": "Bearer k"},
+        )
+
+    async def test_dimension_is_configured(self) -> None:
+        embedder = HttpEmbedding("bge-small", dimension=3, max_tokens=512)
+        assert embedder.dimension == 3
+        assert embedder.max_tokens == 512
+
+        client = _client(_response({"data": [{"embedding": [0.1, 0.2]}]}))
+        with patch("calm.embedding.http.httpx.AsyncClient", return_value=client):
+            with pytest.raises(EmbeddingModelError, match="configured dimension"):
+                await embedder.embed("a")
+
+    async def test_errors(self) -> None:
+        embedder = HttpEmbedding("bge-small", dimension=2)
+        client = _client(_response({"error": "model not loaded"}))
+
+        with patch("calm.embedding.http.httpx.AsyncClient", return_value=client):
+            with pytest.raises(EmbeddingModelError, match="Unexpected response"):
+                await embedder.embed("a")
+
+        client.post.side_effect = httpx.ConnectError("Connection refused")
+        with patch("calm.embedding.http.httpx.AsyncClient", return_value=client):
+            with pytest.raises(EmbeddingModelError, match="Connection refused"):
+                await embedder.embed("a")
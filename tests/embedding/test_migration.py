@@ -0,0 +1,396 @@
+"""Tests for re-embedding collections after a model change."""
+
+import asyncio
+from collections.abc import AsyncIterator
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+from calm.embedding.migration import (
+    CollectionReembeddingError,
+    ReembedGuard,
+    Reembedder,
+    embedding_text,
+    reembed_collection,
+    start_reembed_job,
+)
+from calm.embedding.mock import MockEmbeddingService
+from calm.storage.base import Vector
+from calm.storage.memory import MemoryStore
+from calm.storage.metadata import MetadataStore
+
+
+@pytest.fixture
+async def metadata_store(tmp_path: Path) -> AsyncIterator[MetadataStore]:
+    store = MetadataStore(tmp_path / "metadata.db")
+    await store.initialize()
+    yield store
+    await store.close()
+
+
+@pytest.fixture
+async def store() -> MemoryStore:
+    """A store with three memories embedded at dimension 4."""
+    store = MemoryStore()
+    await store.create_collection("memories", dimension=4)
+    for i in range(3):
+        await store.upsert(
+            "memories",
+            f"m{i}",
+            np.ones(4, dtype=np.float32),
+            {"content": f"memory {i}", "category": "fact"},
+        )
+    return store
+
+
+class TestEmbeddingText:
+    """Tests for rebuilding embedded text from payloads."""
+
+    def test_memories_and_values(self) -> None:
+        assert embedding_text("memories", {"content": "use uv"}) == "use uv"
+        assert embedding_text("values", {"text": "test first"}) == "test first"
+
+    def test_code_units(self) -> None:
+        payload = {
+            "signature": "def retry(n)",
+            "docstring": "Retry n times.",
+            "code": "def retry(n): ...",
+        }
+        assert (
+            embedding_text("code_units", payload)
+            == "def retry(n)\n\nRetry n times.\n\ndef retry(n): ..."
+        )
+
+    def test_first_chunk_of_long_unit(self) -> None:
+        payload = {
+            "signature": "fn long()",
+            "code": "fn long() {\n    a();\n    b();\n}",
+            "start_line": 10,
+            "chunk_index": 0,
+            "chunk_count": 2,
+            "chunk_end_line": 11,
+        }
+        assert (
+            embedding_text("code_units", payload)
+            == "fn long()\n\nfn long() {\n    a();"
+        )
+
+    def test_experience_axis(self) -> None:
+        payload = {
+            "strategy": "divide-and-conquer",
+            "goal": "Fix flaky test",
+            "outcome_status": "confirmed",
+            "iteration_count": 2,
+            "lesson": {"what_worked": "Bisecting", "takeaway": None},
+        }
+        assert embedding_text("ghap_strategy", payload) == (
+            "Strategy: divide-and-conquer\n"
+            "Applied to: Fix flaky test\n"
+            "Outcome: confirmed after 2 iteration(s)\n"
+            "What worked: Bisecting"
+        )
+
+    def test_commits(self) -> None:
+        payload = {"message": "Fix bug", "files_changed": ["a.py"], "author": "Al"}
+        assert (
+            embedding_text("commits", payload)
+            == "Fix bug\n\nFiles: a.py\n\nAuthor: Al"
+        )
+
+    def test_unknown_collection(self) -> None:
+        with pytest.raises(ValueError, match="cannot be re-embedded"):
+            embedding_text("scratch", {})
+
+
+class TestReembedCollection:
+    """Tests for reembed_collection."""
+
+    async def test_swaps_in_new_vectors(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        embedder = MockEmbeddingService(dimension=8)
+
+        stats = await reembed_collection(
+            store, embedder, "memories", "model-b", metadata_store, batch_size=2
+        )
+
+        assert stats.points_embedded == 3
+        info = await store.get_collection_info("memories")
+        assert info is not None
+        assert (info.dimension, info.vector_count) == (8, 3)
+        result = await store.get("memories", "m1", with_vector=True)
+        assert result is not None
+        assert result.payload == {"content": "memory 1", "category": "fact"}
+        assert result.vector is not None
+        np.testing.assert_array_equal(result.vector, await embedder.embed("memory 1"))
+
+        recorded = await metadata_store.get_collection_embedding("memories")
+        assert recorded is not None
+        assert (recorded.model, recorded.dimension) == ("model-b", 8)
+        assert list(store._collections) == ["memories"]
+
+    async def test_catches_up_with_concurrent_writes(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        class WritingEmbedder(MockEmbeddingService):
+            """Writes to the old collection during the first batch."""
+
+            writes_pending = True
+
+            async def embed_batch(self, texts: list[str]) -> list[Vector]:
+                if self.writes_pending:
+                    self.writes_pending = False
+                    await store.upsert(
+                        "memories",
+                        "m3",
+                        np.ones(4, dtype=np.float32),
+                        {"content": "memory 3"},
+                    )
+                    await store.delete("memories", "m0")
+                return await super().embed_batch(texts)
+
+        await reembed_collection(
+            store, WritingEmbedder(dimension=8), "memories", "model-b", metadata_store
+        )
+
+        assert await store.get("memories", "m0") is None
+        assert await store.get("memories", "m3") is not None
+        assert await store.count("memories") == 3
+
+    async def test_failure_keeps_old_collection(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        class FailingEmbedder(MockEmbeddingService):
+            async def embed_batch(self, texts: list[str]) -> list[Vector]:
+                raise RuntimeError("model crashed")
+
+        with pytest.raises(RuntimeError, match="model crashed"):
+            await reembed_collection(
+                store, FailingEmbedder(dimension=8), "memories", "b", metadata_store
+            )
+
+        info = await store.get_collection_info("memories")
+        assert info is not None
+        assert (info.dimension, info.vector_count) == (4, 3)
+        assert list(store._collections) == ["memories"]
+        assert await metadata_store.get_collection_embedding("memories") is None
+
+    async def test_failed_swap_keeps_shadow(
+        self, metadata_store: MetadataStore
+    ) -> None:
+        class HalfSwappingStore(MemoryStore):
+            """Deletes the old collection, then fails to swap in the new one."""
+
+            async def swap_collection(self, name: str, replacement: str) -> None:
+                await self.delete_collection(name)
+                raise RuntimeError("alias update failed")
+
+        store = HalfSwappingStore()
+        await store.create_collection("memories", dimension=4)
+        await store.upsert(
+            "memories", "m0", np.ones(4, dtype=np.float32), {"content": "memory 0"}
+        )
+
+        with pytest.raises(RuntimeError, match="alias update failed"):
+            await reembed_collection(
+                store, MockEmbeddingService(dimension=8), "memories", "b", metadata_store
+            )
+
+        # The shadow holds the only copy of the points, so it is kept
+        [shadow] = list(store._collections)
+        assert shadow.startswith("memories__reembed_")
+        assert await store.count(shadow) == 1
+
+    async def test_concurrent_runs_are_serialized(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        embedder = MockEmbeddingService(dimension=8)
+
+        first, second = await asyncio.gather(
+            reembed_collection(store, embedder, "memories", "b", metadata_store),
+            reembed_collection(
+                store, embedder, "memories", "b", metadata_store, only_if_stale=True
+            ),
+        )
+
+        assert (first.points_embedded, second.points_embedded) == (3, 0)
+        assert list(store._collections) == ["memories"]
+        info = await store.get_collection_info("memories")
+        assert info is not None
+        assert (info.dimension, info.vector_count) == (8, 3)
+
+
+class TestReembedGuard:
+    """Tests for holding back writes to collections being re-embedded."""
+
+    async def test_held_writes_apply_after_swap(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        guard = ReembedGuard(store)
+        guard.hold("memories")
+        embedder = MockEmbeddingService(dimension=8)
+
+        # Written with the new model while the old vectors are still stored
+        await guard.upsert(
+            "memories", "m3", await embedder.embed("memory 3"), {"content": "memory 3"}
+        )
+        await guard.delete("memories", "m0")
+        assert await guard.get("memories", "m3") is None
+        assert await guard.get("memories", "m0") is not None
+        with pytest.raises(CollectionReembeddingError, match="re-embedded"):
+            await guard.search("memories", await embedder.embed("memory"))
+
+        await reembed_collection(guard, embedder, "memories", "b", metadata_store)
+
+        assert not guard.is_held("memories")
+        assert await guard.get("memories", "m0") is None
+        assert await guard.get("memories", "m3") is not None
+        assert len(await guard.search("memories", await embedder.embed("memory"))) == 3
+
+    async def test_writes_replayed_after_failure(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        class FailingEmbedder(MockEmbeddingService):
+            async def embed_batch(self, texts: list[str]) -> list[Vector]:
+                raise RuntimeError("model crashed")
+
+        await metadata_store.set_collection_embedding("memories", "semantic-a", 4)
+        guard = ReembedGuard(store)
+        reembedder = Reembedder(
+            guard,
+            metadata_store,
+            MockEmbeddingService(dimension=4),
+            FailingEmbedder(dimension=4),
+            "code-model",
+            "semantic-b",
+            guard=guard,
+        )
+        guard.hold("memories")
+        await guard.upsert(
+            "memories", "m3", np.ones(4, dtype=np.float32), {"content": "memory 3"}
+        )
+        await guard.delete("memories", "m0")
+
+        [stats] = await reembedder.reembed_stale(["memories"])
+
+        assert stats.error == "model crashed"
+        assert not guard.is_held("memories")
+        assert await guard.get("memories", "m0") is None
+        assert await guard.get("memories", "m3") is not None
+        assert await reembedder.is_stale("memories")
+
+    async def test_job_holds_only_stale_collections(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        held: list[list[str]] = []
+
+        class ObservingEmbedder(MockEmbeddingService):
+            """Records which collections are held while embedding."""
+
+            async def embed_batch(self, texts: list[str]) -> list[Vector]:
+                held.append([c for c in ("memories", "values") if guard.is_held(c)])
+                return await super().embed_batch(texts)
+
+        await metadata_store.set_collection_embedding("memories", "semantic-a", 4)
+        await store.create_collection("values", dimension=4)
+        await metadata_store.set_collection_embedding("values", "semantic-b", 4)
+        guard = ReembedGuard(store)
+        reembedder = Reembedder(
+            guard,
+            metadata_store,
+            MockEmbeddingService(dimension=4),
+            ObservingEmbedder(dimension=4),
+            "code-model",
+            "semantic-b",
+            guard=guard,
+        )
+
+        task = start_reembed_job(reembedder)
+        assert not guard.is_held("memories")
+        results = await task
+
+        assert [r.collection for r in results] == ["memories"]
+        assert held and all(h == ["memories"] for h in held)
+        assert not any(guard.is_held(c) for c in ("memories", "code_units", "values"))
+
+
+class TestReembedder:
+    """Tests for Reembedder."""
+
+    def _reembedder(
+        self, store: MemoryStore, metadata_store: MetadataStore, dimension: int
+    ) -> Reembedder:
+        return Reembedder(
+            store,
+            metadata_store,
+            MockEmbeddingService(dimension=dimension),
+            MockEmbeddingService(dimension=dimension),
+            "code-model",
+            "semantic-b",
+        )
+
+    async def test_unrecorded_collection_is_adopted(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        reembedder = self._reembedder(store, metadata_store, dimension=4)
+
+        assert await reembedder.reembed_stale() == []
+
+        recorded = await metadata_store.get_collection_embedding("memories")
+        assert recorded is not None
+        assert recorded.model == "semantic-b"
+        assert await metadata_store.get_collection_embedding("commits") is None
+
+    async def test_model_change_is_stale(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        await metadata_store.set_collection_embedding("memories", "semantic-a", 4)
+        reembedder = self._reembedder(store, metadata_store, dimension=4)
+
+        assert await reembedder.is_stale("memories")
+        results = await reembedder.reembed_stale()
+
+        assert [(r.collection, r.points_embedded) for r in results] == [
+            ("memories", 3)
+        ]
+        assert not await reembedder.is_stale("memories")
+
+    async def test_dimension_change_is_stale(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        reembedder = self._reembedder(store, metadata_store, dimension=8)
+
+        assert await reembedder.is_stale("memories")
+        assert not await reembedder.is_stale("commits")
+
+    async def test_failures_dont_stop_other_collections(
+        self, store: MemoryStore, metadata_store: MetadataStore
+    ) -> None:
+        class FailingEmbedder(MockEmbeddingService):
+            async def embed_batch(self, texts: list[str]) -> list[Vector]:
+                if "" in texts:
+                    raise RuntimeError("empty text")
+                return await super().embed_batch(texts)
+
+        await store.upsert("memories", "blank", np.ones(4, dtype=np.float32), {})
+        await store.create_collection("values", dimension=4)
+        await store.upsert(
+            "values", "v1", np.ones(4, dtype=np.float32), {"text": "test first"}
+        )
+        reembedder = Reembedder(
+            store,
+            metadata_store,
+            MockEmbeddingService(dimension=4),
+            FailingEmbedder(dimension=4),
+            "code-model",
+            "semantic-b",
+        )
+
+        # force re-embeds collections that look current
+        results = await reembedder.reembed_stale(["memories", "values"], force=True)
+
+        assert [(r.collection, r.error) for r in results] == [
+            ("memories", "empty text"),
+            ("values", None),
+        ]
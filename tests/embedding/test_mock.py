@@ -1,9 +1,9 @@
-"""Tests for MockEmbeddingService implementation."""
+"""Tests for MockEmbeddingService and MockReranker implementations."""
 
 import numpy as np
 import pytest
 
-from calm.embedding.mock import MockEmbeddingService
+from calm.embedding.mock import MockEmbeddingService, MockReranker
 
 
 class TestMockEmbedding:
@@ -131,3 +131,19 @@ class TestMockEmbedding:
         assert embedding.shape == (768,)
         assert embedding.dtype == np.float32
         assert np.isclose(np.linalg.norm(embedding), 1.0, rtol=1e-5)
+
+
+class TestMockReranker:
+    """Test suite for MockReranker."""
+
+    async def test_score_by_query_term_overlap(self) -> None:
+        """Test that scores are the fraction of query terms matched."""
+        scores = await MockReranker().score(
+            "Retry Backoff", ["def retry(): backoff()", "retry once", "unrelated"]
+        )
+        assert scores == [1.0, 0.5, 0.0]
+
+    async def test_score_empty_inputs(self) -> None:
+        """Test empty documents and empty queries."""
+        assert await MockReranker().score("query", []) == []
+        assert await MockReranker().score("  ", ["a", "b"]) == [0.0, 0.0]
@@ -0,0 +1,108 @@
+"""Tests for embedding provider discovery."""
+
+from types import SimpleNamespace
+from typing import Any
+
+import pytest
+
+import calm.embedding.providers as providers_module
+from calm.embedding.base import EmbeddingModelError
+from calm.embedding.mock import MockEmbeddingService
+from calm.embedding.providers import (
+    available_providers,
+    create_embedder,
+    load_provider,
+)
+from calm.embedding.registry import EmbeddingRegistry
+
+
+def mock_provider(model_name: str, dimension: int = 8) -> MockEmbeddingService:
+    """Provider registered by a fake plugin package."""
+    return MockEmbeddingService(dimension=dimension, max_tokens=len(model_name))
+
+
+@pytest.fixture
+def plugin(monkeypatch: pytest.MonkeyPatch) -> None:
+    """Install a "mock" provider and a broken one via entry points."""
+
+    def broken() -> Any:
+        raise ImportError("No module named 'onnxruntime'")
+
+    installed = [
+        SimpleNamespace(name="mock", load=lambda: mock_provider),
+        SimpleNamespace(name="onnx", load=broken),
+        SimpleNamespace(name="nomic", load=lambda: mock_provider),
+    ]
+    monkeypatch.setattr(
+        providers_module, "entry_points", lambda group: installed
+    )
+
+
+class TestProviders:
+    """Tests for provider discovery and loading."""
+
+    def test_available_providers(self, plugin: None) -> None:
+        assert available_providers() == [
+            "http",
+            "mock",
+            "nomic",
+            "onnx",
+            "sentence-transformers",
+        ]
+
+    def test_builtins_take_precedence(self, plugin: None) -> None:
+        from calm.embedding.nomic import NomicEmbedding
+
+        assert load_provider("nomic") is NomicEmbedding
+
+    def test_create_embedder_with_options(self, plugin: None) -> None:
+        embedder = create_embedder("mock", "tiny-model", {"dimension": 16})
+
+        assert isinstance(embedder, MockEmbeddingService)
+        assert embedder.dimension == 16
+        assert embedder.max_tokens == 10
+
+    def test_unknown_provider(self, plugin: None) -> None:
+        with pytest.raises(EmbeddingModelError, match="Available: http, mock"):
+            load_provider("gguf")
+
+    def test_provider_import_error(self, plugin: None) -> None:
+        with pytest.raises(EmbeddingModelError, match="onnxruntime"):
+            load_provider("onnx")
+
+    def test_provider_must_return_embedding_service(
+        self, monkeypatch: pytest.MonkeyPatch
+    ) -> None:
+        installed = [SimpleNamespace(name="bad", load=lambda: lambda model: model)]
+        monkeypatch.setattr(
+            providers_module, "entry_points", lambda group: installed
+        )
+
+        with pytest.raises(EmbeddingModelError, match="returned str"):
+            create_embedder("bad", "model")
+
+    def test_registry_uses_configured_providers(self, plugin: None) -> None:
+        registry = EmbeddingRegistry(
+            "code-model",
+            "semantic-model",
+            code_provider="mock",
+            semantic_provider="mock",
+            provider_options={"mock": {"dimension": 32}},
+        )
+
+        code = registry.get_code_embedder()
+        assert code.dimension == 32
+        assert registry.get_semantic_embedder() is not code
+        assert registry.get_code_embedder() is code
+
+    def test_registry_options_by_purpose(self, plugin: None) -> None:
+        registry = EmbeddingRegistry(
+            "code-model",
+            "semantic-model",
+            code_provider="mock",
+            semantic_provider="mock",
+            provider_options={"mock": {"dimension": 32}, "semantic": {"dimension": 64}},
+        )
+
+        assert registry.get_code_embedder().dimension == 32
+        assert registry.get_semantic_embedder().dimension == 64
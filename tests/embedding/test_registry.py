@@ -8,6 +8,7 @@ import pytest
 from calm.embedding.registry import (
     EmbeddingRegistry,
     get_code_embedder,
+    get_reranker,
     get_semantic_embedder,
     initialize_registry,
 )
@@ -231,3 +232,29 @@ def test_direct_registry_usage() -> None:
     # Second access returns cached
     assert registry.get_code_embedder() is code
     assert registry.get_semantic_embedder() is semantic
+
+
+def test_reranker_lazy_loading() -> None:
+    """Test that the reranker is only loaded on first access."""
+    registry = EmbeddingRegistry(
+        "sentence-transformers/all-MiniLM-L6-v2",
+        "nomic-ai/nomic-embed-text-v1.5",
+        rerank_model="cross-encoder/test-model",
+    )
+    assert registry._reranker is None
+
+    with patch("calm.embedding.cross_encoder.CrossEncoderReranker") as reranker_cls:
+        reranker = registry.get_reranker()
+        assert registry.get_reranker() is reranker
+
+    reranker_cls.assert_called_once_with(model_name="cross-encoder/test-model")
+
+
+def test_get_reranker_not_initialized() -> None:
+    """Test that the global reranker requires an initialized registry."""
+    import calm.embedding.registry as registry_module
+
+    registry_module._registry = None
+
+    with pytest.raises(RuntimeError, match="Registry not initialized"):
+        get_reranker()
@@ -0,0 +1,47 @@
+using System;
+
+namespace Sample.Shapes
+{
+    /// <summary>
+    /// A circle with a radius.
+    /// </summary>
+    public class Circle
+    {
+        private readonly double radius;
+
+        public Circle(double radius)
+        {
+            this.radius = radius;
+        }
+
+        /// <summary>Compute the area.</summary>
+        public double Area()
+        {
+            return Math.PI * radius * radius;
+        }
+
+        public string Describe()
+        {
+            if (radius <= 0)
+            {
+                return "empty";
+            }
+            foreach (var part in new[] { "a", "b" })
+            {
+                Console.WriteLine(part);
+            }
+            return radius > 10 ? "large" : "small";
+        }
+    }
+
+    public interface IShape
+    {
+        double Area();
+    }
+
+    public struct Size
+    {
+        public int Width;
+        public int Height;
+    }
+}
@@ -0,0 +1,44 @@
+// Package sample is a Go module for testing code parsing.
+package sample
+
+import "fmt"
+
+// Point is a point in 2D space.
+type Point struct {
+	X, Y int
+}
+
+type (
+	// Shape has an area.
+	Shape interface {
+		Area() float64
+	}
+	ID string
+)
+
+// NewPoint creates a point.
+func NewPoint(x, y int) *Point {
+	return &Point{X: x, Y: y}
+}
+
+// Translate moves the point by an offset.
+func (p *Point) Translate(dx, dy int) {
+	p.X += dx
+	p.Y += dy
+}
+
+func Classify(n int) string {
+	if n < 0 {
+		return "negative"
+	}
+	switch {
+	case n == 0:
+		return "zero"
+	case n > 100:
+		return "large"
+	}
+	for i := 0; i < n; i++ {
+		fmt.Println(i)
+	}
+	return "positive"
+}
@@ -25,6 +25,14 @@ public class Calculator {
     public int getValue() {
         return this.value;
     }
+
+    /**
+     * Running history of results
+     */
+    static class History {
+        void record(int result) {
+        }
+    }
 }
 
 /**
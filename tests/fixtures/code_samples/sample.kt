@@ -0,0 +1,41 @@
+package com.example.sample
+
+/**
+ * A simple calculator.
+ */
+class Calculator(private var value: Int) {
+    /**
+     * Add to the current value.
+     */
+    fun add(x: Int): Int {
+        value += x
+        return value
+    }
+
+    fun sign(): String {
+        return when {
+            value < 0 -> "negative"
+            value == 0 -> "zero"
+            else -> "positive"
+        }
+    }
+
+    companion object {
+        fun zero(): Calculator = Calculator(0)
+    }
+}
+
+interface Operation {
+    fun execute(x: Int, y: Int): Int
+}
+
+object Registry {
+    fun register(name: String) {
+        println(name)
+    }
+}
+
+/**
+ * Add two numbers.
+ */
+fun add(a: Int, b: Int): Int = a + b
@@ -2,6 +2,7 @@
 
 local M = {}
 
+--- Add two numbers
 function M.add(a, b)
     return a + b
 end
@@ -24,4 +25,8 @@ function M.process(items)
     return result
 end
 
+function M:reset()
+    self.total = 0
+end
+
 return M
@@ -57,3 +57,16 @@ class Calculator:
         else:
             self.value *= x
         return self.value
+
+
+def make_counter(start: int = 0):
+    """Create a counter closure."""
+    count = start
+
+    def increment() -> int:
+        """Advance the counter."""
+        nonlocal count
+        count += 1
+        return count
+
+    return increment
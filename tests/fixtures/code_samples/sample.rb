@@ -0,0 +1,34 @@
+# Sample Ruby module for testing code parsing
+
+module Shapes
+  # A circle with a radius
+  class Circle
+    def initialize(radius)
+      @radius = radius
+    end
+
+    # Compute the area
+    def area
+      Math::PI * @radius * @radius
+    end
+
+    def describe
+      if @radius <= 0
+        "empty"
+      elsif @radius > 10
+        "large"
+      else
+        "small"
+      end
+    end
+
+    def self.unit
+      new(1)
+    end
+  end
+end
+
+# Greet someone
+def greet(name)
+  puts "Hello, #{name}"
+end
@@ -1,5 +1,9 @@
-/// Sample Rust module for testing code parsing
+//! Sample Rust module for testing code parsing
 
+use std::fmt;
+
+/// A point in 2D space
+#[derive(Debug, Clone)]
 pub struct Point {
     x: i32,
     y: i32,
@@ -18,6 +22,11 @@ pub fn distance(p1: &Point, p2: &Point) -> f64 {
     (dx * dx + dy * dy).sqrt()
 }
 
+/// The origin point
+pub(crate) fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
 impl Point {
     /// Create a new point
     pub fn new(x: i32, y: i32) -> Self {
@@ -30,3 +39,19 @@ impl Point {
         self.y += dy;
     }
 }
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+mod shapes {
+    /// Area of the unit square
+    pub fn unit_area() -> f64 {
+        fn side() -> f64 {
+            1.0
+        }
+        side() * side()
+    }
+}
@@ -41,3 +41,20 @@ export class UserService {
         return this.users.find(u => u.id === id);
     }
 }
+
+/**
+ * Double a number
+ */
+export const double = (n: number): number => n * 2;
+
+/**
+ * Validation helpers
+ */
+export namespace Validation {
+    /**
+     * Check for an email address
+     */
+    export function isEmail(value: string): boolean {
+        return value.includes("@");
+    }
+}
@@ -0,0 +1,133 @@
+"""Tests for Cargo manifest discovery and Rust module paths."""
+
+from calm.indexers.cargo import (
+    find_crate,
+    load_crate,
+    load_workspace,
+    rust_module_path,
+)
+
+
+def _make_crate(root, name="my-crate", version="0.2.0"):
+    root.mkdir(parents=True, exist_ok=True)
+    (root / "Cargo.toml").write_text(
+        f'[package]\nname = "{name}"\nversion = "{version}"\n'
+    )
+    (root / "src").mkdir(exist_ok=True)
+    return root
+
+
+def test_load_crate_normalizes_name(tmp_path):
+    """Crate names use underscores, as in Rust paths."""
+    _make_crate(tmp_path)
+    crate = load_crate(tmp_path / "Cargo.toml")
+    assert crate is not None
+    assert crate.name == "my_crate"
+    assert crate.version == "0.2.0"
+
+
+def test_load_crate_virtual_manifest(tmp_path):
+    """A workspace-only manifest has no package."""
+    (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["a"]\n')
+    assert load_crate(tmp_path / "Cargo.toml") is None
+
+
+def test_find_crate_nearest_manifest(tmp_path):
+    """The nearest package manifest above the file wins."""
+    _make_crate(tmp_path, name="outer")
+    inner = _make_crate(tmp_path / "crates" / "inner", name="inner")
+    source = inner / "src" / "lib.rs"
+    source.write_text("")
+
+    crate = find_crate(source)
+    assert crate is not None
+    assert crate.name == "inner"
+
+
+def test_find_crate_stops_at_repo_root(tmp_path):
+    """Manifests above a .git directory are ignored."""
+    _make_crate(tmp_path, name="unrelated")
+    repo = tmp_path / "repo"
+    (repo / ".git").mkdir(parents=True)
+    (repo / "main.rs").write_text("")
+
+    assert find_crate(repo / "main.rs") is None
+
+
+def test_rust_module_path_mapping(tmp_path):
+    """File layout maps onto module paths."""
+    root = _make_crate(tmp_path)
+    crate = load_crate(root / "Cargo.toml")
+    assert crate is not None
+
+    def path_of(relative):
+        return rust_module_path(root / relative, crate)
+
+    assert path_of("src/lib.rs") == "my_crate"
+    assert path_of("src/main.rs") == "my_crate"
+    assert path_of("src/foo/mod.rs") == "my_crate::foo"
+    assert path_of("src/foo/bar.rs") == "my_crate::foo::bar"
+    assert path_of("src/bin/tool.rs") == "tool"
+    assert path_of("src/bin/tool/main.rs") == "tool"
+    assert path_of("tests/integration.rs") == "my_crate::tests::integration"
+
+
+def _make_workspace(root):
+    root.mkdir(parents=True, exist_ok=True)
+    (root / "Cargo.toml").write_text(
+        "[workspace]\n"
+        'members = ["crates/*"]\n'
+        'exclude = ["crates/legacy"]\n'
+        'default-members = ["crates/net"]\n'
+        "\n[workspace.package]\n"
+        'version = "1.4.0"\n'
+    )
+    net = _make_crate(root / "crates" / "net", name="net")
+    (net / "Cargo.toml").write_text(
+        '[package]\nname = "net"\nversion.workspace = true\n'
+    )
+    _make_crate(root / "crates" / "core-utils", name="core-utils")
+    _make_crate(root / "crates" / "legacy", name="legacy")
+    return root
+
+
+def test_load_workspace_members(tmp_path):
+    """Members globs are expanded and exclude entries dropped."""
+    workspace = load_workspace(_make_workspace(tmp_path))
+    assert workspace is not None
+
+    names = sorted(c.name for c in workspace.members)
+    assert names == ["core_utils", "net"]
+    assert [c.name for c in workspace.default_members] == ["net"]
+
+
+def test_load_workspace_inherits_version(tmp_path):
+    """version.workspace = true resolves to [workspace.package].version."""
+    workspace = load_workspace(_make_workspace(tmp_path))
+    assert workspace is not None
+
+    net = next(c for c in workspace.members if c.name == "net")
+    assert net.version == "1.4.0"
+
+
+def test_workspace_crate_for(tmp_path):
+    """Files map to the member crate that contains them."""
+    root = _make_workspace(tmp_path)
+    workspace = load_workspace(root)
+    assert workspace is not None
+
+    crate = workspace.crate_for(root / "crates" / "net" / "src" / "retry.rs")
+    assert crate is not None
+    assert crate.name == "net"
+    assert workspace.crate_for(root / "crates" / "legacy" / "src" / "lib.rs") is None
+    assert workspace.is_excluded(root / "crates" / "legacy" / "src" / "lib.rs")
+    assert not workspace.is_excluded(root / "crates" / "net" / "src" / "retry.rs")
+
+
+def test_load_workspace_single_package(tmp_path):
+    """A plain package is a one-member workspace."""
+    _make_crate(tmp_path)
+    workspace = load_workspace(tmp_path)
+    assert workspace is not None
+    assert [c.name for c in workspace.members] == ["my_crate"]
+    assert load_workspace(tmp_path / "src") is None
@@ -0,0 +1,82 @@
+"""Tests for splitting long units into chunks."""
+
+from calm.indexers.base import SemanticUnit, UnitType
+from calm.indexers.chunking import chunk_id, chunk_unit
+from calm.indexers.utils import generate_unit_id
+
+
+def _unit(line_count: int, docstring: str | None = None) -> SemanticUnit:
+    lines = ["fn long() {"]
+    lines += [f"    let x{i} = {i};" for i in range(line_count)]
+    lines.append("}")
+    return SemanticUnit(
+        name="long",
+        qualified_name="lib::long",
+        unit_type=UnitType.FUNCTION,
+        signature="fn long()",
+        content="\n".join(lines),
+        file_path="/repo/src/lib.rs",
+        start_line=10,
+        end_line=10 + line_count + 1,
+        language="rust",
+        docstring=docstring,
+    )
+
+
+class TestChunkUnit:
+    """Tests for chunk_unit."""
+
+    def test_short_unit_is_one_chunk(self) -> None:
+        unit = _unit(3, docstring="Does things.")
+
+        chunks = chunk_unit(unit, max_chars=1000, overlap_chars=50)
+
+        assert len(chunks) == 1
+        assert chunks[0].content == unit.content
+        assert chunks[0].text == f"fn long()\n\nDoes things.\n\n{unit.content}"
+        assert (chunks[0].start_line, chunks[0].end_line) == (10, 14)
+
+    def test_long_unit_is_split_with_overlap(self) -> None:
+        unit = _unit(40)
+
+        chunks = chunk_unit(unit, max_chars=200, overlap_chars=40)
+
+        assert len(chunks) > 1
+        assert [c.index for c in chunks] == list(range(len(chunks)))
+        assert all(len(c.text) <= 200 for c in chunks)
+        assert all(c.text.startswith("fn long()\n\n") for c in chunks)
+        assert chunks[0].start_line == 10
+        assert chunks[-1].end_line == unit.end_line
+        lines = unit.content.split("\n")
+        for chunk in chunks:
+            assert chunk.content == "\n".join(
+                lines[chunk.start_line - 10 : chunk.end_line - 9]
+            )
+        for before, after in zip(chunks, chunks[1:]):
+            # Two 19-20 character lines fit in the 40 character overlap
+            assert after.start_line == before.end_line - 1
+
+    def test_long_docstring_leaves_room_for_code(self) -> None:
+        unit = _unit(40, docstring="Explains. " * 30)
+
+        chunks = chunk_unit(unit, max_chars=200, overlap_chars=0)
+
+        assert all(len(c.content) <= 100 for c in chunks)
+        assert chunks[-1].end_line == unit.end_line
+
+    def test_overlong_line_is_cut(self) -> None:
+        unit = _unit(0)
+        unit.content = "x" * 500
+
+        chunks = chunk_unit(unit, max_chars=100, overlap_chars=20)
+
+        assert len(chunks) == 1
+        assert len(chunks[0].text) == 100
+
+
+def test_chunk_id() -> None:
+    assert chunk_id("p", "/a.rs", "lib::long", 0) == generate_unit_id(
+        "p", "/a.rs", "lib::long"
+    )
+    ids = {chunk_id("p", "/a.rs", "lib::long", i) for i in range(3)}
+    assert len(ids) == 3
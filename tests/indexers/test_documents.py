@@ -0,0 +1,181 @@
+"""Tests for splitting Markdown and config files into units."""
+
+import pytest
+
+from calm.indexers import DocumentParser, TreeSitterParser, UnitType
+
+
+@pytest.fixture
+def parser():
+    return DocumentParser()
+
+
+def _units(parser, source, file_path, language):
+    return {
+        u.qualified_name: u
+        for u in parser.extract_units(source, file_path, language)
+    }
+
+
+def test_detect_language(parser):
+    """Document formats are detected from the file extension."""
+    assert parser.detect_language("README.md") == "markdown"
+    assert parser.detect_language("notes.markdown") == "markdown"
+    assert parser.detect_language("Cargo.toml") == "toml"
+    assert parser.detect_language("ci.yml") == "yaml"
+    assert parser.detect_language("compose.yaml") == "yaml"
+    assert parser.detect_language("main.py") is None
+
+
+def test_markdown_sections(parser):
+    """Headings split Markdown into sections qualified by their parents."""
+    source = (
+        "Intro text.\n"
+        "\n"
+        "# Setup\n"
+        "Read this first.\n"
+        "\n"
+        "## From source ##\n"
+        "```sh\n"
+        "# not a heading\n"
+        "make install\n"
+        "```\n"
+        "\n"
+        "## Empty\n"
+        "# Usage v1.2\n"
+        "Run it.\n"
+    )
+    units = _units(parser, source, "docs/README.md", "markdown")
+
+    assert list(units) == [
+        "README",
+        "README.Setup",
+        "README.Setup.From source",
+        "README.Usage v1.2",
+    ]
+    assert all(u.unit_type == UnitType.SECTION for u in units.values())
+    assert all(u.language == "markdown" for u in units.values())
+
+    intro = units["README"]
+    assert intro.name == "README"
+    assert (intro.start_line, intro.end_line) == (1, 1)
+
+    setup = units["README.Setup"]
+    assert setup.name == "Setup"
+    assert setup.signature == "# Setup"
+    assert (setup.start_line, setup.end_line) == (3, 4)
+
+    from_source = units["README.Setup.From source"]
+    assert from_source.name == "From source"
+    assert "# not a heading" in from_source.content
+    assert (from_source.start_line, from_source.end_line) == (6, 10)
+
+    assert units["README.Usage v1.2"].content == "# Usage v1.2\nRun it."
+
+
+def test_markdown_front_matter(parser):
+    """Front matter belongs to the text before the first heading."""
+    source = "---\ntitle: Notes\n# tags\n---\n# Notes\nBody\n"
+    units = _units(parser, source, "notes.md", "markdown")
+
+    assert list(units) == ["notes", "notes.Notes"]
+    assert units["notes"].content == "---\ntitle: Notes\n# tags\n---"
+
+
+def test_toml_tables(parser):
+    """TOML tables and the keys before them become config units."""
+    source = (
+        "# Shared settings\n"
+        "edition = \"2021\"\n"
+        "\n"
+        "[package]\n"
+        "name = \"net\"\n"
+        "description = \"\"\"\n"
+        "[not a table]\n"
+        "\"\"\"\n"
+        "\n"
+        "# Runtime dependencies\n"
+        "# (kept minimal)\n"
+        "[ dependencies ]\n"
+        "serde = \"1\"\n"
+        "\n"
+        "[[bin]]\n"
+        "name = \"a\"\n"
+        "[[bin]]\n"
+        "name = \"b\"\n"
+        "[tool . lint]\n"
+        "strict = true\n"
+    )
+    units = _units(parser, source, "net/Cargo.toml", "toml")
+
+    assert list(units) == [
+        "Cargo.edition",
+        "Cargo.package",
+        "Cargo.dependencies",
+        "Cargo.bin",
+        "Cargo.bin (2)",
+        "Cargo.tool.lint",
+    ]
+    assert all(u.unit_type == UnitType.CONFIG for u in units.values())
+    assert units["Cargo.edition"].docstring == "Shared settings"
+
+    package = units["Cargo.package"]
+    assert package.name == "package"
+    assert package.docstring is None
+    assert (package.start_line, package.end_line) == (4, 8)
+
+    dependencies = units["Cargo.dependencies"]
+    assert dependencies.docstring == "Runtime dependencies\n(kept minimal)"
+    assert dependencies.signature == "[ dependencies ]"
+    assert (dependencies.start_line, dependencies.end_line) == (12, 13)
+
+    assert units["Cargo.bin (2)"].content == '[[bin]]\nname = "b"'
+
+
+def test_yaml_keys(parser):
+    """Top-level YAML keys become config units, split at document markers."""
+    source = (
+        "# Build pipeline\n"
+        "name: ci\n"
+        "\"on\": [push]\n"
+        "jobs:\n"
+        "  test:\n"
+        "    steps:\n"
+        "      - run: make\n"
+        "---\n"
+        "url: http://example.com\n"
+        "...\n"
+    )
+    units = _units(parser, source, ".github/ci.yml", "yaml")
+
+    assert list(units) == ["ci.name", "ci.on", "ci.jobs", "ci.url"]
+    assert units["ci.name"].docstring == "Build pipeline"
+    assert units["ci.jobs"].end_line == 7
+    assert units["ci.url"].content == "url: http://example.com"
+    assert all(u.language == "yaml" for u in units.values())
+
+
+def test_config_without_keys(parser):
+    """A config file without top-level keys is a single unit."""
+    source = "\n- alpha\n- beta\n\n"
+    units = _units(parser, source, "list.yaml", "yaml")
+
+    assert list(units) == ["list"]
+    assert units["list"].content == "- alpha\n- beta"
+    assert (units["list"].start_line, units["list"].end_line) == (2, 3)
+
+    assert parser.extract_units("\n\n", "blank.toml", "toml") == []
+
+
+@pytest.mark.asyncio
+async def test_documents_parsed_alongside_code(tmp_path):
+    """The code parser hands document files to the document parser."""
+    parser = TreeSitterParser()
+    assert {"markdown", "toml", "yaml"} <= set(parser.supported_languages())
+
+    path = tmp_path / "CHANGES.md"
+    path.write_text("# Changes\n\n## 1.0\nFirst release.\n")
+    units = await parser.parse_file(str(path))
+
+    assert [u.qualified_name for u in units] == ["CHANGES.Changes.1.0"]
+    assert units[0].language == "markdown"
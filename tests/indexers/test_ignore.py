@@ -0,0 +1,122 @@
+"""Tests for gitignore-style exclusion rules."""
+
+from pathlib import Path
+
+import pytest
+
+from calm.indexers.ignore import (
+    ExclusionSettings,
+    IgnoreMatcher,
+    parse_ignore_pattern,
+)
+
+BASE = Path("/repo")
+
+
+def _ignored(patterns: list[str], path: str, is_dir: bool = False) -> bool:
+    rules = [parse_ignore_pattern(p, BASE) for p in patterns]
+    matcher = IgnoreMatcher(rules=[r for r in rules if r is not None])
+    return matcher.is_ignored(BASE / path, is_dir=is_dir)
+
+
+@pytest.fixture(autouse=True)
+def _no_global_excludes(monkeypatch, tmp_path):
+    """Keep the developer's global git excludes out of these tests."""
+    monkeypatch.setenv("HOME", str(tmp_path / "home"))
+    monkeypatch.setenv("XDG_CONFIG_HOME", str(tmp_path / "home" / ".config"))
+
+
+class TestPatterns:
+    """Tests for pattern semantics."""
+
+    def test_unanchored_matches_at_any_depth(self) -> None:
+        assert _ignored(["*.gen.py"], "a/b/model.gen.py")
+        assert _ignored(["vendor"], "src/vendor", is_dir=True)
+
+    def test_anchored_matches_only_at_base(self) -> None:
+        assert _ignored(["/build"], "build", is_dir=True)
+        assert not _ignored(["/build"], "src/build", is_dir=True)
+        assert _ignored(["src/gen"], "src/gen", is_dir=True)
+        assert not _ignored(["src/gen"], "lib/src/gen", is_dir=True)
+
+    def test_directory_only_rules(self) -> None:
+        assert _ignored(["fixtures/"], "tests/fixtures", is_dir=True)
+        assert not _ignored(["fixtures/"], "tests/fixtures", is_dir=False)
+
+    def test_double_star(self) -> None:
+        assert _ignored(["docs/**/*.py"], "docs/a/b/conf.py")
+        assert _ignored(["docs/**/*.py"], "docs/conf.py")
+        assert _ignored(["**/generated"], "a/b/generated", is_dir=True)
+        assert _ignored(["out/**"], "out/x/y.py")
+
+    def test_negation_last_match_wins(self) -> None:
+        assert not _ignored(["*.py", "!keep.py"], "keep.py")
+        assert _ignored(["!keep.py", "*.py"], "keep.py")
+
+    def test_comments_escapes_and_classes(self) -> None:
+        assert parse_ignore_pattern("# comment", BASE) is None
+        assert parse_ignore_pattern("   ", BASE) is None
+        assert _ignored(["\\#notes.py"], "#notes.py")
+        assert _ignored(["[!a]b.py"], "cb.py")
+        assert not _ignored(["[!a]b.py"], "ab.py")
+        assert _ignored(["file?.py"], "file1.py")
+
+
+class TestMatcher:
+    """Tests for IgnoreMatcher sources and precedence."""
+
+    def test_ignore_files_in_repository(self, tmp_path: Path) -> None:
+        repo = tmp_path / "repo"
+        (repo / ".git" / "info").mkdir(parents=True)
+        (repo / ".git" / "info" / "exclude").write_text("secret.py\n")
+        (repo / ".gitignore").write_text("*.gen.py\n")
+        (repo / "pkg").mkdir()
+        (repo / "pkg" / ".gitignore").write_text("!keep.gen.py\n")
+        (repo / "pkg" / ".calmignore").write_text("fixtures/\n")
+
+        # Indexing a subdirectory still applies the repository root rules
+        root = repo / "pkg"
+        matcher = IgnoreMatcher.for_root(root)
+
+        assert matcher.is_path_ignored(root, root / "secret.py")
+        assert matcher.is_path_ignored(root, root / "model.gen.py")
+        assert not matcher.is_path_ignored(root, root / "keep.gen.py")
+        assert matcher.is_path_ignored(root, root / "fixtures" / "data.py")
+        assert not matcher.is_path_ignored(root, root / "main.py")
+
+    def test_global_excludes_file(self, tmp_path: Path) -> None:
+        home = tmp_path / "home"
+        home.mkdir()
+        excludes = tmp_path / "global_ignore"
+        excludes.write_text("*.scratch.py\n")
+        (home / ".gitconfig").write_text(
+            f"[user]\n\tname = dev\n[core]\n\texcludesFile = {excludes}\n"
+        )
+        root = tmp_path / "project"
+        root.mkdir()
+
+        matcher = IgnoreMatcher.for_root(root)
+        assert matcher.is_path_ignored(root, root / "try.scratch.py")
+
+    def test_settings_patterns_and_toggles(self, tmp_path: Path) -> None:
+        (tmp_path / ".gitignore").write_text("gen/\n")
+        (tmp_path / ".calmignore").write_text("legacy.py\n")
+
+        matcher = IgnoreMatcher.for_root(
+            tmp_path,
+            ExclusionSettings(
+                respect_gitignore=False, patterns=["*.pb.py", "!legacy.py"]
+            ),
+        )
+
+        assert not matcher.is_path_ignored(tmp_path, tmp_path / "gen" / "a.py")
+        assert matcher.is_path_ignored(tmp_path, tmp_path / "api.pb.py")
+        # Project patterns win over .calmignore
+        assert not matcher.is_path_ignored(tmp_path, tmp_path / "legacy.py")
+
+    def test_settings_round_trip(self) -> None:
+        settings = ExclusionSettings(respect_calmignore=False, patterns=["x/"])
+        stored = {ExclusionSettings.SETTINGS_KEY: settings.to_dict()}
+
+        assert ExclusionSettings.from_settings(stored) == settings
+        assert ExclusionSettings.from_settings({}) == ExclusionSettings()
@@ -55,6 +55,25 @@ async def test_index_single_file(indexer):
     assert count == stats.units_indexed
 
 
+@pytest.mark.asyncio
+async def test_cpp_header_recorded_as_cpp(indexer, tmp_path):
+    """Test that file metadata records the language the header parsed as."""
+    header = tmp_path / "shape.h"
+    header.write_text(
+        "namespace geo {\nclass Shape {\npublic:\n    double area() const;\n};\n}\n"
+    )
+    await indexer.index_file(str(header), "test_project")
+
+    indexed = await indexer.metadata_store.get_indexed_file(
+        str(header), "test_project"
+    )
+    assert indexed is not None
+    assert indexed.language == "cpp"
+    assert (await indexer.get_indexing_stats("test_project"))["languages"] == {
+        "cpp": 1
+    }
+
+
 @pytest.mark.asyncio
 async def test_index_file_twice_skips_second(indexer):
     """Test that reindexing unchanged file is skipped."""
@@ -168,6 +187,397 @@ def func1():
         Path(temp_path).unlink(missing_ok=True)
 
 
+@pytest.mark.asyncio
+async def test_reindex_embeds_only_changed_units(indexer, tmp_path, monkeypatch):
+    """Test that reindexing reuses the vectors of units whose content is unchanged."""
+    import os
+
+    path = tmp_path / "funcs.py"
+    path.write_text(
+        "def keep():\n    return 1\n\n\n"
+        "def edit():\n    return 2\n\n\n"
+        "def drop():\n    return 3\n"
+    )
+    stats1 = await indexer.index_file(str(path), "test_project")
+    assert stats1.units_indexed == 3
+    assert stats1.units_unchanged == 0
+
+    embedded = []
+    embed_batch = indexer.embedding_service.embed_batch
+
+    async def record(texts):
+        embedded.extend(texts)
+        return await embed_batch(texts)
+
+    monkeypatch.setattr(indexer.embedding_service, "embed_batch", record)
+
+    path.write_text(
+        "def added():\n    return 0\n\n\n"
+        "def keep():\n    return 1\n\n\n"
+        "def edit():\n    return 20\n"
+    )
+    mtime = path.stat().st_mtime + 10
+    os.utime(path, (mtime, mtime))
+    stats2 = await indexer.index_file(str(path), "test_project")
+
+    assert stats2.units_indexed == 3
+    assert stats2.units_unchanged == 1
+    assert [text.split("(")[0] for text in embedded] == ["def added", "def edit"]
+
+    results = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    units = {r.payload["name"]: r.payload for r in results}
+    assert set(units) == {"added", "keep", "edit"}
+    # The moved unit keeps its vector but its lines are updated
+    assert (units["keep"]["start_line"], units["keep"]["end_line"]) == (5, 6)
+    assert units["edit"]["code"] == "def edit():\n    return 20"
+    assert all(len(u["content_hash"]) == 64 for u in units.values())
+
+
+@pytest.mark.asyncio
+async def test_overloads_keep_separate_points(indexer, tmp_path):
+    """Test that units sharing a qualified name don't overwrite each other."""
+    import os
+
+    path = tmp_path / "Calc.java"
+    path.write_text(
+        "class Calc {\n"
+        "    int add(int a, int b) { return a + b; }\n"
+        "    double add(double a, double b) { return a + b; }\n"
+        "}\n"
+    )
+    stats1 = await indexer.index_file(str(path), "test_project")
+    count = await indexer._count_file_units(str(path), "test_project")
+    assert count == stats1.units_indexed == 3
+
+    # A change outside every unit leaves each overload matching its own point
+    path.write_text(path.read_text() + "// end\n")
+    mtime = path.stat().st_mtime + 10
+    os.utime(path, (mtime, mtime))
+    stats2 = await indexer.index_file(str(path), "test_project")
+    assert stats2.units_unchanged == 3
+
+
+@pytest.mark.asyncio
+async def test_reindex_rewrites_payload_of_unchanged_units(
+    indexer, tmp_path, monkeypatch
+):
+    """Test that unchanged units get payload fields outside the content hash."""
+    import os
+
+    from calm.config import settings
+
+    path = tmp_path / "funcs.py"
+    path.write_text("def keep():\n    a = 1\n    b = 2\n    return a + b\n")
+    await indexer.index_file(str(path), "test_project")
+    before = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    assert before[0].payload["code_truncated"] is False
+
+    monkeypatch.setattr(settings.indexer, "max_payload_code_chars", 20)
+    path.write_text(path.read_text() + "\n\ndef other():\n    pass\n")
+    mtime = path.stat().st_mtime + 10
+    os.utime(path, (mtime, mtime))
+    stats = await indexer.index_file(str(path), "test_project")
+
+    assert stats.units_unchanged == 1
+    after = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    keep = next(r.payload for r in after if r.payload["name"] == "keep")
+    assert keep["code"] == "def keep():"
+    assert keep["code_truncated"] is True
+    assert keep["indexed_at"] >= before[0].payload["indexed_at"]
+
+
+@pytest.mark.asyncio
+async def test_rust_payload_visibility_and_attributes(indexer):
+    """Test that Rust visibility and attributes are stored and filterable."""
+    path = str(FIXTURES_DIR / "sample.rs")
+    await indexer.index_file(path, "test_project")
+
+    derived = await indexer.vector_store.scroll(
+        collection="code_units",
+        filters={"project": "test_project", "attribute_names": "derive"},
+    )
+    assert [r.payload["qualified_name"] for r in derived] == ["sample::Point"]
+    assert derived[0].payload["attributes"] == ["derive(Debug, Clone)"]
+    assert derived[0].payload["has_docstring"] is True
+
+    crate_visible = await indexer.vector_store.scroll(
+        collection="code_units",
+        filters={"project": "test_project", "visibility": "pub(crate)"},
+    )
+    assert [r.payload["name"] for r in crate_visible] == ["origin"]
+
+
+@pytest.mark.asyncio
+async def test_payload_stores_bounded_code_and_docstring(indexer, tmp_path, monkeypatch):
+    """Test that unit source and docstring are stored for keyword search."""
+    from calm.config import settings
+
+    source = tmp_path / "tokens.py"
+    body = "".join(f"    step_{i} = {i}\n" for i in range(50))
+    source.write_text(
+        'def tokenize(text):\n    """Split text into tokens."""\n' + body
+    )
+    monkeypatch.setattr(settings.indexer, "max_payload_code_chars", 200)
+
+    await indexer.index_file(str(source), "test_project")
+
+    results = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    payload = next(r.payload for r in results if r.payload["name"] == "tokenize")
+    assert payload["docstring"] == "Split text into tokens."
+    assert payload["code"].startswith("def tokenize(text):")
+    assert payload["code_truncated"] is True
+    assert len(payload["code"]) <= 200
+    assert payload["code"].endswith("= 8")  # Cut on a line boundary
+
+
+@pytest.mark.asyncio
+async def test_long_unit_is_chunked(indexer, tmp_path, monkeypatch):
+    """Test that units longer than the model input are embedded in chunks."""
+    from calm.config import settings
+
+    indexer.embedding_service = MockEmbeddingService(max_tokens=50)
+    monkeypatch.setattr(settings.indexer, "chunk_overlap_tokens", 8)
+    embedded: list[str] = []
+    embed_batch = indexer.embedding_service.embed_batch
+
+    async def record(texts):
+        embedded.extend(texts)
+        return await embed_batch(texts)
+
+    monkeypatch.setattr(indexer.embedding_service, "embed_batch", record)
+    source = tmp_path / "long.py"
+    body = "".join(f"    step_{i} = {i}\n" for i in range(50))
+    source.write_text("def long_function():\n" + body)
+
+    stats = await indexer.index_file(str(source), "test_project")
+
+    assert all(len(text) <= 200 for text in embedded)  # 50 tokens * 4 chars
+    points = await indexer.vector_store.scroll(
+        collection="code_units", filters={"name": "long_function"}
+    )
+    chunks = sorted(points, key=lambda p: p.payload["chunk_index"])
+    assert len(chunks) > 1
+    assert len(embedded) == len(chunks)
+    parent = chunks[0]
+    assert {p.payload["parent_id"] for p in chunks} == {parent.id}
+    assert all(p.payload["chunk_count"] == len(chunks) for p in chunks)
+    assert parent.payload["code"].startswith("def long_function():")
+    assert chunks[1].payload["code"].startswith("    step_")
+    assert chunks[-1].payload["code"].endswith("step_49 = 49")
+
+    # Chunks cover the unit's lines in order, each overlapping the previous
+    assert parent.payload["chunk_start_line"] == 1
+    assert chunks[-1].payload["chunk_end_line"] == 51
+    for before, after in zip(chunks, chunks[1:]):
+        assert after.payload["chunk_start_line"] <= before.payload["chunk_end_line"]
+        assert after.payload["chunk_start_line"] > before.payload["chunk_start_line"]
+
+    # Units are counted once, and removed with all their chunks
+    assert stats.units_indexed == 1
+    assert await indexer.remove_file(str(source), "test_project") == 1
+    assert await indexer.vector_store.count("code_units") == 0
+
+
+@pytest.mark.asyncio
+async def test_index_cargo_workspace_tags_crates(indexer, tmp_path):
+    """Test that units in a Cargo workspace are tagged with crate and version."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[workspace]\nmembers = ["net", "store"]\nexclude = ["legacy"]\n'
+    )
+    for name, version in (("net", "0.3.1"), ("store", "2.0.0"), ("legacy", "0.1.0")):
+        crate_dir = tmp_path / name
+        (crate_dir / "src").mkdir(parents=True)
+        (crate_dir / "Cargo.toml").write_text(
+            f'[package]\nname = "{name}"\nversion = "{version}"\n'
+        )
+        (crate_dir / "src" / "lib.rs").write_text("pub fn retry() {}\n")
+
+    stats = await indexer.index_directory(str(tmp_path), "test_project")
+    assert stats.crates == ["net", "store"]
+    assert stats.workspace is not None
+    assert sorted(c.name for c in stats.workspace.members) == ["net", "store"]
+
+    results = await indexer.vector_store.scroll(
+        collection="code_units",
+        filters={"project": "test_project", "crate": "net"},
+    )
+    units = {r.payload["qualified_name"]: r.payload for r in results}
+    assert set(units) == {"net::retry", "Cargo.package"}
+    assert units["net::retry"]["crate_version"] == "0.3.1"
+    assert units["Cargo.package"]["language"] == "toml"
+
+    # Excluded crates aren't tagged through the nearest Cargo.toml either
+    legacy = await indexer.vector_store.scroll(
+        collection="code_units",
+        filters={"project": "test_project", "file_path": str(tmp_path / "legacy" / "src" / "lib.rs")},
+    )
+    assert [r.payload["crate"] for r in legacy] == [None]
+
+
+@pytest.mark.asyncio
+async def test_index_directory_builds_call_graph(indexer, tmp_path):
+    """Test that call sites are resolved to indexed units across files."""
+    (tmp_path / "util.py").write_text("def normalize(x):\n    return x.strip()\n")
+    (tmp_path / "app.py").write_text(
+        "from util import normalize\n\n\n"
+        "class Service:\n"
+        "    def run(self, x):\n"
+        "        return self.clean(x)\n\n"
+        "    def clean(self, x):\n"
+        "        print(x)\n"
+        "        return normalize(x)\n"
+    )
+
+    await indexer.index_directory(str(tmp_path), "test_project")
+
+    store = indexer.metadata_store
+    callees = await store.get_callees("app.Service.run", "test_project")
+    assert [e.callee_qualified_name for e in callees] == ["app.Service.clean"]
+
+    # Cross-file call resolves; builtins like print are dropped
+    callees = await store.get_callees("app.Service.clean", "test_project")
+    assert [e.callee_qualified_name for e in callees] == ["util.normalize"]
+    assert callees[0].callee_file == str(tmp_path / "util.py")
+
+    await indexer.remove_file(str(tmp_path / "app.py"), "test_project")
+    assert await store.get_callers("util.normalize", "test_project") == []
+
+
+@pytest.mark.asyncio
+async def test_index_file_resolves_calls(indexer, tmp_path):
+    """Test that indexing single files keeps the call graph current."""
+    util = tmp_path / "util.py"
+    app = tmp_path / "app.py"
+    util.write_text("def normalize(x):\n    return x.strip()\n")
+    app.write_text("from util import normalize\n\n\ndef run(x):\n    return normalize(x)\n")
+
+    await indexer.index_file(str(util), "test_project")
+    await indexer.index_file(str(app), "test_project")
+
+    callees = await indexer.metadata_store.get_callees("app.run", "test_project")
+    assert [e.callee_qualified_name for e in callees] == ["util.normalize"]
+
+
+@pytest.mark.asyncio
+async def test_index_directory_purges_deleted_files(indexer, tmp_path):
+    """Test that files gone from disk are removed on the next index run."""
+    (tmp_path / "pkg").mkdir()
+    keep = tmp_path / "keep.py"
+    gone = tmp_path / "pkg" / "gone.py"
+    keep.write_text("def keep():\n    pass\n")
+    gone.write_text("def gone():\n    pass\n")
+    await indexer.index_directory(str(tmp_path), "test_project")
+
+    gone.rename(tmp_path / "moved.txt")
+
+    # A non-recursive run does not see pkg/, so it must not purge it
+    stats = await indexer.index_directory(
+        str(tmp_path), "test_project", recursive=False
+    )
+    assert stats.files_removed == 0
+
+    stats = await indexer.index_directory(str(tmp_path), "test_project")
+    assert stats.files_removed == 1
+
+    files = await indexer.metadata_store.list_indexed_files("test_project")
+    assert [f.file_path for f in files] == [str(keep)]
+    results = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    assert {r.payload["name"] for r in results} == {"keep"}
+
+
+@pytest.mark.asyncio
+async def test_index_directory_respects_ignore_files(indexer, tmp_path):
+    """Test that ignore files are honored and exclusions persist per project."""
+    (tmp_path / ".gitignore").write_text("generated/\n*_pb2.py\n")
+    (tmp_path / ".calmignore").write_text("fixtures/\n")
+    for rel in ("app.py", "api_pb2.py", "generated/models.py", "fixtures/data.py"):
+        (tmp_path / rel).parent.mkdir(exist_ok=True)
+        (tmp_path / rel).write_text("def f():\n    pass\n")
+
+    found = indexer._find_files(str(tmp_path), True, None)
+    assert [Path(f).name for f in found] == ["app.py"]
+
+    from calm.indexers.ignore import ExclusionSettings
+
+    await indexer.index_directory(
+        str(tmp_path),
+        "test_project",
+        exclusions=ExclusionSettings(respect_gitignore=False, patterns=["app.py"]),
+    )
+    files = await indexer.metadata_store.list_indexed_files("test_project")
+    assert sorted(Path(f.file_path).name for f in files) == ["api_pb2.py", "models.py"]
+
+    # Saved settings apply to later runs that don't pass exclusions
+    saved = await indexer.get_exclusions("test_project")
+    assert saved.respect_gitignore is False
+    assert saved.patterns == ["app.py"]
+
+
+@pytest.mark.asyncio
+async def test_index_directory_batches_across_files(indexer, tmp_path, monkeypatch):
+    """Test that units from several files share embedding batches."""
+    from calm.config import settings
+
+    for i in range(6):
+        (tmp_path / f"mod{i}.py").write_text(f"def f{i}():\n    pass\n")
+    monkeypatch.setattr(settings.indexer, "embedding_batch_size", 4)
+
+    batch_sizes = []
+    embed_batch = indexer.embedding_service.embed_batch
+
+    async def spy(texts):
+        batch_sizes.append(len(texts))
+        return await embed_batch(texts)
+
+    monkeypatch.setattr(indexer.embedding_service, "embed_batch", spy)
+    reports = []
+
+    stats = await indexer.index_directory(
+        str(tmp_path),
+        "test_project",
+        progress=lambda p: reports.append((p.files_done, p.files_total)),
+    )
+
+    assert stats.files_indexed == 6
+    assert stats.units_indexed == 6
+    assert batch_sizes == [4, 2]
+    assert reports == [(4, 6), (6, 6)]
+
+
+@pytest.mark.asyncio
+async def test_index_directory_parses_in_process_pool(indexer, tmp_path, monkeypatch):
+    """Test the process pool parse path yields the same units."""
+    from calm.config import settings
+    from calm.indexers import indexer as indexer_module
+
+    (tmp_path / "a.py").write_text("def a():\n    pass\n")
+    (tmp_path / "b.py").write_text("def b():\n    pass\n")
+    (tmp_path / "c.py").write_bytes(b"\xff\xfe invalid")
+    monkeypatch.setattr(indexer_module, "_PARALLEL_PARSE_MIN_FILES", 1)
+    monkeypatch.setattr(settings.indexer, "parse_workers", 2)
+    assert indexer._parse_workers(3) == 2
+
+    stats = await indexer.index_directory(str(tmp_path), "test_project")
+
+    assert stats.files_indexed == 2
+    assert [e.error_type for e in stats.errors] == ["encoding_error"]
+    assert stats.errors[0].file_path == str(tmp_path / "c.py")
+    results = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "test_project"}
+    )
+    assert {r.payload["name"] for r in results} == {"a", "b"}
+
+
 @pytest.mark.asyncio
 async def test_empty_file_handling(indexer):
     """Test handling of empty files."""
@@ -230,14 +640,10 @@ async def test_get_indexing_stats(indexer):
 
 @pytest.mark.asyncio
 async def test_dimension_migration():
-    """Test that changing embedding dimension requires reindexing.
+    """Test that changing embedding dimension re-embeds the collection.
 
-    This verifies the spec requirement that dimension changes are detected
-    and handled by recreating the collection.
-
-    Note: This test documents the expected behavior - dimension changes
-    require manual intervention (deleting the collection) since we can't
-    automatically migrate embeddings between different models.
+    This verifies the spec requirement that dimension changes are detected.
+    Stored units are re-embedded from their payloads rather than dropped.
     """
     from calm.embedding.minilm import MiniLMEmbedding
 
@@ -271,9 +677,19 @@ async def test_dimension_migration():
         assert collection_info is not None
         assert collection_info.dimension == 384
 
-        # Now we would switch models (e.g., to Nomic with 768 dims)
-        # But that would require deleting the collection first
-        # This test verifies the collection dimension is tracked correctly
+        # Switch to a 768-dimension model: existing units are re-embedded
+        indexer_768 = CodeIndexer(
+            parser, MockEmbeddingService(dimension=768), vector_store, metadata_store
+        )
+        await indexer_768._ensure_collection()
+
+        collection_info = await vector_store.get_collection_info("code_units")
+        assert collection_info is not None
+        assert collection_info.dimension == 768
+        assert collection_info.vector_count == stats1.units_indexed
+        recorded = await metadata_store.get_collection_embedding("code_units")
+        assert recorded is not None
+        assert recorded.dimension == 768
 
     finally:
         await metadata_store.close()
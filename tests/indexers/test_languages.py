@@ -0,0 +1,166 @@
+"""Tests for the language registry."""
+
+from collections.abc import Iterator
+from dataclasses import replace
+from types import SimpleNamespace
+from typing import Any
+
+import pytest
+
+import calm.indexers.languages as languages_module
+from calm.indexers import TreeSitterParser, UnitType
+from calm.indexers.languages import (
+    BUILTIN_LANGUAGES,
+    LanguageSpec,
+    extension_map,
+    get_languages,
+    split_patterns,
+)
+from calm.indexers.tree_sitter import BRANCH_TYPES
+from calm.indexers.utils import EXTENSION_MAP
+
+# A plugin language reusing the Python grammar with a tags-style query
+TOY = LanguageSpec(
+    name="toy",
+    extensions=(".toy", ".py"),
+    grammar="tree_sitter_python:language",
+    branch_types=frozenset({"if_statement"}),
+    query="""
+    (
+      (comment)* @doc
+      .
+      (function_definition name: (identifier) @name) @definition.function
+    )
+    (class_definition name: (identifier) @name) @definition.class
+    """,
+)
+
+
+@pytest.fixture
+def plugin(monkeypatch: pytest.MonkeyPatch) -> Iterator[None]:
+    """Install the "toy" language, a broken plugin and an invalid one."""
+
+    def broken() -> Any:
+        raise ImportError("No module named 'tree_sitter_elixir'")
+
+    installed = [
+        SimpleNamespace(name="toy", load=lambda: TOY),
+        SimpleNamespace(name="elixir", load=broken),
+        SimpleNamespace(name="bogus", load=lambda: "not a spec"),
+    ]
+    monkeypatch.setattr(languages_module, "entry_points", lambda group: installed)
+    get_languages.cache_clear()
+    yield
+    get_languages.cache_clear()
+
+
+class TestRegistry:
+    """Tests for language registration."""
+
+    def test_builtin_languages(self) -> None:
+        names = {spec.name for spec in BUILTIN_LANGUAGES}
+        assert {"python", "rust", "c", "cpp", "go", "lua"} <= names
+        assert set(BRANCH_TYPES) >= names
+
+    def test_extension_map_is_derived(self) -> None:
+        for spec in BUILTIN_LANGUAGES:
+            for ext in spec.extensions:
+                assert EXTENSION_MAP[ext] == spec.name
+
+    def test_c_has_its_own_grammar(self) -> None:
+        specs = get_languages()
+        assert specs["c"].grammar == "tree_sitter_c:language"
+        assert specs["c"].package != specs["cpp"].package
+
+    def test_plugins_are_registered(self, plugin: None) -> None:
+        languages = get_languages()
+        assert languages["toy"] is TOY
+        assert "elixir" not in languages
+        assert "bogus" not in languages
+
+    def test_builtins_keep_their_extensions(self, plugin: None) -> None:
+        mapping = extension_map()
+        assert mapping[".toy"] == "toy"
+        assert mapping[".py"] == "python"
+
+    def test_builtin_queries_are_loaded(self) -> None:
+        for spec in BUILTIN_LANGUAGES:
+            assert spec.query and "@name" in spec.query
+
+    def test_split_patterns(self) -> None:
+        query = """
+        ; A comment with (parens)
+        (function_definition name: (identifier) @name) @definition.function
+
+        ((identifier) @name
+         (#match? @name "^[(]"))
+        [(class_definition) (decorated_definition)] @definition.class
+        """
+
+        assert split_patterns(query) == [
+            "(function_definition name: (identifier) @name) @definition.function",
+            '((identifier) @name\n         (#match? @name "^[(]"))',
+            "[(class_definition) (decorated_definition)] @definition.class",
+        ]
+
+
+class TestQueryExtraction:
+    """Tests for extracting plugin languages with their query."""
+
+    @pytest.fixture
+    def parser(
+        self, plugin: None, monkeypatch: pytest.MonkeyPatch
+    ) -> TreeSitterParser:
+        pytest.importorskip("tree_sitter_python")
+        monkeypatch.setitem(EXTENSION_MAP, ".toy", "toy")
+        return TreeSitterParser()
+
+    async def test_extracts_definitions(
+        self, parser: TreeSitterParser, tmp_path: Any
+    ) -> None:
+        path = tmp_path / "shapes.toy"
+        path.write_text(
+            "# Compute an area\n"
+            "def area(r):\n"
+            "    if r < 0:\n"
+            "        return 0\n"
+            "    return 3 * r * r\n"
+            "\n"
+            "class Circle:\n"
+            "    pass\n"
+        )
+
+        assert "toy" in parser.supported_languages()
+        units = {u.qualified_name: u for u in await parser.parse_file(str(path))}
+
+        assert set(units) == {"shapes.area", "shapes.Circle"}
+        area = units["shapes.area"]
+        assert area.unit_type == UnitType.FUNCTION
+        assert area.language == "toy"
+        assert area.docstring == "Compute an area"
+        assert area.complexity == 2
+        assert area.start_line == 2
+        assert units["shapes.Circle"].unit_type == UnitType.CLASS
+        assert units["shapes.Circle"].complexity is None
+
+    async def test_invalid_pattern_is_skipped(
+        self, parser: TreeSitterParser, monkeypatch: pytest.MonkeyPatch, tmp_path: Any
+    ) -> None:
+        query = f"(no_such_node) @definition.function\n{TOY.query}"
+        monkeypatch.setitem(parser._specs, "toy", replace(TOY, query=query))
+        path = tmp_path / "shapes.toy"
+        path.write_text("def area(r):\n    return r\n")
+
+        units = await parser.parse_file(str(path))
+        assert [u.qualified_name for u in units] == ["shapes.area"]
+
+    async def test_language_without_query(
+        self, parser: TreeSitterParser, monkeypatch: pytest.MonkeyPatch, tmp_path: Any
+    ) -> None:
+        monkeypatch.setitem(
+            parser._specs, "toy", LanguageSpec("toy", (".toy",), TOY.grammar)
+        )
+        path = tmp_path / "empty.toy"
+        path.write_text("def f():\n    pass\n")
+
+        assert await parser.parse_file(str(path)) == []
@@ -1,5 +1,8 @@
 """Tests for TreeSitterParser."""
 
+import json
+import os
+from dataclasses import replace
 from pathlib import Path
 
 import pytest
@@ -7,6 +10,10 @@ import pytest
 from calm.indexers import TreeSitterParser, UnitType
 
 FIXTURES_DIR = Path(__file__).parent.parent / "fixtures" / "code_samples"
+GOLDEN_DIR = FIXTURES_DIR / "golden"
+
+# Set CALM_UPDATE_GOLDEN=1 to rewrite the golden files from the parser
+UPDATE_GOLDEN = os.environ.get("CALM_UPDATE_GOLDEN") == "1"
 
 
 @pytest.fixture
@@ -30,7 +37,12 @@ def test_language_detection(parser):
     assert parser.detect_language("test.cpp") == "cpp"
     assert parser.detect_language("test.hpp") == "cpp"
     assert parser.detect_language("test.sql") == "sql"
-    assert parser.detect_language("test.lua") is None  # Not supported
+    assert parser.detect_language("test.go") == "go"
+    assert parser.detect_language("test.kt") == "kotlin"
+    assert parser.detect_language("test.kts") == "kotlin"
+    assert parser.detect_language("test.cs") == "csharp"
+    assert parser.detect_language("test.rb") == "ruby"
+    assert parser.detect_language("test.lua") == "lua"
     assert parser.detect_language("test.unknown") is None
 
 
@@ -48,6 +60,27 @@ def test_supported_languages(parser):
     assert "sql" in languages
 
 
+@pytest.mark.asyncio
+async def test_missing_grammar_is_skipped(monkeypatch, tmp_path):
+    """Test that a missing optional grammar skips its files instead of failing."""
+    parser = TreeSitterParser()
+    monkeypatch.setitem(
+        parser._specs,
+        "go",
+        replace(parser._specs["go"], grammar="tree_sitter_not_installed:language"),
+    )
+    assert "go" not in parser.supported_languages()
+    assert "python" in parser.supported_languages()
+
+    go_file = tmp_path / "main.go"
+    go_file.write_text("package main\n\nfunc main() {}\n")
+    assert await parser.parse_file(str(go_file)) == []
+
+    py_file = tmp_path / "main.py"
+    py_file.write_text("def main():\n    pass\n")
+    assert [u.name for u in await parser.parse_file(str(py_file))] == ["main"]
+
+
 @pytest.mark.asyncio
 async def test_parse_python(parser):
     """Test parsing Python files."""
@@ -158,6 +191,59 @@ async def test_parse_rust(parser):
     assert len(functions) >= 1
 
 
+@pytest.mark.asyncio
+async def test_parse_rust_impl_methods(parser):
+    """Test that methods inside inherent and trait impl blocks are extracted."""
+    path = str(FIXTURES_DIR / "sample.rs")
+    units = await parser.parse_file(path)
+
+    methods = {u.qualified_name: u for u in units if u.unit_type == UnitType.METHOD}
+    assert "sample::Point::new" in methods
+    assert "sample::Point::translate" in methods
+    assert "sample::<Point as Display>::fmt" in methods
+
+    translate = methods["sample::Point::translate"]
+    assert translate.name == "translate"
+    assert translate.signature.startswith("pub fn translate")
+    assert translate.complexity == 1
+
+    # Free functions keep FUNCTION type and the short path
+    distance = next(u for u in units if u.name == "distance")
+    assert distance.unit_type == UnitType.FUNCTION
+    assert distance.qualified_name == "sample::distance"
+
+
+@pytest.mark.asyncio
+async def test_parse_rust_docs_attributes_visibility(parser):
+    """Test Rust doc comments, attributes, and visibility extraction."""
+    path = str(FIXTURES_DIR / "sample.rs")
+    units = await parser.parse_file(path)
+    by_name = {u.qualified_name: u for u in units}
+
+    # Inner doc comments become the module docstring
+    module = by_name["sample"]
+    assert module.unit_type == UnitType.MODULE
+    assert module.docstring == "Sample Rust module for testing code parsing"
+
+    point = by_name["sample::Point"]
+    assert point.docstring == "A point in 2D space"
+    assert point.attributes == ["derive(Debug, Clone)"]
+    assert point.visibility == "pub"
+
+    color = by_name["sample::Color"]
+    assert color.docstring is None
+    assert color.attributes == []
+
+    assert by_name["sample::distance"].docstring == (
+        "Calculate distance between two points"
+    )
+    assert by_name["sample::origin"].visibility == "pub(crate)"
+    assert by_name["sample::Point::translate"].docstring == "Move point by offset"
+
+    # Trait impl methods inherit the trait's visibility
+    assert by_name["sample::<Point as Display>::fmt"].visibility is None
+
+
 @pytest.mark.asyncio
 async def test_parse_java(parser):
     """Test parsing Java files."""
@@ -204,6 +290,23 @@ async def test_parse_cpp(parser):
     assert len(classes) >= 1
 
 
+@pytest.mark.asyncio
+async def test_parse_headers(parser, tmp_path):
+    """Test that .h headers are parsed as C++ when they contain C++."""
+    c_header = tmp_path / "point.h"
+    c_header.write_text("struct point { int x; };\nint add(int a, int b);\n")
+    units = await parser.parse_file(str(c_header))
+    assert units and all(u.language == "c" for u in units)
+
+    cpp_header = tmp_path / "shape.h"
+    cpp_header.write_text(
+        "namespace geo {\nclass Shape {\npublic:\n    double area() const;\n};\n}\n"
+    )
+    units = await parser.parse_file(str(cpp_header))
+    assert "Shape" in {u.name for u in units}
+    assert all(u.language == "cpp" for u in units)
+
+
 @pytest.mark.asyncio
 async def test_parse_swift(parser):
     """Test parsing Swift files."""
@@ -240,3 +343,278 @@ async def test_parse_malformed_file(parser):
     # Should still extract valid units despite syntax errors
     # Tree-sitter is error-tolerant
     assert isinstance(units, list)
+
+
+@pytest.mark.asyncio
+async def test_parse_rust_generic_impl_scopes(parser, tmp_path):
+    """Test that generic arguments are left out of impl scope names."""
+    path = tmp_path / "stack.rs"
+    path.write_text(
+        "pub struct Stack<T> { items: Vec<T> }\n\n"
+        "impl<T> Stack<T> {\n    pub fn new() -> Self { todo!() }\n}\n\n"
+        "impl<T: Debug> From<Vec<T>> for Stack<T> {\n"
+        "    fn from(items: Vec<T>) -> Self { Stack { items } }\n}\n"
+    )
+    units = await parser.parse_file(str(path))
+
+    methods = {u.qualified_name for u in units if u.unit_type == UnitType.METHOD}
+    assert methods == {"stack::Stack::new", "stack::<Stack as From>::from"}
+
+
+@pytest.mark.asyncio
+async def test_parse_rust_crate_module_paths(parser, tmp_path):
+    """Test that Rust qualified names follow the crate module path."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "my-crate"\nversion = "0.1.0"\n'
+    )
+    src = tmp_path / "src"
+    (src / "foo").mkdir(parents=True)
+    (src / "bar").mkdir()
+    (src / "lib.rs").write_text(
+        "pub fn init() {}\n\nmod inner {\n    pub struct Config;\n}\n"
+    )
+    (src / "foo" / "mod.rs").write_text("pub fn run() {}\n")
+    (src / "bar" / "mod.rs").write_text("pub fn run() {}\n")
+    (src / "foo" / "util.rs").write_text(
+        "pub struct Helper;\n\nimpl Helper {\n    fn help(&self) {}\n}\n"
+    )
+
+    names = set()
+    for path in src.rglob("*.rs"):
+        names.update(u.qualified_name for u in await parser.parse_file(str(path)))
+
+    assert names == {
+        "my_crate::init",
+        "my_crate::inner::Config",
+        "my_crate::foo::run",
+        "my_crate::bar::run",
+        "my_crate::foo::util::Helper",
+        "my_crate::foo::util::Helper::help",
+    }
+
+
+@pytest.mark.asyncio
+async def test_parse_call_sites(parser, tmp_path):
+    """Test that call sites are attached to the innermost enclosing function."""
+    py_file = tmp_path / "calls.py"
+    py_file.write_text(
+        "def outer():\n"
+        "    helper()\n"
+        "    def inner():\n"
+        "        return os.path.join('a', 'b')\n"
+        "    return inner()\n"
+        "\n\n"
+        "class Job:\n"
+        "    def run(self):\n"
+        "        self.step()\n"
+        "        self.step()\n"
+    )
+    units = {u.qualified_name: u for u in await parser.parse_file(str(py_file))}
+    # Calls in nested functions also belong to the outer one
+    assert units["calls.outer"].calls == ["helper", "os.path.join", "inner"]
+    assert units["calls.outer.inner"].calls == ["os.path.join"]
+    assert units["calls.Job.run"].calls == ["self.step"]
+
+    rs_file = tmp_path / "calls.rs"
+    rs_file.write_text(
+        "fn main() {\n"
+        "    let p = Point::new(1, 2);\n"
+        "    p.translate(3, 4);\n"
+        "    log(p);\n"
+        "}\n"
+    )
+    units = {u.qualified_name: u for u in await parser.parse_file(str(rs_file))}
+    assert units["calls::main"].calls == ["Point::new", "p.translate", "log"]
+
+    java_file = tmp_path / "Calls.java"
+    java_file.write_text(
+        "class Calls {\n"
+        "    void run() {\n"
+        "        Worker w = new Worker();\n"
+        "        w.start();\n"
+        "        stop();\n"
+        "    }\n"
+        "}\n"
+    )
+    units = {u.name: u for u in await parser.parse_file(str(java_file))}
+    assert units["run"].calls == ["Worker", "w.start", "stop"]
+
+
+@pytest.mark.asyncio
+async def test_parse_go(parser):
+    """Test parsing Go files."""
+    pytest.importorskip("tree_sitter_go")
+    path = str(FIXTURES_DIR / "sample.go")
+    units = {u.qualified_name: u for u in await parser.parse_file(path)}
+
+    assert set(units) == {
+        "sample.Point",
+        "sample.Shape",
+        "sample.ID",
+        "sample.NewPoint",
+        "sample.Point.Translate",
+        "sample.Classify",
+    }
+    assert units["sample.Point"].unit_type == UnitType.CLASS
+    assert units["sample.Point"].docstring == "Point is a point in 2D space."
+    assert units["sample.Shape"].docstring == "Shape has an area."
+
+    translate = units["sample.Point.Translate"]
+    assert translate.unit_type == UnitType.METHOD
+    assert translate.docstring == "Translate moves the point by an offset."
+    assert units["sample.NewPoint"].unit_type == UnitType.FUNCTION
+    assert units["sample.Classify"].docstring is None
+    assert units["sample.Classify"].complexity > 3
+    assert units["sample.Classify"].calls == ["fmt.Println"]
+
+
+@pytest.mark.asyncio
+async def test_parse_kotlin(parser):
+    """Test parsing Kotlin files."""
+    pytest.importorskip("tree_sitter_kotlin")
+    path = str(FIXTURES_DIR / "sample.kt")
+    units = {u.qualified_name: u for u in await parser.parse_file(path)}
+
+    prefix = "com.example.sample"
+    calculator = units[f"{prefix}.Calculator"]
+    assert calculator.unit_type == UnitType.CLASS
+    assert calculator.docstring is not None
+    assert "A simple calculator" in calculator.docstring
+
+    add = units[f"{prefix}.Calculator.add"]
+    assert add.unit_type == UnitType.METHOD
+    assert "Add to the current value" in add.docstring
+    assert units[f"{prefix}.Calculator.sign"].complexity > 1
+    assert units[f"{prefix}.Calculator.zero"].unit_type == UnitType.METHOD
+
+    assert units[f"{prefix}.Operation"].unit_type == UnitType.CLASS
+    assert units[f"{prefix}.Registry.register"].unit_type == UnitType.METHOD
+    assert units[f"{prefix}.add"].unit_type == UnitType.FUNCTION
+
+
+@pytest.mark.asyncio
+async def test_parse_csharp(parser):
+    """Test parsing C# files."""
+    pytest.importorskip("tree_sitter_c_sharp")
+    path = str(FIXTURES_DIR / "sample.cs")
+    units = {u.qualified_name: u for u in await parser.parse_file(path)}
+
+    assert set(units) == {
+        "Sample.Shapes.Circle",
+        "Sample.Shapes.Circle.Circle",
+        "Sample.Shapes.Circle.Area",
+        "Sample.Shapes.Circle.Describe",
+        "Sample.Shapes.IShape",
+        "Sample.Shapes.IShape.Area",
+        "Sample.Shapes.Size",
+    }
+    assert units["Sample.Shapes.Circle"].docstring == "A circle with a radius."
+    assert units["Sample.Shapes.Circle.Area"].docstring == "Compute the area."
+    assert units["Sample.Shapes.Circle.Area"].unit_type == UnitType.METHOD
+    assert units["Sample.Shapes.Circle.Describe"].complexity > 3
+    assert units["Sample.Shapes.Circle.Describe"].calls == ["Console.WriteLine"]
+
+
+@pytest.mark.asyncio
+async def test_parse_ruby(parser):
+    """Test parsing Ruby files."""
+    pytest.importorskip("tree_sitter_ruby")
+    path = str(FIXTURES_DIR / "sample.rb")
+    units = {u.qualified_name: u for u in await parser.parse_file(path)}
+
+    assert set(units) == {
+        "Shapes",
+        "Shapes::Circle",
+        "Shapes::Circle.initialize",
+        "Shapes::Circle.area",
+        "Shapes::Circle.describe",
+        "Shapes::Circle.unit",
+        "sample.greet",
+    }
+    assert units["Shapes::Circle"].name == "Circle"
+    assert units["Shapes::Circle"].docstring == "A circle with a radius"
+    assert units["Shapes::Circle.area"].docstring == "Compute the area"
+    assert units["Shapes::Circle.initialize"].docstring is None
+    assert units["Shapes::Circle.describe"].complexity > 2
+    assert units["sample.greet"].unit_type == UnitType.FUNCTION
+    assert units["sample.greet"].docstring == "Greet someone"
+
+
+@pytest.mark.asyncio
+async def test_parse_lua(parser):
+    """Test parsing Lua files."""
+    pytest.importorskip("tree_sitter_lua")
+    path = str(FIXTURES_DIR / "sample.lua")
+    units = {u.qualified_name: u for u in await parser.parse_file(path)}
+
+    assert set(units) == {
+        "sample.M.add",
+        "sample.helper",
+        "sample.M.process",
+        "sample.M.reset",
+    }
+    assert units["sample.M.add"].name == "add"
+    assert units["sample.M.add"].docstring == "Add two numbers"
+    assert units["sample.M.reset"].unit_type == UnitType.METHOD
+    assert units["sample.helper"].complexity > 1
+    assert units["sample.M.process"].calls == ["ipairs", "helper"]
+
+
+@pytest.mark.asyncio
+async def test_parse_nested_scopes(parser, tmp_path):
+    """Test that definitions at any depth are qualified by their scopes."""
+    ts_file = tmp_path / "nested.ts"
+    ts_file.write_text(
+        "namespace Outer {\n"
+        "    export namespace Inner {\n"
+        "        export class Box {\n"
+        "            open() {\n"
+        "                const peek = () => 1;\n"
+        "                return peek();\n"
+        "            }\n"
+        "        }\n"
+        "    }\n"
+        "}\n"
+    )
+    units = {u.qualified_name: u for u in await parser.parse_file(str(ts_file))}
+    assert set(units) == {
+        "nested.Outer.Inner.Box",
+        "nested.Outer.Inner.Box.open",
+        "nested.Outer.Inner.Box.open.peek",
+    }
+    assert units["nested.Outer.Inner.Box.open"].unit_type == UnitType.METHOD
+    assert units["nested.Outer.Inner.Box.open.peek"].unit_type == UnitType.FUNCTION
+    assert units["nested.Outer.Inner.Box.open"].calls == ["peek"]
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize(
+    "sample",
+    sorted(p.name.removesuffix(".json") for p in GOLDEN_DIR.glob("*.json")),
+)
+async def test_golden_units(parser, sample):
+    """Test extracted units against each sample's golden file."""
+    path = FIXTURES_DIR / sample
+    language = parser.detect_language(str(path))
+    pytest.importorskip(parser._specs[language].package)
+
+    units = sorted(
+        await parser.parse_file(str(path)),
+        key=lambda u: (u.start_line, u.qualified_name),
+    )
+    actual = [
+        {
+            "name": u.name,
+            "qualified_name": u.qualified_name,
+            "unit_type": u.unit_type.value,
+            "start_line": u.start_line,
+            "end_line": u.end_line,
+            "docstring": u.docstring,
+        }
+        for u in units
+    ]
+
+    golden = GOLDEN_DIR / f"{sample}.json"
+    if UPDATE_GOLDEN:
+        golden.write_text(json.dumps(actual, indent=2) + "\n")
+    assert actual == json.loads(golden.read_text())
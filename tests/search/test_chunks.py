@@ -0,0 +1,123 @@
+"""Tests for collapsing chunk hits into one result per code unit."""
+
+from typing import Any
+
+import pytest
+
+from calm.embedding.mock import MockEmbeddingService
+from calm.search.chunks import collapse_chunks
+from calm.search.searcher import _keyword_search, _semantic_search
+from calm.storage.base import SearchResult
+from calm.storage.memory import MemoryStore
+
+UNIT = {
+    "project": "p",
+    "qualified_name": "lib::long",
+    "code": "fn long() {\n    a();\n    b();\n    c();\n}",
+    "start_line": 10,
+    "end_line": 14,
+}
+
+CHUNKS = [
+    {"code": UNIT["code"], "chunk_start_line": 10, "chunk_end_line": 12},
+    {"code": "    b();\n    c();", "chunk_start_line": 12, "chunk_end_line": 13},
+    {"code": "    c();\n}", "chunk_start_line": 13, "chunk_end_line": 14},
+]
+
+
+def _chunk_payload(index: int) -> dict[str, Any]:
+    return {
+        **UNIT,
+        **CHUNKS[index],
+        "parent_id": "u",
+        "chunk_index": index,
+        "chunk_count": len(CHUNKS),
+    }
+
+
+@pytest.fixture
+async def store() -> MemoryStore:
+    """A code unit stored as three chunks, plus an unchunked unit."""
+    embedder = MockEmbeddingService(dimension=8)
+    store = MemoryStore()
+    await store.create_collection("code_units", dimension=8)
+    for index, chunk in enumerate(CHUNKS):
+        await store.upsert(
+            "code_units",
+            "u" if index == 0 else f"u#{index}",
+            await embedder.embed(chunk["code"]),
+            _chunk_payload(index),
+        )
+    await store.upsert(
+        "code_units",
+        "legacy",
+        await embedder.embed("fn short() {}"),
+        {"project": "p", "qualified_name": "lib::short", "code": "fn short() {}"},
+    )
+    return store
+
+
+class TestCollapseChunks:
+    """Tests for collapse_chunks."""
+
+    async def test_keeps_best_chunk_per_unit(self, store: MemoryStore) -> None:
+        hits = [
+            SearchResult(id="u#2", score=0.9, payload=_chunk_payload(2)),
+            SearchResult(id="legacy", score=0.8, payload={"code": "fn short() {}"}),
+            SearchResult(id="u", score=0.7, payload=_chunk_payload(0)),
+        ]
+
+        results = await collapse_chunks(store, "code_units", hits, limit=10)
+
+        assert [(r.id, r.score) for r in results] == [("u", 0.9), ("legacy", 0.8)]
+        unit = results[0].payload
+        assert unit["code"] == UNIT["code"]  # The unit's payload, not the chunk's
+        assert (unit["match_start_line"], unit["match_end_line"]) == (13, 14)
+        assert "parent_id" not in unit
+        assert "chunk_index" not in unit
+
+    async def test_limit_counts_units(self, store: MemoryStore) -> None:
+        hits = [
+            SearchResult(id="u", score=0.9, payload=_chunk_payload(0)),
+            SearchResult(id="u#1", score=0.8, payload=_chunk_payload(1)),
+            SearchResult(id="legacy", score=0.7, payload={"code": "fn short() {}"}),
+        ]
+
+        results = await collapse_chunks(store, "code_units", hits, limit=2)
+
+        assert [r.id for r in results] == ["u", "legacy"]
+        assert results[0].payload["match_start_line"] == 10
+
+    async def test_other_collections_unchanged(self, store: MemoryStore) -> None:
+        hits = [
+            SearchResult(id="m1", score=0.9, payload={"parent_id": "x"}),
+            SearchResult(id="m2", score=0.8, payload={"parent_id": "x"}),
+        ]
+
+        results = await collapse_chunks(store, "memories", hits, limit=10)
+
+        assert results == hits
+
+
+class TestChunkedSearch:
+    """Tests that searches return one result per unit."""
+
+    async def test_semantic_search(self, store: MemoryStore) -> None:
+        embedder = MockEmbeddingService(dimension=8)
+
+        results = await _semantic_search(
+            embedder, store, "code_units", "    b();\n    c();", 10, None
+        )
+
+        assert sorted(r.id for r in results) == ["legacy", "u"]
+        assert results[0].id == "u"
+        assert results[0].score == pytest.approx(1.0)
+        assert results[0].payload["match_start_line"] == 12
+
+    async def test_keyword_search(self, store: MemoryStore) -> None:
+        results = await _keyword_search(
+            store, "code_units", "c()", 10, None, ["code"]
+        )
+
+        assert [r.id for r in results] == ["u"]
+        assert results[0].payload["code"] == UNIT["code"]
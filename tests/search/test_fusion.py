@@ -0,0 +1,101 @@
+"""Tests for hybrid search rank fusion."""
+
+import pytest
+
+from calm.search.fusion import FUSION_METHODS, fuse_results
+from calm.storage.base import SearchResult
+
+
+def _results(*scored: tuple[str, float]) -> list[SearchResult]:
+    return [
+        SearchResult(id=id, score=score, payload={"id": id}) for id, score in scored
+    ]
+
+
+class TestReciprocalRankFusion:
+    """Tests for the rrf method."""
+
+    def test_results_in_both_lists_rank_first(self) -> None:
+        semantic = _results(("a", 0.9), ("b", 0.8), ("c", 0.7))
+        keyword = _results(("c", 1.0), ("d", 0.5))
+
+        fused = fuse_results(semantic, keyword, limit=10, method="rrf", rrf_k=60)
+
+        assert [r.id for r in fused] == ["c", "a", "b", "d"]
+        assert fused[0].score == pytest.approx(0.5 / 63 + 0.5 / 61)
+
+    def test_raw_score_scale_is_ignored(self) -> None:
+        """BM25-sized keyword scores don't swamp cosine similarities."""
+        semantic = _results(("a", 0.9), ("b", 0.2))
+        keyword = _results(("b", 25.0))
+
+        fused = fuse_results(semantic, keyword, limit=10, method="rrf")
+        assert fused[0].id == "b"
+
+        fused = fuse_results(
+            semantic, keyword, limit=10, method="rrf", keyword_weight=0.0
+        )
+        assert fused[0].id == "a"
+
+    def test_ranking_breakdown(self) -> None:
+        semantic = _results(("a", 0.9), ("b", 0.8))
+        keyword = _results(("b", 0.4))
+
+        fused = {r.id: r for r in fuse_results(semantic, keyword, limit=10)}
+
+        assert fused["b"].ranking == {
+            "method": "rrf",
+            "semantic_rank": 2,
+            "semantic_score": 0.8,
+            "keyword_rank": 1,
+            "keyword_score": 0.4,
+            "fused_score": fused["b"].score,
+        }
+        assert fused["a"].ranking is not None
+        assert fused["a"].ranking["keyword_rank"] is None
+        assert fused["a"].ranking["keyword_score"] is None
+
+
+class TestWeightedFusion:
+    """Tests for the weighted method."""
+
+    def test_scores_are_min_max_normalized(self) -> None:
+        semantic = _results(("a", 0.9), ("b", 0.7), ("c", 0.5))
+        keyword = _results(("c", 12.0), ("a", 2.0))
+
+        fused = {
+            r.id: r.score
+            for r in fuse_results(
+                semantic,
+                keyword,
+                limit=10,
+                method="weighted",
+                semantic_weight=0.6,
+                keyword_weight=0.4,
+            )
+        }
+
+        assert fused["a"] == pytest.approx(0.6 * 1.0 + 0.4 * 0.0)
+        assert fused["b"] == pytest.approx(0.6 * 0.5)
+        assert fused["c"] == pytest.approx(0.6 * 0.0 + 0.4 * 1.0)
+
+    def test_single_result_list_normalizes_to_one(self) -> None:
+        fused = fuse_results(
+            _results(("a", 0.3)), _results(("a", 0.1)), limit=10, method="weighted"
+        )
+        assert fused[0].score == pytest.approx(1.0)
+
+
+class TestFuseResults:
+    """Tests shared by all methods."""
+
+    @pytest.mark.parametrize("method", FUSION_METHODS)
+    def test_limit_and_empty_lists(self, method: str) -> None:
+        semantic = _results(("a", 0.9), ("b", 0.8), ("c", 0.7))
+
+        assert [r.id for r in fuse_results(semantic, [], 2, method)] == ["a", "b"]
+        assert fuse_results([], [], 5, method) == []
+
+    def test_invalid_method(self) -> None:
+        with pytest.raises(ValueError, match="Invalid fusion method"):
+            fuse_results([], [], limit=5, method="max")
@@ -14,6 +14,8 @@ from calm.search import (
     ValueResult,
 )
 from calm.search.searcher import (
+    _KEYWORD_SCROLL_LIMIT,
+    KEYWORD_TEXT_FIELDS,
     VALID_SEARCH_MODES,
     CollectionNotFoundError,
     InvalidSearchModeError,
@@ -21,6 +23,7 @@ from calm.search.searcher import (
     _keyword_match_score,
 )
 from calm.storage.base import VectorStore
+from calm.storage.keyword_index import KeywordIndex, KeywordIndexedStore
 from calm.storage.memory import MemoryStore
 
 # ---------------------------------------------------------------------------
@@ -241,6 +244,13 @@ class TestKeywordMatchScore:
         )
         assert score > 0.0
 
+    def test_identifier_matches_word_pieces(self):
+        """Identifiers also match prose containing their word pieces."""
+        score = _keyword_match_score(
+            "parse_test_results", {"content": "parse the test results"}, ["content"]
+        )
+        assert score > 0.0
+
 
 # ---------------------------------------------------------------------------
 # Integration tests: keyword search via Searcher
@@ -409,7 +419,7 @@ class TestKeywordSearchValues:
 
 
 class TestHybridSearch:
-    """Tests for hybrid (semantic + keyword boost) mode."""
+    """Tests for hybrid (semantic + keyword, fused) mode."""
 
     async def test_hybrid_returns_results(
         self,
@@ -436,7 +446,7 @@ class TestHybridSearch:
         )
         mock_embedding_service.embed.assert_called()
 
-    async def test_hybrid_boosts_keyword_matches(
+    async def test_hybrid_ranks_keyword_matches(
         self,
         searcher_with_memory_store: Searcher,
         memory_store: MemoryStore,
@@ -450,6 +460,8 @@ class TestHybridSearch:
         # Verify at least one result has async/await content
         matching = [r for r in hybrid_results if "async/await" in r.content.lower()]
         assert len(matching) >= 1
+        # Found by both searches, so it outranks semantic-only results
+        assert "async/await" in hybrid_results[0].content.lower()
 
     async def test_hybrid_code_search(
         self,
@@ -612,3 +624,55 @@ class TestKeywordSearchLimit:
             "async", search_mode="keyword", limit=1
         )
         assert len(results) <= 1
+
+
+# ---------------------------------------------------------------------------
+# BM25 keyword index
+# ---------------------------------------------------------------------------
+
+
+class TestKeywordIndexBackedSearch:
+    """Stores wrapped in KeywordIndexedStore are searched with BM25."""
+
+    async def test_keyword_search_uses_index_beyond_scroll_limit(
+        self,
+        mock_embedding_service: AsyncMock,
+        memory_store: MemoryStore,
+        tmp_path,
+    ):
+        store = KeywordIndexedStore(
+            memory_store,
+            KeywordIndex(tmp_path / "keyword.db", KEYWORD_TEXT_FIELDS),
+        )
+
+        def payload(content: str, category: str) -> dict:
+            return {
+                "content": content,
+                "category": category,
+                "importance": 0.5,
+                "tags": [],
+                "created_at": datetime.now(UTC).isoformat(),
+            }
+
+        count = _KEYWORD_SCROLL_LIMIT + 50
+        await store.upsert_batch(
+            "memories",
+            [f"m{i}" for i in range(count)],
+            [_vec() for _ in range(count)],
+            [payload(f"Routine note {i}", "fact") for i in range(count)],
+        )
+        await store.upsert(
+            "memories", "needle", _vec(), payload("Handle TokenizerError", "error")
+        )
+        searcher = Searcher(mock_embedding_service, store)
+
+        results = await searcher.search_memories(
+            "tokenizer error", search_mode="keyword"
+        )
+        assert [r.content for r in results] == ["Handle TokenizerError"]
+
+        results = await searcher.search_memories(
+            "tokenizer", category="fact", search_mode="keyword"
+        )
+        assert results == []
+        mock_embedding_service.embed.assert_not_called()
@@ -0,0 +1,113 @@
+"""Tests for code-aware query parsing and expansion."""
+
+import pytest
+
+from calm.search.query import (
+    expand_identifiers,
+    is_identifier,
+    parse_query,
+    query_terms,
+)
+
+
+class TestParseQuery:
+    """Tests for parse_query."""
+
+    def test_plain_query_is_unchanged(self) -> None:
+        parsed = parse_query("how do we retry failed uploads")
+
+        assert parsed.text == "how do we retry failed uploads"
+        assert parsed.filters == {}
+        assert parsed.identifiers == []
+
+    def test_qualifiers_become_filters(self) -> None:
+        parsed = parse_query("lang:rust path:src/indexers type:method retry")
+
+        assert parsed.text == "retry"
+        assert parsed.filters == {
+            "language": "rust",
+            "file_path": {"$glob": "src/indexers"},
+            "unit_type": "method",
+        }
+
+    def test_language_aliases_and_repeats(self) -> None:
+        parsed = parse_query("lang:py Lang:TS language:python parser")
+
+        assert parsed.filters == {"language": {"$in": ["python", "typescript"]}}
+
+    def test_globs_are_removed_from_text(self) -> None:
+        parsed = parse_query("retry *.rs")
+
+        assert parsed.text == "retry"
+        assert parsed.filters == {"file_path": {"$glob": "*.rs"}}
+
+    def test_several_paths_match_any(self) -> None:
+        parsed = parse_query("path:src/calm tests/**/test_*.py retry")
+
+        assert parsed.filters == {
+            "$or": [
+                {"file_path": {"$glob": "src/calm"}},
+                {"file_path": {"$glob": "tests/**/test_*.py"}},
+            ]
+        }
+
+    def test_prose_wildcards_are_kept(self) -> None:
+        parsed = parse_query("what is this?")
+
+        assert parsed.text == "what is this?"
+        assert parsed.filters == {}
+
+    def test_identifiers_and_paths(self) -> None:
+        parsed = parse_query("where is `Point::translate` in src/geometry.rs")
+
+        assert parsed.identifiers == ["Point::translate", "src/geometry.rs"]
+
+    def test_expanded(self) -> None:
+        parsed = parse_query("lang:rust Point::translate")
+
+        assert parsed.expanded == "Point::translate point translate"
+
+
+class TestIdentifiers:
+    """Tests for identifier detection and expansion."""
+
+    @pytest.mark.parametrize(
+        "token",
+        [
+            "parse_test_results",
+            "Point::translate",
+            "HttpServer",
+            "self.items",
+            "getValue()",
+            "obj->next",
+        ],
+    )
+    def test_is_identifier(self, token: str) -> None:
+        assert is_identifier(token)
+
+    @pytest.mark.parametrize("token", ["parser", "Parser", "_private", "42", "a-b"])
+    def test_is_not_identifier(self, token: str) -> None:
+        assert not is_identifier(token)
+
+    def test_expand_snake_case(self) -> None:
+        assert (
+            expand_identifiers("parse_test_results")
+            == "parse_test_results parse test results"
+        )
+
+    def test_expand_skips_words_already_present(self) -> None:
+        assert (
+            expand_identifiers("translate Point::translate")
+            == "translate Point::translate point"
+        )
+
+    def test_plain_text_is_unchanged(self) -> None:
+        assert expand_identifiers("retry failed uploads") == "retry failed uploads"
+
+    def test_query_terms(self) -> None:
+        assert query_terms("HttpServer start") == [
+            "httpserver",
+            "start",
+            "http",
+            "server",
+        ]
@@ -0,0 +1,132 @@
+"""Tests for the cross-encoder rerank stage of Searcher."""
+
+from datetime import UTC, datetime
+from unittest.mock import AsyncMock
+
+import numpy as np
+import pytest
+
+from calm.embedding.base import Reranker
+from calm.embedding.mock import MockEmbeddingService, MockReranker
+from calm.search.rerank import document_text, rerank_results
+from calm.search.searcher import Searcher
+from calm.storage.base import SearchResult
+from calm.storage.memory import MemoryStore
+
+
+def _result(id: str, score: float, content: str) -> SearchResult:
+    return SearchResult(id=id, score=score, payload={"content": content})
+
+
+class TestRerankResults:
+    """Tests for rerank_results."""
+
+    async def test_reorders_by_rerank_score(self) -> None:
+        results = [
+            _result("a", 0.9, "unrelated text"),
+            _result("b", 0.8, "retry once"),
+            _result("c", 0.7, "retry with backoff"),
+        ]
+
+        reranked = await rerank_results(
+            MockReranker(), "retry backoff", results, ["content"], limit=2
+        )
+
+        assert [r.id for r in reranked] == ["c", "b"]
+        assert reranked[0].score == 1.0
+        assert reranked[0].ranking == {
+            "first_stage_rank": 3,
+            "first_stage_score": 0.7,
+            "rerank_score": 1.0,
+        }
+
+    async def test_keeps_fusion_breakdown(self) -> None:
+        result = _result("a", 0.03, "retry")
+        result.ranking = {"method": "rrf", "semantic_rank": 1}
+
+        reranked = await rerank_results(
+            MockReranker(), "retry", [result], ["content"], limit=5
+        )
+
+        assert reranked[0].ranking is not None
+        assert reranked[0].ranking["method"] == "rrf"
+        assert reranked[0].ranking["first_stage_score"] == 0.03
+
+    async def test_failure_keeps_first_stage_order(self) -> None:
+        reranker = AsyncMock(spec=Reranker)
+        reranker.score.side_effect = RuntimeError("model crashed")
+        results = [_result("a", 0.9, "x"), _result("b", 0.8, "y")]
+
+        reranked = await rerank_results(
+            reranker, "query", results, ["content"], limit=1
+        )
+
+        assert [r.id for r in reranked] == ["a"]
+
+    def test_document_text_joins_text_fields(self) -> None:
+        payload = {"qualified_name": "mod.retry", "docstring": None, "code": "..."}
+        assert (
+            document_text(payload, ["qualified_name", "docstring", "code"])
+            == "mod.retry\n..."
+        )
+
+
+class TestSearcherRerank:
+    """Tests for Searcher with a reranker."""
+
+    @pytest.fixture
+    async def store(self) -> MemoryStore:
+        store = MemoryStore()
+        await store.create_collection("memories", dimension=64)
+        rng = np.random.default_rng(0)
+        contents = [
+            "Deploy with the blue green script",
+            "Retry flaky network calls with exponential backoff",
+            "Use dataclasses for config objects",
+            "Retry once on timeout",
+        ]
+        for i, content in enumerate(contents):
+            await store.upsert(
+                collection="memories",
+                id=f"m{i}",
+                vector=rng.random(64).astype(np.float32),
+                payload={
+                    "content": content,
+                    "category": "fact",
+                    "importance": 0.5,
+                    "tags": [],
+                    "created_at": datetime.now(UTC).isoformat(),
+                },
+            )
+        return store
+
+    async def test_reranks_configured_collections(self, store: MemoryStore) -> None:
+        searcher = Searcher(
+            MockEmbeddingService(dimension=64),
+            store,
+            reranker=MockReranker(),
+            rerank_candidates={"memories": 10},
+        )
+
+        results = await searcher.search_memories("retry backoff", limit=2)
+
+        assert [r.content for r in results] == [
+            "Retry flaky network calls with exponential backoff",
+            "Retry once on timeout",
+        ]
+
+    async def test_unconfigured_collections_are_not_reranked(
+        self, store: MemoryStore
+    ) -> None:
+        reranker = AsyncMock(spec=Reranker)
+        searcher = Searcher(
+            MockEmbeddingService(dimension=64),
+            store,
+            reranker=reranker,
+            rerank_candidates={"code_units": 10},
+        )
+
+        results = await searcher.search_memories("retry backoff", limit=2)
+
+        assert len(results) == 2
+        reranker.score.assert_not_called()
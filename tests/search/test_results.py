@@ -117,6 +117,26 @@ class TestCodeResult:
         result = CodeResult.from_search_result(search_result)
         assert result.docstring is None
 
+    def test_from_search_result_indexer_payload(self):
+        """Verify conversion of the start_line/end_line keys the indexer stores."""
+        search_result = SearchResult(
+            id="code_125",
+            score=0.8,
+            payload={
+                "project": "clams",
+                "file_path": "/src/utils.py",
+                "language": "python",
+                "unit_type": "function",
+                "qualified_name": "utils.slugify",
+                "code": "def slugify(s): ...",
+                "start_line": 3,
+                "end_line": 4,
+            },
+        )
+        result = CodeResult.from_search_result(search_result)
+        assert result.line_start == 3
+        assert result.line_end == 4
+
 
 class TestExperienceResult:
     """Tests for ExperienceResult dataclass."""
@@ -60,6 +60,15 @@ class TestSearchMemories:
         await searcher.search_memories("test query")
         mock_embedding_service.embed.assert_called_once_with("test query")
 
+    async def test_identifiers_not_expanded(
+        self, searcher: Searcher, mock_embedding_service: AsyncMock
+    ):
+        """Verify only code search splits identifiers into word pieces."""
+        await searcher.search_memories("avoid parse_test_results")
+        mock_embedding_service.embed.assert_called_once_with(
+            "avoid parse_test_results"
+        )
+
     async def test_calls_vector_store_with_correct_collection(
         self, searcher: Searcher, mock_vector_store: AsyncMock
     ):
@@ -92,6 +101,19 @@ class TestSearchMemories:
         assert "invalid_mode" in str(exc_info.value).lower()
         assert "semantic" in str(exc_info.value).lower()
 
+    async def test_hybrid_uses_requested_fusion(
+        self, searcher: Searcher, mock_vector_store: AsyncMock
+    ):
+        """Verify the fusion argument reaches the hybrid fusion step."""
+        mock_vector_store.search.return_value = [
+            SearchResult(id="mem-1", score=0.9, payload={"content": "x"})
+        ]
+        mock_vector_store.scroll.return_value = []
+        with pytest.raises(ValueError, match="bogus"):
+            await searcher.search_memories(
+                "test", search_mode="hybrid", fusion="bogus"
+            )
+
     async def test_embedding_failure_raises_embedding_error(
         self, searcher: Searcher, mock_embedding_service: AsyncMock
     ):
@@ -173,6 +195,22 @@ class TestSearchCode:
             "unit_type": "function",
         }
 
+    async def test_inline_qualifiers_merge_with_arguments(
+        self,
+        searcher: Searcher,
+        mock_embedding_service: AsyncMock,
+        mock_vector_store: AsyncMock,
+    ):
+        """Verify qualifiers become filters and explicit arguments win."""
+        await searcher.search_code(
+            "type:method lang:rust Point::translate", language="go"
+        )
+        call_filters = mock_vector_store.search.call_args[1]["filters"]
+        assert call_filters == {"unit_type": "method", "language": "go"}
+        mock_embedding_service.embed.assert_called_once_with(
+            "Point::translate point translate"
+        )
+
     async def test_maps_results_correctly(
         self, searcher: Searcher, mock_vector_store: AsyncMock
     ):
@@ -0,0 +1,48 @@
+"""Tests for code snippet extraction."""
+
+from calm.search.snippets import build_snippet
+
+CODE = "\n".join(
+    [
+        "def load_config(path):",
+        '    """Load settings."""',
+        "    with open(path) as f:",
+        "        data = f.read()",
+        "    return parse_yaml(data)",
+    ]
+)
+
+
+class TestBuildSnippet:
+    """Tests for build_snippet."""
+
+    def test_highlights_phrase_match(self) -> None:
+        """Lines containing the full query are highlighted with file line numbers."""
+        snippet = build_snippet(CODE, "parse_yaml", start_line=40)
+
+        assert snippet is not None
+        assert snippet["highlight_lines"] == [44]
+        assert snippet["start_line"] == 42  # Two lines of context
+        assert snippet["end_line"] == 44
+        assert snippet["text"].splitlines()[-1] == "> 44 |     return parse_yaml(data)"
+
+    def test_falls_back_to_term_matches(self) -> None:
+        """Individual query terms match when the full phrase does not."""
+        snippet = build_snippet(CODE, "open yaml", start_line=1, context_lines=0)
+
+        assert snippet is not None
+        assert snippet["highlight_lines"] == [3, 5]
+        assert snippet["start_line"] == 3
+
+    def test_no_match_returns_head(self) -> None:
+        """Semantic hits without a textual match show the top of the unit."""
+        snippet = build_snippet(CODE, "configuration loader", start_line=1, max_lines=2)
+
+        assert snippet is not None
+        assert snippet["highlight_lines"] == []
+        assert snippet["start_line"] == 1
+        assert snippet["end_line"] == 2
+
+    def test_empty_code(self) -> None:
+        """Empty code yields no snippet."""
+        assert build_snippet("", "query", start_line=1) is None
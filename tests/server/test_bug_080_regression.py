@@ -77,11 +77,11 @@ class TestDeadSessionToolsRemoved:
         # Ping should still be present
         assert "ping" in tool_names
 
-    def test_tool_count_is_29(self) -> None:
-        """Verify we have exactly 29 tools (34 - 5 removed)."""
+    def test_tool_count_is_32(self) -> None:
+        """Verify we have exactly 32 tools (34 - 5 removed + 2 call graph + 1 search)."""
         tool_defs = _get_all_tool_definitions()
-        assert len(tool_defs) == 29, (
-            f"Expected 29 tools after removing 5 session tools, "
+        assert len(tool_defs) == 32, (
+            f"Expected 32 tools after removing 5 session tools, "
             f"but found {len(tool_defs)}"
         )
 
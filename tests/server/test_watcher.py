@@ -0,0 +1,186 @@
+"""Tests for watch-mode incremental re-indexing."""
+
+import os
+import tempfile
+from pathlib import Path
+
+import pytest
+from watchdog.events import DirDeletedEvent, DirMovedEvent
+
+from calm.embedding.mock import MockEmbeddingService
+from calm.indexers import CodeIndexer, TreeSitterParser
+from calm.server.watcher import ProjectWatcher, _EventHandler
+from calm.storage.memory import MemoryStore
+from calm.storage.metadata import MetadataStore
+
+
+@pytest.fixture
+async def indexer():
+    """Create a CodeIndexer with in-memory vectors and a temp metadata DB."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        metadata_store = MetadataStore(Path(tmpdir) / "metadata.db")
+        await metadata_store.initialize()
+        yield CodeIndexer(
+            TreeSitterParser(), MockEmbeddingService(), MemoryStore(), metadata_store
+        )
+        await metadata_store.close()
+
+
+async def _names(indexer: CodeIndexer) -> set[str]:
+    results = await indexer.vector_store.scroll(
+        collection="code_units", filters={"project": "proj"}
+    )
+    return {r.payload["qualified_name"] for r in results}
+
+
+@pytest.mark.asyncio
+async def test_index_directory_registers_project(indexer, tmp_path):
+    """Indexing a directory records the project root for watch mode."""
+    (tmp_path / "app.py").write_text("def main():\n    pass\n")
+    await indexer.index_directory(str(tmp_path), "proj")
+
+    watcher = ProjectWatcher(indexer)
+    assert await watcher.load_projects() == {"proj": str(tmp_path)}
+
+    project = await indexer.metadata_store.get_project("proj")
+    assert project is not None
+    assert project.last_indexed is not None
+
+
+@pytest.mark.asyncio
+async def test_flush_reindexes_changed_and_removes_deleted(indexer, tmp_path):
+    """Changed files are re-indexed and deleted files lose their units."""
+    app = tmp_path / "app.py"
+    util = tmp_path / "util.py"
+    app.write_text("def main():\n    pass\n")
+    util.write_text("def helper():\n    pass\n")
+    await indexer.index_directory(str(tmp_path), "proj")
+
+    watcher = ProjectWatcher(indexer, debounce_seconds=0)
+    await watcher.load_projects()
+
+    app.write_text("def main():\n    pass\n\n\ndef extra():\n    pass\n")
+    os.utime(app, (app.stat().st_atime, app.stat().st_mtime + 10))
+    util.unlink()
+    # Bursts of events for the same file collapse into one entry
+    watcher.on_change("proj", str(app))
+    watcher.on_change("proj", str(app))
+    watcher.on_change("proj", str(util), deleted=True)
+
+    counts = await watcher.flush()
+
+    assert counts == {"indexed": 1, "removed": 1}
+    assert await _names(indexer) == {"app.main", "app.extra"}
+    assert not await indexer.is_file_indexed(str(util), "proj")
+
+
+@pytest.mark.asyncio
+async def test_flush_ignores_unsupported_and_excluded_files(indexer, tmp_path):
+    """Files the directory indexer would skip are skipped in watch mode too."""
+    (tmp_path / "app.py").write_text("def main():\n    pass\n")
+    await indexer.index_directory(str(tmp_path), "proj")
+
+    watcher = ProjectWatcher(indexer, debounce_seconds=0)
+    await watcher.load_projects()
+
+    notes = tmp_path / "notes.txt"
+    notes.write_text("not code\n")
+    vendored = tmp_path / "node_modules" / "lib.js"
+    vendored.parent.mkdir()
+    vendored.write_text("function lib() {}\n")
+    watcher.on_change("proj", str(notes))
+    watcher.on_change("proj", str(vendored))
+    watcher.on_change("other", str(tmp_path / "app.py"))
+
+    assert await watcher.flush() == {"indexed": 0, "removed": 0}
+    assert await _names(indexer) == {"app.main"}
+
+
+@pytest.mark.asyncio
+async def test_symlinked_root_matches_indexed_paths(indexer, tmp_path):
+    """Changes under a symlinked root update the files indexed through it."""
+    real = tmp_path / "real"
+    real.mkdir()
+    link = tmp_path / "link"
+    link.symlink_to(real, target_is_directory=True)
+    app = link / "app.py"
+    app.write_text("def main():\n    pass\n")
+    await indexer.index_directory(str(link), "proj")
+
+    watcher = ProjectWatcher(indexer, debounce_seconds=0)
+    assert await watcher.load_projects() == {"proj": str(link)}
+
+    app.write_text("def main():\n    pass\n\n\ndef extra():\n    pass\n")
+    os.utime(app, (app.stat().st_atime, app.stat().st_mtime + 10))
+    watcher.on_change("proj", str(app))
+    assert await watcher.flush() == {"indexed": 1, "removed": 0}
+    assert await _names(indexer) == {"app.main", "app.extra"}
+
+    app.unlink()
+    watcher.on_change("proj", str(app), deleted=True)
+    assert await watcher.flush() == {"indexed": 0, "removed": 1}
+    assert await _names(indexer) == set()
+
+
+@pytest.mark.asyncio
+async def test_directory_rename_moves_indexed_files(indexer, tmp_path):
+    """A single directory move event re-homes every file under it."""
+    old = tmp_path / "src" / "old"
+    (old / "sub").mkdir(parents=True)
+    (old / "app.py").write_text("def main():\n    pass\n")
+    (old / "sub" / "util.py").write_text("def helper():\n    pass\n")
+    await indexer.index_directory(str(tmp_path), "proj")
+
+    watcher = ProjectWatcher(indexer, debounce_seconds=0)
+    await watcher.load_projects()
+    handler = _EventHandler(watcher, "proj")
+
+    new = tmp_path / "src" / "new"
+    old.rename(new)
+    handler.dispatch(DirMovedEvent(str(old), str(new)))
+
+    assert await watcher.flush() == {"indexed": 2, "removed": 2}
+    for path in (old / "app.py", old / "sub" / "util.py"):
+        assert not await indexer.is_file_indexed(str(path), "proj")
+    for path in (new / "app.py", new / "sub" / "util.py"):
+        assert await indexer.is_file_indexed(str(path), "proj")
+
+    (new / "sub" / "util.py").unlink()
+    (new / "sub").rmdir()
+    handler.dispatch(DirDeletedEvent(str(new / "sub")))
+    assert await watcher.flush() == {"indexed": 0, "removed": 1}
+    assert await indexer.is_file_indexed(str(new / "app.py"), "proj")
+
+
+@pytest.mark.asyncio
+async def test_reindexing_subdirectory_keeps_project_root(indexer, tmp_path):
+    """Indexing part of a project never narrows its recorded root."""
+    pkg = tmp_path / "pkg"
+    pkg.mkdir()
+    (pkg / "app.py").write_text("def main():\n    pass\n")
+    await indexer.index_directory(str(pkg), "proj")
+    await indexer.index_directory(str(tmp_path), "proj")
+    await indexer.index_directory(str(pkg), "proj")
+
+    project = await indexer.metadata_store.get_project("proj")
+    assert project is not None
+    assert project.root_path == str(tmp_path)
+
+
+@pytest.mark.asyncio
+async def test_watch_project_follows_newly_indexed_projects(indexer, tmp_path):
+    """Projects indexed after the watcher starts are watched too."""
+    watcher = ProjectWatcher(indexer, debounce_seconds=0)
+    assert not await watcher.watch_project("proj")
+
+    await watcher.start()
+    try:
+        assert not await watcher.watch_project("proj")
+
+        (tmp_path / "app.py").write_text("def main():\n    pass\n")
+        await indexer.index_directory(str(tmp_path), "proj")
+
+        assert await watcher.watch_project("proj")
+        assert watcher.roots == {"proj": str(tmp_path)}
+    finally:
+        await watcher.stop()
@@ -217,6 +217,7 @@ class _MockIndexingStats:
     files_indexed: int
     units_indexed: int
     files_skipped: int
+    files_removed: int = 0
     errors: list[str] = field(default_factory=list)
     duration_ms: int = 0
 
@@ -230,6 +231,7 @@ async def test_index_codebase_success_with_indexer(mock_services, tmp_path):
             files_indexed=12,
             units_indexed=45,
             files_skipped=3,
+            files_removed=2,
             errors=[],
             duration_ms=1500,
         )
@@ -253,5 +255,185 @@ async def test_index_codebase_success_with_indexer(mock_services, tmp_path):
     assert result["files_indexed"] == 12
     assert result["units_indexed"] == 45
     assert result["files_skipped"] == 3
+    assert result["files_removed"] == 2
     assert result["errors"] == 0
     assert result["duration_ms"] == 1500
+
+
+def _call_graph_indexer(edges: list[tuple[str, str]]) -> Mock:
+    """Create a mock CodeIndexer whose metadata store serves the given edges."""
+    from datetime import datetime
+
+    from calm.storage.metadata import CallGraphEntry
+
+    def _entry(caller: str, callee: str) -> CallGraphEntry:
+        return CallGraphEntry(
+            id=None,
+            caller_qualified_name=caller,
+            callee_qualified_name=callee,
+            caller_file=f"/src/{caller.split('.')[0]}.py",
+            callee_file=f"/src/{callee.split('.')[0]}.py",
+            project="test-project",
+            indexed_at=datetime.now(),
+        )
+
+    async def get_callers(name: str, project: str) -> list[CallGraphEntry]:
+        return [_entry(a, b) for a, b in edges if b == name]
+
+    async def get_callees(name: str, project: str) -> list[CallGraphEntry]:
+        return [_entry(a, b) for a, b in edges if a == name]
+
+    indexer = Mock()
+    indexer.metadata_store.get_callers = AsyncMock(side_effect=get_callers)
+    indexer.metadata_store.get_callees = AsyncMock(side_effect=get_callees)
+    return indexer
+
+
+@pytest.mark.asyncio
+async def test_get_callees_depth_limited(mock_services):
+    """Test that get_callees walks the call graph up to the requested depth."""
+    indexer = _call_graph_indexer(
+        [("app.main", "app.run"), ("app.run", "util.load"), ("util.load", "app.main")]
+    )
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=indexer
+    )
+
+    result = await tools["get_callees"](qualified_name="app.main", project="test-project")
+    assert [c["qualified_name"] for c in result["callees"]] == ["app.run"]
+    assert result["callees"][0]["file_path"] == "/src/app.py"
+
+    # Cycles back to the starting unit are not repeated
+    result = await tools["get_callees"](
+        qualified_name="app.main", project="test-project", depth=3
+    )
+    assert [(c["qualified_name"], c["depth"]) for c in result["callees"]] == [
+        ("app.run", 1),
+        ("util.load", 2),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_get_callers_limit_truncates(mock_services):
+    """Test that get_callers stops at the limit and reports truncation."""
+    indexer = _call_graph_indexer([("a.one", "util.load"), ("b.two", "util.load")])
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=indexer
+    )
+
+    result = await tools["get_callers"](
+        qualified_name="util.load", project="test-project", limit=1
+    )
+
+    assert result["count"] == 1
+    assert result["callers"][0]["qualified_name"] == "a.one"
+    assert result["truncated"] is True
+
+
+@pytest.mark.asyncio
+async def test_call_graph_tools_validation(mock_services):
+    """Test call graph tool parameter validation and unavailable indexer."""
+    tools = get_code_tools(mock_services.vector_store, mock_services.code_embedder)
+
+    result = await tools["get_callers"](
+        qualified_name="app.main", project="test-project", depth=6
+    )
+    assert result["error"]["type"] == "validation_error"
+
+    result = await tools["get_callees"](qualified_name="app.main", project="test-project")
+    assert result["status"] == "not_available"
+
+
+@pytest.mark.asyncio
+async def test_search_code_returns_highlighted_snippet(mock_services, mock_search_result):
+    """Test that search_code replaces the stored body with a highlighted snippet."""
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=Mock()
+    )
+    mock_services.vector_store.search.return_value = [
+        mock_search_result(
+            payload={
+                "project": "test-project",
+                "name": "retry",
+                "start_line": 10,
+                "code": "def retry(fn):\n    backoff = 1\n    return fn()",
+            }
+        )
+    ]
+
+    result = await tools["search_code"](query="backoff")
+
+    hit = result["results"][0]
+    assert "code" not in hit
+    assert hit["snippet"]["highlight_lines"] == [11]
+    assert "> 11 |     backoff = 1" in hit["snippet"]["text"]
+
+
+@pytest.mark.asyncio
+async def test_search_code_hybrid_includes_ranking(mock_services, mock_search_result):
+    """Test that hybrid search_code results carry their fusion breakdown."""
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=Mock()
+    )
+    semantic_only = mock_search_result(
+        id="a", score=0.9, payload={"name": "load", "code": "def load(): pass"}
+    )
+    both = mock_search_result(
+        id="b", score=0.8, payload={"name": "retry", "code": "def retry(): pass"}
+    )
+    mock_services.vector_store.search.return_value = [semantic_only, both]
+    mock_services.vector_store.scroll.return_value = [semantic_only, both]
+
+    result = await tools["search_code"](query="retry", search_mode="hybrid")
+
+    assert [hit["name"] for hit in result["results"]] == ["retry", "load"]
+    ranking = result["results"][0]["ranking"]
+    assert ranking["method"] == "rrf"
+    assert ranking["semantic_rank"] == 2
+    assert ranking["keyword_rank"] == 1
+    assert result["results"][1]["ranking"]["keyword_rank"] is None
+
+
+@pytest.mark.asyncio
+async def test_search_code_inline_qualifiers(mock_services, mock_search_result):
+    """Test that lang:/path: qualifiers become filters and leave the query."""
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=Mock()
+    )
+    mock_services.vector_store.search.return_value = []
+
+    await tools["search_code"](query="lang:rs path:src/indexers retry_backoff")
+
+    filters = mock_services.vector_store.search.call_args.kwargs["filters"]
+    assert filters["language"] == "rust"
+    assert filters["file_path"] == {"$glob": "src/indexers"}
+    mock_services.code_embedder.embed.assert_called_once_with(
+        "retry_backoff retry backoff"
+    )
+
+
+@pytest.mark.asyncio
+async def test_search_code_explicit_language_overrides_qualifier(mock_services):
+    """Test that the language argument wins over a lang: qualifier."""
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=Mock()
+    )
+    mock_services.vector_store.search.return_value = []
+
+    await tools["search_code"](query="lang:rust retry", language="go")
+
+    filters = mock_services.vector_store.search.call_args.kwargs["filters"]
+    assert filters["language"] == "go"
+
+
+@pytest.mark.asyncio
+async def test_search_code_only_qualifiers_returns_empty(mock_services):
+    """Test that a query of only qualifiers searches nothing."""
+    tools = get_code_tools(
+        mock_services.vector_store, mock_services.code_embedder, code_indexer=Mock()
+    )
+
+    result = await tools["search_code"](query="lang:rust *.rs")
+
+    assert result == {"results": [], "count": 0}
+    mock_services.vector_store.search.assert_not_called()
@@ -315,6 +315,7 @@ class TestSearchCodeLanguageValidation:
             "swift",
             "kotlin",
             "scala",
+            "lua",
         ],
     )
     async def test_all_supported_languages(
@@ -5,6 +5,8 @@ from uuid import UUID
 
 import pytest
 
+from calm.config import settings
+from calm.embedding.mock import MockReranker
 from calm.tools.memory import get_memory_tools
 
 
@@ -164,6 +166,68 @@ async def test_retrieve_memories_limit_validation(mock_services):
     assert "error" in result
     assert result["error"]["type"] == "validation_error"
     assert re.search(r"Limit.*out of range", result["error"]["message"])
+@pytest.mark.asyncio
+async def test_retrieve_memories_hybrid_includes_ranking(
+    mock_services, mock_search_result
+):
+    """Test hybrid retrieval fuses rankings and reports the breakdown."""
+    tools = get_memory_tools(mock_services.vector_store, mock_services.semantic_embedder)
+    retrieve_memories = tools["retrieve_memories"]
+
+    mock_result = mock_search_result()
+    mock_services.vector_store.search.return_value = [mock_result]
+    mock_services.vector_store.scroll.return_value = [mock_result]
+
+    result = await retrieve_memories(
+        query="test content", search_mode="hybrid", fusion="weighted"
+    )
+
+    assert result["count"] == 1
+    ranking = result["results"][0]["ranking"]
+    assert ranking["method"] == "weighted"
+    assert ranking["semantic_rank"] == 1
+    assert ranking["keyword_rank"] == 1
+    assert ranking["fused_score"] == result["results"][0]["score"]
+
+
+@pytest.mark.asyncio
+async def test_retrieve_memories_reranks_configured_collection(
+    mock_services, mock_search_result, monkeypatch
+):
+    """Test that retrieval applies the rerank stage configured in settings."""
+    monkeypatch.setitem(settings.rerank.collections, "memories", 10)
+    tools = get_memory_tools(
+        mock_services.vector_store,
+        mock_services.semantic_embedder,
+        reranker=MockReranker(),
+    )
+    retrieve_memories = tools["retrieve_memories"]
+
+    mock_services.vector_store.search.return_value = [
+        mock_search_result(id=str(i), score=score, payload={"content": content})
+        for i, (score, content) in enumerate(
+            [(0.9, "deploy script"), (0.8, "retry with backoff")]
+        )
+    ]
+
+    result = await retrieve_memories(query="retry backoff", limit=1)
+
+    assert [r["content"] for r in result["results"]] == ["retry with backoff"]
+    assert result["results"][0]["ranking"]["first_stage_rank"] == 2
+    assert mock_services.vector_store.search.call_args.kwargs["limit"] == 10
+
+
+@pytest.mark.asyncio
+async def test_retrieve_memories_invalid_fusion(mock_services):
+    """Test validation error for an unknown fusion method."""
+    tools = get_memory_tools(mock_services.vector_store, mock_services.semantic_embedder)
+    retrieve_memories = tools["retrieve_memories"]
+
+    result = await retrieve_memories(query="test", search_mode="hybrid", fusion="max")
+    assert result["error"]["type"] == "validation_error"
+    assert re.search(r"Invalid fusion", result["error"]["message"])
+
+
 @pytest.mark.asyncio
 async def test_list_memories_success(mock_services, mock_search_result):
     """Test successful memory listing."""
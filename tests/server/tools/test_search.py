@@ -5,7 +5,11 @@ from unittest.mock import AsyncMock
 
 import pytest
 
+from calm.embedding.mock import MockEmbeddingService
+from calm.storage.base import SearchResult
+from calm.storage.memory import MemoryStore
 from calm.tools.learning import get_learning_tools
+from calm.tools.search import get_search_tools
 
 
 @pytest.fixture
@@ -153,3 +157,149 @@ class TestSearchExperiences:
         assert "error" in result
         assert result["error"]["type"] == "validation_error"
         assert "between 1 and 50" in result["error"]["message"]
+
+    @pytest.mark.asyncio
+    async def test_search_experiences_hybrid_includes_ranking(
+        self, tools: dict[str, Any], mock_vector_store: AsyncMock
+    ) -> None:
+        """Test that hybrid results carry their fusion breakdown."""
+        experience = SearchResult(
+            id="exp-1",
+            score=0.7,
+            payload={"goal": "Fix flaky async test", "domain": "debugging"},
+        )
+        mock_vector_store.search.return_value = [experience]
+
+        tool = tools["search_experiences"]
+        result = await tool(query="flaky", search_mode="hybrid", fusion="rrf")
+
+        assert result["count"] == 1
+        ranking = result["results"][0]["ranking"]
+        assert ranking["method"] == "rrf"
+        assert ranking["semantic_rank"] == 1
+        assert ranking["keyword_rank"] is None
+
+    @pytest.mark.asyncio
+    async def test_search_experiences_invalid_fusion(
+        self, tools: dict[str, Any]
+    ) -> None:
+        """Test validation error for an unknown fusion method."""
+        tool = tools["search_experiences"]
+        result = await tool(query="test query", search_mode="hybrid", fusion="sum")
+
+        assert result["error"]["type"] == "validation_error"
+        assert "Invalid fusion" in result["error"]["message"]
+
+
+class TestSearchAll:
+    """Tests for the federated search_all tool."""
+
+    @pytest.fixture
+    async def store(self) -> MemoryStore:
+        """MemoryStore with memories and code (no other collections)."""
+        store = MemoryStore()
+        semantic = MockEmbeddingService(dimension=768)
+        code = MockEmbeddingService(dimension=384)
+        await store.create_collection("memories", dimension=768)
+        await store.create_collection("code_units", dimension=384)
+        for i, content in enumerate(["Retry with backoff", "Use dataclasses"]):
+            await store.upsert(
+                "memories", f"m{i}", await semantic.embed(content),
+                {"content": content, "category": "fact"},
+            )
+        await store.upsert(
+            "code_units", "c0", await code.embed("def retry(): pass"),
+            {"project": "calm", "name": "retry", "code": "def retry(): pass"},
+        )
+        return store
+
+    @pytest.fixture
+    def search_all(self, store: MemoryStore) -> Any:
+        return get_search_tools(
+            store,
+            MockEmbeddingService(dimension=768),
+            MockEmbeddingService(dimension=384),
+        )["search_all"]
+
+    @pytest.mark.asyncio
+    async def test_interleaves_sources(self, search_all: Any) -> None:
+        """Results from every source are merged with normalized scores."""
+        result = await search_all(query="retry")
+
+        assert "errors" not in result
+        assert result["count"] == 3
+        sources = [r["source"] for r in result["results"]]
+        assert sorted(sources) == ["code", "memories", "memories"]
+        # Each source's best hit normalizes to 1.0
+        assert [r["score"] for r in result["results"]][:2] == [1.0, 1.0]
+        assert result["results"][-1]["score"] == 0.0
+        code_hit = next(r for r in result["results"] if r["source"] == "code")
+        assert "code" not in code_hit
+        assert "snippet" in code_hit
+        assert code_hit["id"] == "c0"
+
+    @pytest.mark.asyncio
+    async def test_selected_sources_and_keyword_mode(self, search_all: Any) -> None:
+        """Only the chosen sources are searched, in any search mode."""
+        result = await search_all(
+            query="backoff", sources=["memories"], search_mode="keyword"
+        )
+
+        assert result["count"] == 1
+        assert result["results"][0]["source"] == "memories"
+        assert result["results"][0]["content"] == "Retry with backoff"
+
+    @pytest.mark.asyncio
+    async def test_limit_applies_overall(self, search_all: Any) -> None:
+        result = await search_all(query="retry", limit=2)
+        assert result["count"] == 2
+
+    @pytest.mark.asyncio
+    async def test_invalid_sources(self, search_all: Any) -> None:
+        for sources in (["memories", "notes"], []):
+            result = await search_all(query="retry", sources=sources)
+            assert result["error"]["type"] == "validation_error"
+            assert "Invalid sources" in result["error"]["message"]
+
+    @pytest.mark.asyncio
+    async def test_invalid_search_mode(self, search_all: Any) -> None:
+        result = await search_all(query="retry", search_mode="fuzzy")
+        assert result["error"]["type"] == "validation_error"
+
+    @pytest.mark.asyncio
+    async def test_failed_source_is_reported(
+        self, mock_vector_store: AsyncMock, mock_semantic_embedder: AsyncMock
+    ) -> None:
+        """A failing source doesn't hide results from the others."""
+
+        async def search(collection: str, **kwargs: Any) -> list[SearchResult]:
+            if collection == "commits":
+                raise RuntimeError("connection reset")
+            return [SearchResult(id=f"{collection}-1", score=0.5, payload={})]
+
+        mock_vector_store.search.side_effect = search
+        tools = get_search_tools(
+            mock_vector_store, mock_semantic_embedder, mock_semantic_embedder
+        )
+
+        result = await tools["search_all"](
+            query="retry", sources=["memories", "commits"]
+        )
+
+        assert [r["id"] for r in result["results"]] == ["memories-1"]
+        assert result["errors"] == {"commits": "connection reset"}
+
+    @pytest.mark.asyncio
+    async def test_not_found_error_is_not_an_empty_source(
+        self, mock_vector_store: AsyncMock, mock_semantic_embedder: AsyncMock
+    ) -> None:
+        """Only a missing collection counts as empty, not any "not found"."""
+        mock_vector_store.search.side_effect = RuntimeError("model file not found")
+        tools = get_search_tools(
+            mock_vector_store, mock_semantic_embedder, mock_semantic_embedder
+        )
+
+        result = await tools["search_all"](query="retry", sources=["memories"])
+
+        assert result["results"] == []
+        assert result["errors"] == {"memories": "model file not found"}
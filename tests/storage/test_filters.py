@@ -0,0 +1,153 @@
+"""Tests for the shared payload filter language."""
+
+import pytest
+
+from calm.storage.filters import (
+    And,
+    FieldCondition,
+    FilterError,
+    Not,
+    Or,
+    field_values,
+    glob_literals,
+    matches_filters,
+    parse_filters,
+)
+
+PAYLOAD = {
+    "project": "clams",
+    "language": "rust",
+    "tags": ["ffi", "unsafe"],
+    "complexity": 7,
+    "docstring": None,
+    "file_path": "/repo/src/indexers/tree_sitter.rs",
+    "meta": {"crate": "core", "owners": [{"name": "ana"}, {"name": "bo"}]},
+}
+
+
+class TestParseFilters:
+    """Tests for parsing filter dicts into the AST."""
+
+    def test_equality_and_operators(self):
+        """Test field conditions, with range bounds grouped together."""
+        node = parse_filters(
+            {"project": "clams", "complexity": {"$gte": 3, "$lt": 10, "$nin": [5]}}
+        )
+        assert node == And(
+            (
+                FieldCondition("project", "$eq", "clams"),
+                FieldCondition("complexity", "$range", {"$gte": 3, "$lt": 10}),
+                FieldCondition("complexity", "$nin", (5,)),
+            )
+        )
+
+    def test_logical_operators(self):
+        """Test $or and $not nest parsed sub-filters."""
+        node = parse_filters({"$or": [{"a": 1}, {"$not": {"b": 2}}]})
+        assert node == And(
+            (
+                Or(
+                    (
+                        And((FieldCondition("a", "$eq", 1),)),
+                        And((Not(And((FieldCondition("b", "$eq", 2),))),)),
+                    )
+                ),
+            )
+        )
+
+    @pytest.mark.parametrize(
+        "filters",
+        [
+            {"name": {"$regex": "x"}},
+            {"name": {"$ne": "x"}},
+            {"$and": [{"a": 1}]},
+            {"$or": []},
+            {"$or": {"a": 1}},
+            {"$not": [{"a": 1}]},
+            {"tags": {"$in": "ffi"}},
+            {"docstring": {"$exists": "yes"}},
+            {"file_path": {"$glob": ["*.rs"]}},
+            {"file_path": {"$glob": "/"}},
+            {"name": {}},
+        ],
+    )
+    def test_invalid_filters_raise(self, filters):
+        """Test unknown operators and malformed values are rejected."""
+        with pytest.raises(FilterError):
+            parse_filters(filters)
+
+
+class TestMatchesFilters:
+    """Tests for evaluating filters against payloads."""
+
+    @pytest.mark.parametrize(
+        "filters",
+        [
+            {},
+            {"project": "clams", "language": "rust"},
+            {"tags": "ffi"},
+            {"tags": {"$contains": "unsafe"}},
+            {"tags": {"$contains": ["ffi", "unsafe"]}},
+            {"language": {"$in": ["go", "rust"]}},
+            {"tags": {"$in": ["unsafe", "async"]}},
+            {"language": {"$nin": ["go"]}},
+            {"missing": {"$nin": ["go"]}},
+            {"complexity": {"$gt": 5, "$lte": 7}},
+            {"project": {"$exists": True}},
+            {"docstring": {"$exists": False}},
+            {"missing": {"$exists": False}},
+            {"$or": [{"language": "go"}, {"complexity": {"$gte": 5}}]},
+            {"$not": {"language": "go"}},
+            {"meta.crate": "core"},
+            {"meta.owners.name": "bo"},
+            {"meta.crate": {"$exists": True}},
+            {"file_path": {"$glob": "*.rs"}},
+            {"file_path": {"$glob": "src/indexers"}},
+            {"file_path": {"$glob": "src/**/tree_*.rs"}},
+            {"file_path": {"$glob": "/repo/src"}},
+            {"file_path": {"$glob": "tree_sitter.r?"}},
+        ],
+    )
+    def test_matching(self, filters):
+        """Test filters that match the sample payload."""
+        assert matches_filters(PAYLOAD, filters)
+
+    @pytest.mark.parametrize(
+        "filters",
+        [
+            {"language": "go"},
+            {"missing": "x"},
+            {"tags": {"$contains": ["ffi", "async"]}},
+            {"language": {"$in": ["go"]}},
+            {"tags": {"$nin": ["unsafe"]}},
+            {"complexity": {"$gt": 7}},
+            {"project": {"$gte": 3}},
+            {"docstring": {"$exists": True}},
+            {"$or": [{"language": "go"}, {"complexity": {"$lt": 5}}]},
+            {"$not": {"language": "rust"}},
+            {"meta.owners.name": "cy"},
+            {"meta.missing.name": {"$exists": True}},
+            {"file_path": {"$glob": "*.py"}},
+            {"file_path": {"$glob": "indexers/*.py"}},
+            {"file_path": {"$glob": "src/*.rs"}},
+            {"file_path": {"$glob": "/src"}},
+            {"file_path": {"$glob": "dexers"}},
+            {"complexity": {"$glob": "7"}},
+        ],
+    )
+    def test_not_matching(self, filters):
+        """Test filters that reject the sample payload."""
+        assert not matches_filters(PAYLOAD, filters)
+
+    def test_field_values_flattens_lists(self):
+        """Test dotted paths and list flattening."""
+        assert field_values(PAYLOAD, "tags") == ["ffi", "unsafe"]
+        assert field_values(PAYLOAD, "meta.owners.name") == ["ana", "bo"]
+        assert field_values(PAYLOAD, "missing") == []
+        assert field_values({"a.b": 1}, "a.b") == [1]
+
+    def test_glob_literals(self):
+        """Test the wildcard-free parts used to approximate globs."""
+        assert glob_literals("src/**/*.rs") == ["src/", ".rs"]
+        assert glob_literals("src/indexers") == ["src/indexers"]
+        assert glob_literals("**/*") == []
@@ -0,0 +1,233 @@
+"""Tests for the BM25 keyword index and KeywordIndexedStore."""
+
+from collections.abc import AsyncIterator
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+from calm.storage.keyword_index import KeywordIndex, KeywordIndexedStore, tokenize
+from calm.storage.memory import MemoryStore
+
+TEXT_FIELDS = {"code_units": ["qualified_name", "code"], "memories": ["content"]}
+
+
+def _vec() -> np.ndarray:
+    return np.array([1.0, 0.0, 0.0], dtype=np.float32)
+
+
+@pytest.fixture
+async def index(tmp_path: Path) -> AsyncIterator[KeywordIndex]:
+    """Create a keyword index backed by a temporary database."""
+    index = KeywordIndex(tmp_path / "keyword.db", TEXT_FIELDS)
+    yield index
+    await index.close()
+
+
+@pytest.fixture
+async def store(index: KeywordIndex) -> KeywordIndexedStore:
+    """Create an indexed MemoryStore with a code_units collection."""
+    store = KeywordIndexedStore(MemoryStore(), index)
+    await store.create_collection("code_units", dimension=3)
+    return store
+
+
+class TestTokenize:
+    """Tests for code-aware tokenization."""
+
+    @pytest.mark.parametrize(
+        ("text", "expected"),
+        [
+            ("hello world", ["hello", "world"]),
+            ("parse_file", ["parse_file", "parse", "file"]),
+            ("ParseFile", ["parsefile", "parse", "file"]),
+            ("HTTPServer2", ["httpserver2", "http", "server", "2"]),
+            ("Parser::new", ["parser", "new"]),
+            ("self.__init__", ["self", "init"]),
+            ("Fix BUG-042", ["fix", "bug", "042"]),
+        ],
+    )
+    def test_tokenize(self, text: str, expected: list[str]) -> None:
+        """Test identifiers split on underscores, case changes and paths."""
+        assert tokenize(text) == expected
+
+
+class TestKeywordIndex:
+    """Tests for BM25 ranking."""
+
+    async def test_ranks_rare_terms_higher(self, index: KeywordIndex) -> None:
+        """Test that BM25 favors documents matching rarer terms."""
+        await index.add(
+            "memories",
+            ["common", "rare", "none"],
+            [
+                {"content": "use the parser for the config"},
+                {"content": "use the tokenizer for the config"},
+                {"content": "unrelated text"},
+            ],
+        )
+
+        results = await index.search("memories", "tokenizer config")
+        assert [doc_id for doc_id, _ in results] == ["rare", "common"]
+        assert results[0][1] > results[1][1] > 0
+
+    async def test_stores_only_text_fields(self, index: KeywordIndex) -> None:
+        """Test that only the indexed text fields are kept, not payloads."""
+        await index.add(
+            "code_units",
+            ["u1"],
+            [{"qualified_name": "mod.load", "code": "def load()", "project": "p"}],
+        )
+        assert await index.document("code_units", "u1") == "mod.load\ndef load()"
+        assert await index.document("code_units", "u2") is None
+
+    async def test_reindex_and_remove(self, index: KeywordIndex) -> None:
+        """Test that re-adding replaces postings and remove drops them."""
+        await index.add("memories", ["m1"], [{"content": "alpha"}])
+        await index.add("memories", ["m1"], [{"content": "beta"}])
+        assert await index.search("memories", "alpha") == []
+        assert [d for d, _ in await index.search("memories", "beta")] == ["m1"]
+
+        await index.remove("memories", ["m1"])
+        assert await index.search("memories", "beta") == []
+
+    async def test_ignores_unconfigured_collections(self, index: KeywordIndex) -> None:
+        """Test that collections without text fields are not indexed."""
+        await index.add("commits", ["c1"], [{"message": "fix parser"}])
+        assert await index.search("commits", "parser") == []
+
+
+class TestKeywordIndexedStore:
+    """Tests for keeping the index in step with the vector store."""
+
+    async def test_upsert_and_delete_update_index(
+        self, store: KeywordIndexedStore
+    ) -> None:
+        """Test that writes through the wrapper are searchable."""
+        await store.upsert("code_units", "a", _vec(), {"qualified_name": "parse_file"})
+        await store.upsert_batch(
+            "code_units",
+            ["b", "c"],
+            [_vec(), _vec()],
+            [{"qualified_name": "ParseError"}, {"qualified_name": "render"}],
+        )
+
+        results = await store.keyword_search("code_units", "parse")
+        assert sorted(r.id for r in results) == ["a", "b"]
+
+        await store.delete("code_units", "a")
+        results = await store.keyword_search("code_units", "parse")
+        assert [r.id for r in results] == ["b"]
+        assert results[0].score == pytest.approx(1.0)
+        assert results[0].payload == {"qualified_name": "ParseError"}
+        assert await store.count("code_units") == 2
+
+    async def test_filters_and_limit(self, store: KeywordIndexedStore) -> None:
+        """Test that payload filters and limits apply to BM25 results."""
+        await store.upsert_batch(
+            "code_units",
+            [f"u{i}" for i in range(4)],
+            [_vec()] * 4,
+            [
+                {"qualified_name": f"mod.load_{i}", "project": f"p{i % 2}"}
+                for i in range(4)
+            ],
+        )
+
+        results = await store.keyword_search(
+            "code_units", "load", filters={"project": "p1"}
+        )
+        assert sorted(r.id for r in results) == ["u1", "u3"]
+        assert len(await store.keyword_search("code_units", "load", limit=1)) == 1
+
+    async def test_backfills_existing_collection(self, index: KeywordIndex) -> None:
+        """Test that data written before wrapping is indexed on first search."""
+        inner = MemoryStore()
+        await inner.create_collection("memories", dimension=3)
+        await inner.upsert("memories", "m1", _vec(), {"content": "prefer dataclasses"})
+
+        store = KeywordIndexedStore(inner, index)
+        assert await index.indexed_count("memories") is None
+
+        results = await store.keyword_search("memories", "dataclasses")
+        assert [r.id for r in results] == ["m1"]
+        assert await index.indexed_count("memories") == 1
+
+    async def test_backfills_writes_that_bypass_wrapper(
+        self, store: KeywordIndexedStore
+    ) -> None:
+        """Test that points written straight to the store are picked up."""
+        await store.create_collection("memories", dimension=3)
+        await store.upsert("memories", "m1", _vec(), {"content": "prefer dataclasses"})
+        assert [r.id for r in await store.keyword_search("memories", "prefer")] == [
+            "m1"
+        ]
+
+        await store.store.upsert("memories", "m2", _vec(), {"content": "prefer enums"})
+        results = await store.keyword_search("memories", "prefer")
+        assert sorted(r.id for r in results) == ["m1", "m2"]
+
+        await store.store.delete("memories", "m1")
+        results = await store.keyword_search("memories", "prefer")
+        assert [r.id for r in results] == ["m2"]
+
+    async def test_resyncs_points_rewritten_behind_wrapper(
+        self, store: KeywordIndexedStore, index: KeywordIndex
+    ) -> None:
+        """Test that a rewrite keeping the point count is re-indexed by ID."""
+        await store.create_collection("memories", dimension=3)
+        await store.upsert_batch(
+            "memories",
+            ["m1", "m2"],
+            [_vec(), _vec()],
+            [{"content": "prefer dataclasses"}, {"content": "prefer enums"}],
+        )
+
+        await store.store.delete("memories", "m1")
+        await store.store.upsert("memories", "m1", _vec(), {"content": "avoid globals"})
+        await store.store.delete("memories", "m2")
+        await store.store.upsert("memories", "m3", _vec(), {"content": "prefer enums"})
+
+        results = await store.keyword_search("memories", "prefer")
+        assert [r.id for r in results] == ["m3"]
+        assert await index.document("memories", "m1") == "avoid globals"
+        assert await index.document("memories", "m2") is None
+
+    async def test_rebuild_pages_through_collection(
+        self, index: KeywordIndex
+    ) -> None:
+        """Test that a backfill indexes collections larger than one page."""
+        inner = MemoryStore()
+        await inner.create_collection("memories", dimension=3)
+        await inner.upsert_batch(
+            "memories",
+            [f"m{i}" for i in range(600)],
+            [_vec()] * 600,
+            [{"content": f"note {i}"} for i in range(600)],
+        )
+
+        store = KeywordIndexedStore(inner, index)
+        assert await store.rebuild("memories") == 600
+        assert await index.indexed_count("memories") == 600
+
+    async def test_finds_documents_beyond_scroll_cap(
+        self, store: KeywordIndexedStore
+    ) -> None:
+        """Test that keyword search covers collections over 1000 points."""
+        ids = [f"u{i}" for i in range(1200)]
+        payloads = [{"qualified_name": f"filler_{i}"} for i in range(1199)]
+        payloads.append({"qualified_name": "needle_in_haystack"})
+        await store.upsert_batch("code_units", ids, [_vec()] * 1200, payloads)
+
+        results = await store.keyword_search("code_units", "needle")
+        assert [r.id for r in results] == ["u1199"]
+
+    async def test_delete_collection_drops_index(
+        self, store: KeywordIndexedStore, index: KeywordIndex
+    ) -> None:
+        """Test that deleting a collection clears its postings."""
+        await store.upsert("code_units", "a", _vec(), {"qualified_name": "parse"})
+        await store.delete_collection("code_units")
+
+        assert await index.indexed_count("code_units") is None
+        assert await index.search("code_units", "parse") == []
@@ -3,7 +3,7 @@
 import numpy as np
 import pytest
 
-from calm.storage import MemoryStore
+from calm.storage import FilterError, MemoryStore
 
 
 @pytest.fixture
@@ -49,6 +49,37 @@ class TestMemoryStore:
         with pytest.raises(ValueError, match="not found"):
             await store.count("test")
 
+    async def test_swap_collection(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test that a swap replaces a collection's points and dimension."""
+        await store.upsert(
+            collection, "old", np.array([1.0, 0.0, 0.0], dtype=np.float32), {}
+        )
+        await store.create_collection("shadow", dimension=2)
+        await store.upsert(
+            "shadow", "new", np.array([0.0, 1.0], dtype=np.float32), {"n": 1}
+        )
+
+        await store.swap_collection(collection, "shadow")
+
+        info = await store.get_collection_info(collection)
+        assert info is not None
+        assert info.dimension == 2
+        assert await store.get(collection, "old") is None
+        result = await store.get(collection, "new")
+        assert result is not None
+        assert result.payload == {"n": 1}
+        assert await store.get_collection_info("shadow") is None
+
+    async def test_swap_into_missing_collection(self, store: MemoryStore) -> None:
+        """Test that swapping can create the target name."""
+        await store.create_collection("shadow", dimension=3)
+
+        await store.swap_collection("fresh", "shadow")
+
+        assert await store.count("fresh") == 0
+
     async def test_upsert_and_get(
         self, store: MemoryStore, collection: str
     ) -> None:
@@ -95,6 +126,39 @@ class TestMemoryStore:
         with pytest.raises(ValueError, match="dimension"):
             await store.upsert(collection, "id1", vector, {})
 
+    async def test_upsert_batch(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test inserting many vectors at once."""
+        vectors = [
+            np.array([1.0, 0.0, 0.0], dtype=np.float32),
+            np.array([0.0, 1.0, 0.0], dtype=np.float32),
+        ]
+        await store.upsert_batch(
+            collection, ["id1", "id2"], vectors, [{"n": 1}, {"n": 2}]
+        )
+
+        assert await store.count(collection) == 2
+        result = await store.get(collection, "id2", with_vector=True)
+        assert result is not None
+        assert result.payload == {"n": 2}
+        np.testing.assert_array_equal(result.vector, vectors[1])
+
+    async def test_upsert_batch_validates_before_writing(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test that a bad vector leaves the whole batch unapplied."""
+        vectors = [
+            np.array([1.0, 0.0, 0.0], dtype=np.float32),
+            np.array([1.0, 0.0], dtype=np.float32),
+        ]
+        with pytest.raises(ValueError, match="dimension"):
+            await store.upsert_batch(collection, ["id1", "id2"], vectors, [{}, {}])
+        assert await store.count(collection) == 0
+
+        with pytest.raises(ValueError, match="same length"):
+            await store.upsert_batch(collection, ["id1"], vectors, [{}])
+
     async def test_search_cosine_similarity(
         self, store: MemoryStore, collection: str
     ) -> None:
@@ -144,6 +208,54 @@ class TestMemoryStore:
         assert len(results) == 2
         assert all(r.payload["category"] == "A" for r in results)
 
+    async def test_search_with_operator_filters(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test $in and range operators in search filters."""
+        for i in range(5):
+            vector = np.array([1.0, float(i), 0.0], dtype=np.float32)
+            language = ["python", "rust", "go"][i % 3]
+            await store.upsert(
+                collection, f"id{i}", vector, {"rank": i, "language": language}
+            )
+
+        query = np.array([1.0, 0.0, 0.0], dtype=np.float32)
+        results = await store.search(
+            collection,
+            query,
+            filters={"language": {"$in": ["python", "go"]}, "rank": {"$gte": 1}},
+        )
+        assert sorted(r.id for r in results) == ["id2", "id3"]
+
+        results = await store.search(
+            collection, query, filters={"rank": {"$gt": 0, "$lte": 2}}
+        )
+        assert sorted(r.id for r in results) == ["id1", "id2"]
+
+        assert await store.count(collection, filters={"rank": {"$lt": 2}}) == 2
+
+    async def test_search_with_logical_filters(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test $or, $not, $exists and $contains in search filters."""
+        vector = np.array([1.0, 0.0, 0.0], dtype=np.float32)
+        await store.upsert(collection, "a", vector, {"tags": ["ffi"], "doc": "x"})
+        await store.upsert(collection, "b", vector, {"tags": ["cli"], "doc": None})
+        await store.upsert(collection, "c", vector, {"tags": []})
+
+        async def ids(filters: dict) -> list[str]:
+            results = await store.search(collection, vector, filters=filters)
+            return sorted(r.id for r in results)
+
+        assert await ids({"tags": {"$contains": "ffi"}}) == ["a"]
+        assert await ids({"$or": [{"tags": "cli"}, {"doc": "x"}]}) == ["a", "b"]
+        assert await ids({"$not": {"tags": "ffi"}}) == ["b", "c"]
+        assert await ids({"doc": {"$exists": True}}) == ["a"]
+        assert await ids({"tags": {"$nin": ["ffi"]}}) == ["b", "c"]
+
+        with pytest.raises(FilterError, match="Unsupported operator"):
+            await store.search(collection, vector, filters={"doc": {"$like": "x"}})
+
     async def test_delete(self, store: MemoryStore, collection: str) -> None:
         """Test deleting a vector."""
         vector = np.array([1.0, 2.0, 3.0], dtype=np.float32)
@@ -170,6 +282,19 @@ class TestMemoryStore:
         assert len(results) == 2
         assert all(r.vector is not None for r in results)
 
+    async def test_scroll_batches(self, store: MemoryStore, collection: str) -> None:
+        """Test paging through a whole collection in bounded batches."""
+        for i in range(5):
+            vector = np.array([float(i), 0.0, 0.0], dtype=np.float32)
+            await store.upsert(collection, f"id{i}", vector, {"index": i})
+
+        batches = [
+            batch async for batch in store.scroll_batches(collection, batch_size=2)
+        ]
+        assert [len(batch) for batch in batches] == [2, 2, 1]
+        ids = {r.id for batch in batches for r in batch}
+        assert ids == {f"id{i}" for i in range(5)}
+
     async def test_scroll_with_filters(
         self, store: MemoryStore, collection: str
     ) -> None:
@@ -183,6 +308,17 @@ class TestMemoryStore:
         assert len(results) == 3  # indices 0, 2, 4
         assert all(r.payload["category"] == "A" for r in results)
 
+    async def test_scroll_filter_matches_list_element(
+        self, store: MemoryStore, collection: str
+    ) -> None:
+        """Test that equality filters match any element of a list payload."""
+        vector = np.array([1.0, 0.0, 0.0], dtype=np.float32)
+        await store.upsert(collection, "id0", vector, {"tags": ["derive", "cfg"]})
+        await store.upsert(collection, "id1", vector, {"tags": ["test"]})
+
+        results = await store.scroll(collection, filters={"tags": "cfg"})
+        assert [r.id for r in results] == ["id0"]
+
     async def test_count(self, store: MemoryStore, collection: str) -> None:
         """Test counting vectors."""
         # Empty collection
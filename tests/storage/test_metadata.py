@@ -7,6 +7,7 @@ from pathlib import Path
 import pytest
 
 from calm.storage import MetadataStore
+from calm.storage.metadata import CallSite, CodeSymbol
 
 
 @pytest.fixture
@@ -270,6 +271,89 @@ class TestProjects:
         assert len(files) == 0
 
 
+class TestCallGraph:
+    """Tests for code symbols, call sites, and call graph edges."""
+
+    async def test_replace_file_symbols(self, metadata_store: MetadataStore) -> None:
+        """Test that symbols and call sites are replaced per file."""
+        await metadata_store.replace_file_symbols(
+            "/project/a.py",
+            "test-project",
+            [CodeSymbol("a.main", "main", "function", "/project/a.py")],
+            [CallSite("a.main", "helper", "/project/a.py")],
+        )
+        await metadata_store.replace_file_symbols(
+            "/project/a.py",
+            "test-project",
+            [CodeSymbol("a.run", "run", "function", "/project/a.py")],
+            [],
+        )
+
+        symbols = await metadata_store.list_symbols("test-project")
+        assert [s.qualified_name for s in symbols] == ["a.run"]
+        assert await metadata_store.list_call_sites("test-project") == []
+
+    async def test_get_callers_and_callees(
+        self, metadata_store: MetadataStore
+    ) -> None:
+        """Test querying call graph edges in both directions."""
+        await metadata_store.replace_call_graph(
+            "test-project",
+            [
+                ("a.main", "b.helper", "/project/a.py", "/project/b.py"),
+                ("a.main", "b.helper", "/project/a.py", "/project/b.py"),
+                ("a.run", "b.helper", "/project/a.py", "/project/b.py"),
+            ],
+        )
+
+        callees = await metadata_store.get_callees("a.main", "test-project")
+        assert [e.callee_qualified_name for e in callees] == ["b.helper"]
+        assert callees[0].callee_file == "/project/b.py"
+
+        callers = await metadata_store.get_callers("b.helper", "test-project")
+        assert [e.caller_qualified_name for e in callers] == ["a.main", "a.run"]
+
+        assert await metadata_store.get_callers("b.helper", "other") == []
+
+    async def test_delete_file_symbols(self, metadata_store: MetadataStore) -> None:
+        """Test that deleting a file drops its symbols and outgoing edges."""
+        await metadata_store.replace_file_symbols(
+            "/project/a.py",
+            "test-project",
+            [CodeSymbol("a.main", "main", "function", "/project/a.py")],
+            [CallSite("a.main", "helper", "/project/a.py")],
+        )
+        await metadata_store.replace_call_graph(
+            "test-project",
+            [("a.main", "b.helper", "/project/a.py", "/project/b.py")],
+        )
+
+        await metadata_store.delete_file_symbols("/project/a.py", "test-project")
+
+        assert await metadata_store.list_symbols("test-project") == []
+        assert await metadata_store.list_call_sites("test-project") == []
+        assert await metadata_store.get_callees("a.main", "test-project") == []
+
+
+class TestCollectionEmbeddings:
+    """Test recording the embedding model of vector collections."""
+
+    async def test_set_and_get(self, metadata_store: MetadataStore) -> None:
+        """Test that the latest model is recorded per collection."""
+        assert await metadata_store.get_collection_embedding("memories") is None
+
+        await metadata_store.set_collection_embedding("memories", "model-a", 384)
+        await metadata_store.set_collection_embedding("memories", "model-b", 768)
+        await metadata_store.set_collection_embedding("commits", "model-a", 384)
+
+        recorded = await metadata_store.get_collection_embedding("memories")
+        assert recorded is not None
+        assert (recorded.model, recorded.dimension) == ("model-b", 768)
+        assert [
+            e.collection for e in await metadata_store.list_collection_embeddings()
+        ] == ["commits", "memories"]
+
+
 class TestJSONSerialization:
     """Tests for JSON round-trip in project settings."""
 
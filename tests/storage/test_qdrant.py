@@ -188,6 +188,19 @@ class TestQdrantVectorStore:
         assert len(results) == 2
         assert all(r.vector is not None for r in results)
 
+    async def test_scroll_batches(self, store: QdrantVectorStore, collection: str) -> None:
+        """Test paging through a whole collection in bounded batches."""
+        for i in range(5):
+            vector = np.array([float(i), 0.0, 0.0], dtype=np.float32)
+            await store.upsert(collection, f"id{i}", vector, {"index": i})
+
+        batches = [
+            batch async for batch in store.scroll_batches(collection, batch_size=2)
+        ]
+        assert [len(batch) for batch in batches] == [2, 2, 1]
+        ids = {r.id for batch in batches for r in batch}
+        assert ids == {f"id{i}" for i in range(5)}
+
     async def test_scroll_with_filters(
         self, store: QdrantVectorStore, collection: str
     ) -> None:
@@ -232,6 +245,39 @@ class TestQdrantVectorStore:
         result = await store.get(collection, "nonexistent")
         assert result is None
 
+    async def test_swap_collection_uses_alias(self, store: QdrantVectorStore) -> None:
+        """Test that a plain collection is served through an alias once swapped."""
+        try:
+            await store.create_collection("test_swap", dimension=3)
+            await store.upsert("test_swap", "a", np.ones(3, dtype=np.float32), {})
+            assert "test_swap" not in await store._aliases()
+
+            for shadow, dimension in (("test_swap_v2", 2), ("test_swap_v3", 4)):
+                await store.create_collection(shadow, dimension=dimension)
+                await store.upsert(
+                    shadow,
+                    "a",
+                    np.ones(dimension, dtype=np.float32),
+                    {"shadow": shadow},
+                )
+
+                await store.swap_collection("test_swap", shadow)
+
+                assert (await store._aliases())["test_swap"] == shadow
+                info = await store.get_collection_info("test_swap")
+                assert info is not None
+                assert info.dimension == dimension
+                result = await store.get("test_swap", "a")
+                assert result is not None
+                assert result.payload == {"shadow": shadow}
+
+            # The first replacement was deleted by the second swap
+            assert await store.get_collection_info("test_swap_v2") is None
+            with pytest.raises(ValueError):
+                await store.create_collection("test_swap", dimension=3)
+        finally:
+            await store.delete_collection("test_swap")
+
     async def test_distance_metrics(self, store: QdrantVectorStore) -> None:
         """Test different distance metrics."""
         for distance in ["cosine", "euclidean", "dot"]:
@@ -1,7 +1,9 @@
 """Tests for QdrantVectorStore filter operators."""
 
+import pytest
 from qdrant_client.http import models as qmodels
 
+from calm.storage.filters import FilterError
 from calm.storage.qdrant import QdrantVectorStore
 
 
@@ -116,3 +118,74 @@ class TestQdrantFilterBuilding:
 
         # Check $gte filter
         assert conditions["importance"].range.gte == 0.3
+
+    def test_or_becomes_nested_should(self):
+        """Test $or translates to a nested should filter."""
+        store = QdrantVectorStore()
+        filters = {
+            "project": "clams",
+            "$or": [{"language": "rust"}, {"tags": {"$contains": "ffi"}}],
+        }
+        qdrant_filter = store._build_filter(filters)
+
+        assert len(qdrant_filter.must) == 2
+        nested = qdrant_filter.must[1]
+        assert isinstance(nested, qmodels.Filter)
+        assert len(nested.should) == 2
+        assert nested.should[0].must[0].match.value == "rust"
+        assert nested.should[1].must[0].must[0].key == "tags"
+
+    def test_not_and_nin_become_must_not(self):
+        """Test $not and $nin translate to nested must_not filters."""
+        store = QdrantVectorStore()
+        filters = {
+            "$not": {"unit_type": "class"},
+            "language": {"$nin": ["sql", "cpp"]},
+        }
+        qdrant_filter = store._build_filter(filters)
+
+        not_filter, nin_filter = qdrant_filter.must
+        assert not_filter.must_not[0].must[0].key == "unit_type"
+        assert isinstance(nin_filter.must_not[0].match, qmodels.MatchAny)
+        assert nin_filter.must_not[0].match.any == ["sql", "cpp"]
+
+    def test_exists_uses_is_empty(self):
+        """Test $exists translates to (negated) IsEmptyCondition."""
+        store = QdrantVectorStore()
+        qdrant_filter = store._build_filter(
+            {"docstring": {"$exists": True}, "crate": {"$exists": False}}
+        )
+
+        exists, missing = qdrant_filter.must
+        assert exists.must_not[0].is_empty.key == "docstring"
+        assert isinstance(missing, qmodels.IsEmptyCondition)
+        assert missing.is_empty.key == "crate"
+
+    def test_contains_all_and_nested_field(self):
+        """Test $contains with several values and dotted field keys."""
+        store = QdrantVectorStore()
+        qdrant_filter = store._build_filter(
+            {"tags": {"$contains": ["a", "b"]}, "meta.language": "rust"}
+        )
+
+        contains, nested_field = qdrant_filter.must
+        assert [c.match.value for c in contains.must] == ["a", "b"]
+        assert nested_field.key == "meta.language"
+
+    def test_unsupported_operator_raises(self):
+        """Test unknown operators raise instead of degrading to equality."""
+        store = QdrantVectorStore()
+
+        with pytest.raises(FilterError, match=r"\$regex"):
+            store._build_filter({"name": {"$regex": "^get_"}})
+        with pytest.raises(FilterError, match=r"\$and"):
+            store._build_filter({"$and": [{"a": 1}]})
+
+    def test_glob_becomes_substring_matches(self):
+        """Test $glob requires each literal part of the pattern."""
+        store = QdrantVectorStore()
+        qdrant_filter = store._build_filter({"file_path": {"$glob": "src/**/*.rs"}})
+
+        (glob,) = qdrant_filter.must
+        assert [c.match.text for c in glob.must] == ["src/", ".rs"]
+        assert all(c.key == "file_path" for c in glob.must)
@@ -0,0 +1,130 @@
+"""Tests for SqliteVectorStore."""
+
+from collections.abc import AsyncIterator
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+from calm.storage import SqliteVectorStore
+from tests.storage import test_memory
+
+
+@pytest.fixture
+async def store(tmp_path: Path) -> AsyncIterator[SqliteVectorStore]:
+    """Create a store backed by a temporary database."""
+    store = SqliteVectorStore(tmp_path / "vectors.db")
+    yield store
+    await store.close()
+
+
+@pytest.fixture
+async def collection(store: SqliteVectorStore) -> str:
+    """Create a test collection."""
+    name = "test_collection"
+    await store.create_collection(name, dimension=3)
+    return name
+
+
+class TestSqliteVectorStoreBehavior(test_memory.TestMemoryStore):
+    """Run the MemoryStore behavioral suite against SqliteVectorStore."""
+
+
+class TestSqliteVectorStore:
+    """SQLite-specific behavior."""
+
+    async def test_persists_across_instances(self, tmp_path: Path) -> None:
+        """Test that collections and points survive reopening the database."""
+        db_path = tmp_path / "vectors.db"
+        store = SqliteVectorStore(db_path)
+        await store.create_collection("code", dimension=3)
+        vector = np.array([0.5, 0.25, 1.0], dtype=np.float32)
+        await store.upsert("code", "id1", vector, {"tags": ["a"], "n": 1})
+        await store.close()
+
+        reopened = SqliteVectorStore(db_path)
+        result = await reopened.get("code", "id1", with_vector=True)
+        assert result is not None
+        assert result.payload == {"tags": ["a"], "n": 1}
+        np.testing.assert_array_equal(result.vector, vector)
+
+        info = await reopened.get_collection_info("code")
+        assert info is not None
+        assert (info.dimension, info.vector_count) == (3, 1)
+        assert await reopened.get_collection_info("missing") is None
+        await reopened.close()
+
+    async def test_search_sees_writes_after_cache_load(
+        self, store: SqliteVectorStore, collection: str
+    ) -> None:
+        """Test that writes invalidate the in-memory search matrix."""
+        query = np.array([1.0, 0.0, 0.0], dtype=np.float32)
+        await store.upsert(collection, "a", np.array([0.0, 1.0, 0.0]), {})
+        assert [r.id for r in await store.search(collection, query)] == ["a"]
+
+        await store.upsert(collection, "b", query, {})
+        results = await store.search(collection, query)
+        assert [r.id for r in results] == ["b", "a"]
+        assert results[0].score == pytest.approx(1.0)
+
+        await store.delete(collection, "b")
+        assert [r.id for r in await store.search(collection, query)] == ["a"]
+
+    async def test_writes_update_cache_in_place(
+        self, store: SqliteVectorStore, collection: str
+    ) -> None:
+        """Test that writes patch the cached collection instead of reloading."""
+        for id in "abcd":
+            await store.upsert(collection, id, np.array([1.0, 0.0, 0.0]), {"id": id})
+        await store.scroll(collection)
+        cached = store._cache[collection]
+
+        await store.upsert(collection, "b", np.array([0.0, 1.0, 0.0]), {"id": "B"})
+        await store.upsert(collection, "e", np.array([0.0, 0.0, 1.0]), {"id": "e"})
+        for id in "acd":
+            await store.delete(collection, id)
+        assert store._cache[collection] is cached
+
+        results = await store.scroll(collection, with_vectors=True)
+        assert [(r.id, r.payload) for r in results] == [
+            ("b", {"id": "B"}),
+            ("e", {"id": "e"}),
+        ]
+        np.testing.assert_array_equal(results[0].vector, [0.0, 1.0, 0.0])
+        assert await store.count(collection, filters={"id": "e"}) == 1
+
+    async def test_sees_writes_from_other_connections(self, tmp_path: Path) -> None:
+        """Test that another process's writes invalidate the cached matrix."""
+        db_path = tmp_path / "vectors.db"
+        store = SqliteVectorStore(db_path)
+        other = SqliteVectorStore(db_path)
+        query = np.array([1.0, 0.0, 0.0], dtype=np.float32)
+        try:
+            await store.create_collection("code", dimension=3)
+            await store.upsert("code", "a", np.array([0.0, 1.0, 0.0]), {})
+            assert [r.id for r in await store.search("code", query)] == ["a"]
+
+            await other.upsert("code", "b", query, {})
+            assert [r.id for r in await store.search("code", query)] == ["b", "a"]
+
+            await other.delete("code", "a")
+            assert [r.id for r in await store.search("code", query)] == ["b"]
+        finally:
+            await store.close()
+            await other.close()
+
+    async def test_dot_and_euclidean_distance(
+        self, store: SqliteVectorStore
+    ) -> None:
+        """Test scoring for non-cosine collections."""
+        await store.create_collection("dot", dimension=2, distance="dot")
+        await store.create_collection("euclid", dimension=2, distance="euclidean")
+        for name in ("dot", "euclid"):
+            await store.upsert(name, "near", np.array([1.0, 0.0]), {})
+            await store.upsert(name, "far", np.array([3.0, 0.0]), {})
+
+        query = np.array([1.0, 0.0], dtype=np.float32)
+        dot = await store.search("dot", query)
+        assert [(r.id, r.score) for r in dot] == [("far", 3.0), ("near", 1.0)]
+        euclid = await store.search("euclid", query)
+        assert [(r.id, r.score) for r in euclid] == [("near", 0.0), ("far", 2.0)]